@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use crate::rendering::atlas::SpriteRegion;
+
+use super::PlaybackMode;
+
+#[derive(Clone)]
+pub struct AnimationFrame {
+    pub region: SpriteRegion,
+    pub duration: Duration,
+}
+
+impl AnimationFrame {
+    pub fn new(region: SpriteRegion, duration: Duration) -> Self {
+        Self {
+            region,
+            duration,
+        }
+    }
+}
+
+/// Sequence of frames, each one with it's own duration.
+#[derive(Clone)]
+pub struct AnimationClip {
+    frames: Vec<AnimationFrame>,
+    mode: PlaybackMode,
+}
+
+impl AnimationClip {
+    pub fn new(frames: Vec<AnimationFrame>) -> Self {
+        Self {
+            frames,
+            mode: PlaybackMode::default(),
+        }
+    }
+
+    /// Every region is displayed for the same duration.
+    pub fn from_regions<I>(regions: I, frame_duration: Duration) -> Self where
+        I: IntoIterator<Item = SpriteRegion>
+    {
+        Self::new(
+            regions.into_iter()
+                   .map(|region| AnimationFrame::new(region, frame_duration))
+                   .collect()
+        )
+    }
+
+    pub fn with_mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&AnimationFrame> {
+        self.frames.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time to go through every frame once.
+    pub fn duration(&self) -> Duration {
+        self.frames
+            .iter()
+            .map(|frame| frame.duration)
+            .sum()
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+use miette::Diagnostic;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum AnimationError {
+    #[error("clip '{0}' not found")]
+    ClipNotFound(String),
+
+    #[error("failed to read sprite sheet '{0}': {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("failed to parse sprite sheet: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("tag '{tag}' references frame {frame}, but there are only {count} frames")]
+    FrameOutOfRange {
+        tag: String,
+        frame: usize,
+        count: usize,
+    },
+}
@@ -0,0 +1,11 @@
+/// Raised by an `Animator` while advancing, it's only kept until next frame.
+///
+/// [`Animator`]: crate::components::Animator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// A `PlaybackMode::Once` clip reached it's last frame.
+    Finished(String),
+
+    /// A looping clip started over.
+    Looped(String),
+}
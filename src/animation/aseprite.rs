@@ -0,0 +1,265 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::BufReader,
+    path::Path,
+    time::Duration,
+};
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize,
+    Deserializer,
+};
+
+use crate::{
+    math::{Rectangle, Size2, Vector2},
+    rendering::atlas::SpriteRegion,
+};
+
+use super::{
+    AnimationClip,
+    AnimationError,
+    AnimationFrame,
+    PlaybackMode,
+};
+
+/// Clips from an Aseprite sprite sheet, exported as JSON with either array or hash frames.
+///
+/// Every frame tag becomes a clip, when there is none every frame belongs to
+/// `AsepriteSheet::DEFAULT_CLIP`. Frame rects are relative to provided region,
+/// so sheet can be a whole texture or packed into an atlas.
+pub struct AsepriteSheet {
+    image: Option<String>,
+    clips: HashMap<String, AnimationClip>,
+}
+
+impl AsepriteSheet {
+    pub const DEFAULT_CLIP: &'static str = "default";
+
+    pub fn from_file<P: AsRef<Path>>(path: P, region: &SpriteRegion) -> Result<Self, AnimationError> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| AnimationError::Io(path.as_ref().to_owned(), e))?;
+
+        let data: AsepriteData = serde_json::from_reader(BufReader::new(file))?;
+
+        Self::from_data(data, region)
+    }
+
+    pub fn from_json(json: &str, region: &SpriteRegion) -> Result<Self, AnimationError> {
+        Self::from_data(serde_json::from_str(json)?, region)
+    }
+
+    /// Image file name, as exported.
+    pub fn image(&self) -> Option<&str> {
+        self.image.as_deref()
+    }
+
+    pub fn clip<S: AsRef<str>>(&self, name: S) -> Option<&AnimationClip> {
+        self.clips.get(name.as_ref())
+    }
+
+    pub fn clips(&self) -> &HashMap<String, AnimationClip> {
+        &self.clips
+    }
+
+    pub fn into_clips(self) -> HashMap<String, AnimationClip> {
+        self.clips
+    }
+
+    fn from_data(data: AsepriteData, region: &SpriteRegion) -> Result<Self, AnimationError> {
+        let frames: Vec<_> = data.frames.0
+            .iter()
+            .map(|frame| AnimationFrame::new(
+                region.sub_region(Rectangle::new(
+                    Vector2::new(frame.frame.x, frame.frame.y),
+                    Size2::new(frame.frame.w, frame.frame.h),
+                )),
+                Duration::from_millis(frame.duration),
+            ))
+            .collect();
+
+        let mut clips = HashMap::new();
+
+        if data.meta.frame_tags.is_empty() {
+            clips.insert(Self::DEFAULT_CLIP.to_owned(), AnimationClip::new(frames));
+        } else {
+            for tag in data.meta.frame_tags {
+                let range = frames
+                    .get(tag.from..=tag.to)
+                    .ok_or_else(|| AnimationError::FrameOutOfRange {
+                        tag: tag.name.clone(),
+                        frame: tag.to,
+                        count: frames.len(),
+                    })?;
+
+                let mut clip_frames = range.to_vec();
+
+                if matches!(tag.direction, AsepriteDirection::Reverse | AsepriteDirection::PingpongReverse) {
+                    clip_frames.reverse();
+                }
+
+                clips.insert(tag.name.clone(), AnimationClip::new(clip_frames).with_mode(tag.mode()));
+            }
+        }
+
+        Ok(Self {
+            image: data.meta.image,
+            clips,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct AsepriteData {
+    frames: AsepriteFrames,
+    meta: AsepriteMeta,
+}
+
+/// Frames in file order, for both array and hash exports.
+struct AsepriteFrames(Vec<AsepriteFrame>);
+
+#[derive(Deserialize)]
+struct AsepriteFrame {
+    frame: AsepriteRect,
+
+    /// In milliseconds.
+    duration: u64,
+}
+
+#[derive(Deserialize)]
+struct AsepriteRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteMeta {
+    image: Option<String>,
+
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+
+    #[serde(default)]
+    direction: AsepriteDirection,
+
+    /// How many times it's played, missing or zero means forever.
+    #[serde(default)]
+    repeat: Option<String>,
+}
+
+impl AsepriteTag {
+    fn mode(&self) -> PlaybackMode {
+        match self.direction {
+            AsepriteDirection::Pingpong | AsepriteDirection::PingpongReverse => PlaybackMode::PingPong,
+            _ if self.repeat.as_deref() == Some("1") => PlaybackMode::Once,
+            _ => PlaybackMode::Loop,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum AsepriteDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Pingpong,
+    PingpongReverse,
+}
+
+impl<'de> Deserialize<'de> for AsepriteFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = AsepriteFrames;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array or map of frames")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+
+                while let Some(frame) = seq.next_element()? {
+                    frames.push(frame);
+                }
+
+                Ok(AsepriteFrames(frames))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::with_capacity(map.size_hint().unwrap_or_default());
+
+                while let Some((_, frame)) = map.next_entry::<String, _>()? {
+                    frames.push(frame);
+                }
+
+                Ok(AsepriteFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rendering::{Texture, TextureFormat}, resources::Asset};
+
+    use super::*;
+
+    const SHEET: &str = r#"{
+        "frames": {
+            "hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+            "hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 150 },
+            "hero 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+            "hero 10.aseprite": { "frame": { "x": 48, "y": 0, "w": 16, "h": 16 }, "duration": 200 }
+        },
+        "meta": {
+            "image": "hero.png",
+            "frameTags": [
+                { "name": "walk", "from": 0, "to": 2, "direction": "forward" },
+                { "name": "idle", "from": 2, "to": 3, "direction": "reverse", "repeat": "1" },
+                { "name": "wave", "from": 1, "to": 3, "direction": "pingpong" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn clips_from_tags() {
+        let texture = Asset::new(Texture::new(TextureFormat::Rgba8UnormSrgb, Size2::new(64, 16), &[0; 64 * 16 * 4]));
+        let sheet = AsepriteSheet::from_json(SHEET, &SpriteRegion::full(&texture)).unwrap();
+
+        assert_eq!(sheet.image(), Some("hero.png"));
+        assert_eq!(sheet.clips().len(), 3);
+
+        let walk = sheet.clip("walk").unwrap();
+        assert_eq!(walk.mode(), PlaybackMode::Loop);
+        assert_eq!(walk.duration(), Duration::from_millis(350));
+        assert_eq!(walk.frame(1).unwrap().region.rect().left(), 16);
+
+        let idle = sheet.clip("idle").unwrap();
+        assert_eq!(idle.mode(), PlaybackMode::Once);
+        assert_eq!(idle.frame(0).unwrap().region.rect().left(), 48);
+
+        assert_eq!(sheet.clip("wave").unwrap().mode(), PlaybackMode::PingPong);
+
+        let out_of_range = SHEET.replace(r#""to": 3, "direction": "pingpong""#, r#""to": 4, "direction": "pingpong""#);
+        assert!(matches!(
+            AsepriteSheet::from_json(&out_of_range, &SpriteRegion::full(&texture)),
+            Err(AnimationError::FrameOutOfRange { .. })
+        ));
+    }
+}
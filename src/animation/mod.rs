@@ -0,0 +1,16 @@
+//! Flipbook animations, where each frame is a region of a texture or atlas.
+
+mod animation_clip;
+pub use animation_clip::{AnimationClip, AnimationFrame};
+
+mod animation_error;
+pub use animation_error::AnimationError;
+
+mod animation_event;
+pub use animation_event::AnimationEvent;
+
+mod aseprite;
+pub use aseprite::AsepriteSheet;
+
+mod playback_mode;
+pub use playback_mode::PlaybackMode;
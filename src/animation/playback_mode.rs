@@ -0,0 +1,13 @@
+/// What happens when a clip reaches it's last frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaybackMode {
+    /// Stops at last frame.
+    Once,
+
+    /// Starts over from first frame.
+    #[default]
+    Loop,
+
+    /// Plays backwards until first frame, then forwards again.
+    PingPong,
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rendering::backend::{BackendPreference, PresentMode},
+    window::FullscreenMode,
+};
+
+use super::AppConfigError;
+
+/// Application and main window settings.
+///
+/// Every field is optional when loading from a file, missing ones use their default value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub title: String,
+
+    /// Initial window size, in logical pixels.
+    pub size: (u32, u32),
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+
+    pub resizable: bool,
+    pub decorations: bool,
+    pub fullscreen: FullscreenMode,
+
+    pub present_mode: PresentMode,
+    pub backend: BackendPreference,
+}
+
+impl AppConfig {
+    /// Loads from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, AppConfigError> {
+        let file = File::open(path)?;
+
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(AppConfigError::from)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, AppConfigError> {
+        serde_json::from_str(json)
+            .map_err(AppConfigError::from)
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            title: env!("CARGO_PKG_NAME").to_owned(),
+            size: (320, 180),
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            decorations: true,
+            fullscreen: FullscreenMode::default(),
+            present_mode: PresentMode::default(),
+            backend: BackendPreference::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_json() {
+        let config = AppConfig::from_json(r#"{
+            "title": "Game",
+            "size": [640, 360],
+            "fullscreen": "borderless",
+            "present_mode": "mailbox"
+        }"#).unwrap();
+
+        assert_eq!(config.title, "Game");
+        assert_eq!(config.size, (640, 360));
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless);
+        assert_eq!(config.present_mode, PresentMode::Mailbox);
+        assert_eq!(config.backend, BackendPreference::Primary);
+        assert!(config.resizable);
+    }
+}
@@ -0,0 +1,12 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum AppConfigError {
+    #[error("failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse config: {0}")]
+    Parse(#[from] serde_json::Error),
+}
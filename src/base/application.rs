@@ -1,8 +1,7 @@
 use std::error::Error;
 
-use crate::window::WindowContext;
-
 use super::{
+    AppConfig,
     ApplicationError,
     ApplicationLoop,
     ApplicationState,
@@ -12,26 +11,55 @@ use super::{
 
 #[derive(Default)]
 pub struct Application {
+    config: AppConfig,
     init_fn: Option<InitFn>,
 }
 
 impl Application {
+    pub fn with_config(config: AppConfig) -> Self {
+        Self {
+            config,
+            init_fn: None,
+        }
+    }
+
     pub fn run<L: 'static + ApplicationLoop>(&mut self) -> Result<(), ApplicationError> {
-        Self::display_header();
+        let loop_control = L::new(self.config.clone())?;
+        self.run_with(loop_control)
+    }
 
-        let loop_control = L::new(
-                WindowContext::new()
-                    .map_err(ApplicationError::WindowCreationFailed)?
-            );
+    /// Runs an already configured loop, such as a `HeadlessLoop` with max frames
+    /// or a stop condition, which `run` can't set up from `AppConfig` alone.
+    pub fn run_with<L: 'static + ApplicationLoop>(&mut self, loop_control: L) -> Result<(), ApplicationError> {
+        Self::display_header();
 
         loop_control.run(self.init_fn.take())?;
 
         Ok(())
     }
 
+    /// Init which needs a window, it fails when running headless.
+    /// See [`initialize_headless_with`] for one that runs in both.
+    ///
+    /// [`initialize_headless_with`]: Self::initialize_headless_with
     pub fn initialize_with<E, F>(mut self, init_fn: F) -> Self where
         E: 'static + Error,
         F: 'static + FnOnce(&mut ApplicationState, InitState) -> Result<(), E>,
+    {
+        self.init_fn = Some(Box::new(|app_state, init_state| {
+            match app_state {
+                Some(app_state) => init_fn(app_state, init_state).map_err(Into::into),
+                None => Err(ApplicationError::WindowRequired.into()),
+            }
+        }));
+
+        self
+    }
+
+    /// Init which also runs headless, where `ApplicationState` is `None`.
+    pub fn initialize_headless_with<E, F>(mut self, init_fn: F) -> Self where
+        E: 'static + Error,
+        F: 'static + FnOnce(Option<&mut ApplicationState>, InitState) -> Result<(), E>,
     {
         self.init_fn = Some(Box::new(|app_state, init_state| {
             init_fn(app_state, init_state).map_err(Into::into)
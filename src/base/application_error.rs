@@ -14,5 +14,8 @@ pub enum ApplicationError {
     WindowError(#[from] WindowError),
 
     #[error("init failed to execute")]
-    InitFailed(Box<dyn std::error::Error>)
+    InitFailed(Box<dyn std::error::Error>),
+
+    #[error("init requires a window, but it's running headless")]
+    WindowRequired,
 }
@@ -1,24 +1,28 @@
 use crate::{
-    base::{ApplicationState, ApplicationError, InitState},
+    base::{AppConfig, ApplicationState, ApplicationError, InitState},
+    diag::Diagnostics,
     ecs::{
+        command::Commands,
         entity::Entities,
+        resource::Resources,
         FrameState,
         SchedulerStep,
         SystemScheduler,
     },
-    input::Event,
-    rendering::GraphicAdapter,
-    time::Time,
+    input::{Event, Input},
+    rendering::{backend::RenderBackendBuilder, GraphicAdapter},
+    time::{FixedTime, FrameRate, Time},
     window::WindowContext,
 };
 
-use super::{ApplicationLoop, InitFn};
-
-const WINDOW_SIZE: [u32; 2] = [320, 180];
+use super::{register_default_systems, run_fixed_update, ApplicationLoop, InitFn};
 
 pub struct ContinuousLoop {
     window_context: WindowContext,
+    config: AppConfig,
     entities: Entities,
+    commands: Commands,
+    resources: Resources,
     sys_scheduler: SystemScheduler,
 }
 
@@ -26,24 +30,53 @@ impl ContinuousLoop {
 }
 
 impl ApplicationLoop for ContinuousLoop {
-    fn new(window_context: WindowContext) -> Self {
-         Self {
+    fn new(config: AppConfig) -> Result<Self, ApplicationError> {
+        let window_context = WindowContext::new()
+            .map_err(ApplicationError::WindowCreationFailed)?;
+
+        let mut resources = Resources::new();
+        resources.insert_resource(Time::new());
+        resources.insert_resource(FixedTime::default());
+        resources.insert_resource(FrameRate::default());
+        resources.insert_resource(Input::default());
+        resources.insert_resource(Diagnostics::default());
+
+        let entities = Entities::new();
+        let mut sys_scheduler = SystemScheduler::new();
+        register_default_systems(&mut sys_scheduler);
+
+        Ok(Self {
             window_context,
-            entities: Entities::new(),
-            sys_scheduler: SystemScheduler::new(),
-        }
+            config,
+            commands: Commands::new(&entities),
+            entities,
+            resources,
+            sys_scheduler,
+        })
     }
 
     fn run(mut self, init_fn: Option<InitFn>) -> Result<(), ApplicationError> {
         let (logical_window_size, physical_window_size)
-            = self.window_context.calculate_window_size(
-                (WINDOW_SIZE[0], WINDOW_SIZE[1])
-            );
+            = self.window_context.calculate_window_size(self.config.size);
 
         // window
-        let window = self.window_context
+        let mut window_builder = self.window_context
             .create_window()
+            .with_title(self.config.title.as_str())
             .with_inner_size(logical_window_size)
+            .with_resizable(self.config.resizable)
+            .with_decorations(self.config.decorations)
+            .with_fullscreen(self.config.fullscreen);
+
+        if let Some(min_size) = self.config.min_size {
+            window_builder = window_builder.with_min_inner_size(self.window_context.calculate_window_size(min_size).0);
+        }
+
+        if let Some(max_size) = self.config.max_size {
+            window_builder = window_builder.with_max_inner_size(self.window_context.calculate_window_size(max_size).0);
+        }
+
+        let window = window_builder
             .build()
             .unwrap();
 
@@ -53,9 +86,14 @@ impl ApplicationLoop for ContinuousLoop {
         let mut last_input_instant = Time::now();
 
         // rendering
-        let graphic_adapter = GraphicAdapter::with_surface_size(
-                &window,
-                (physical_window_size.width, physical_window_size.height),
+        let graphic_adapter = GraphicAdapter::from_builder(
+                RenderBackendBuilder::new(
+                    &window,
+                    (physical_window_size.width, physical_window_size.height),
+                )
+                .with_app_name(self.config.title.as_str())
+                .with_present_mode(self.config.present_mode)
+                .with_backend(self.config.backend)
             )
             .unwrap();
 
@@ -71,12 +109,13 @@ impl ApplicationLoop for ContinuousLoop {
         // run provided init fn
         if let Some(init) = init_fn {
             let init_state = InitState {
-                window_context: &mut self.window_context,
+                window_context: Some(&mut self.window_context),
                 system_scheduler: &mut self.sys_scheduler,
                 entities: &mut self.entities,
+                resources: &mut self.resources,
             };
 
-            init(&mut state, init_state)
+            init(Some(&mut state), init_state)
                 .map_err(ApplicationError::InitFailed)?;
         }
 
@@ -93,44 +132,62 @@ impl ApplicationLoop for ContinuousLoop {
                             state.graphic_adapter.borrow_mut().request_resize_surface(dims.width, dims.height);
                         },
                         winit::event::WindowEvent::RedrawRequested => {
-                            let delta_time = state.time.delta(&mut last_render_instant);
-                            state.diagnostics.draw_calls = 0; // TODO  handle this line properly
+                            let delta_time = self.resources.get::<Time>().unwrap().delta(&mut last_render_instant);
+                            {
+                                let mut diagnostics = self.resources.get_mut::<Diagnostics>().unwrap();
+                                diagnostics.register_frame(delta_time.duration);
+                                diagnostics.draw_calls = 0; // TODO  handle this line properly
+                            }
+
                             let mut frame_state = FrameState {
                                 delta: delta_time,
-                                app: &mut state,
+                                app: Some(&mut state),
+                                commands: &mut self.commands,
                             };
 
                             let render_timer_instant = Time::now();
-                            self.sys_scheduler.run(&SchedulerStep::Render, &self.entities, &mut frame_state);
-                            state.diagnostics.render_timer = Time::now() - render_timer_instant;
+                            self.sys_scheduler.run(&SchedulerStep::Render, &mut self.entities, &self.resources, &mut frame_state);
+                            self.resources.get_mut::<Diagnostics>().unwrap().render_timer = Time::now() - render_timer_instant;
                         },
                         _ => {
-                            state.input.handle(Event::from(win_event));
+                            self.resources.get_mut::<Input>().unwrap().handle(Event::from(win_event));
 
-                            let delta_time = state.time.delta(&mut last_input_instant);
+                            let delta_time = self.resources.get::<Time>().unwrap().delta(&mut last_input_instant);
                             let mut frame_state = FrameState {
                                 delta: delta_time,
-                                app: &mut state,
+                                app: Some(&mut state),
+                                commands: &mut self.commands,
                             };
 
-                            self.sys_scheduler.run(&SchedulerStep::Input, &self.entities, &mut frame_state);
+                            self.sys_scheduler.run(&SchedulerStep::Input, &mut self.entities, &self.resources, &mut frame_state);
                         },
                     }
                 },
                 winit::event::Event::AboutToWait => {
-                    // TODO  set max wait time to be able to change framerate
-                    let delta_time = state.time.delta(&mut last_update_instant);
+                    let delta_time = self.resources.get::<Time>().unwrap().delta(&mut last_update_instant);
                     let mut frame_state = FrameState {
                         delta: delta_time,
-                        app: &mut state,
+                        app: Some(&mut state),
+                        commands: &mut self.commands,
                     };
 
                     let update_timer_instant = Time::now();
 
-                    self.sys_scheduler.run(&SchedulerStep::Update, &self.entities, &mut frame_state);
+                    run_fixed_update(&mut self.sys_scheduler, &mut self.entities, &self.resources, &mut frame_state);
+
+                    self.sys_scheduler.run(&SchedulerStep::Update, &mut self.entities, &self.resources, &mut frame_state);
 
-                    state.diagnostics.update_timer = Time::now() - update_timer_instant;
-                    state.main_window.request_redraw();
+                    self.resources.get_mut::<Diagnostics>().unwrap().update_timer = Time::now() - update_timer_instant;
+
+                    // frame cap, sleeps until next frame is due
+                    let frame_rate = self.resources.get::<FrameRate>().unwrap();
+
+                    if frame_rate.is_due(last_render_instant, Time::now()) {
+                        state.main_window.request_redraw();
+                        event_handler.poll();
+                    } else if let Some(next_frame) = frame_rate.next_frame(last_render_instant) {
+                        event_handler.wait_until(next_frame);
+                    }
                 },
                 _ => ()
             }
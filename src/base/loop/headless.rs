@@ -0,0 +1,386 @@
+use std::{thread, time::Duration};
+
+use crate::{
+    base::{AppConfig, ApplicationError, InitState},
+    diag::Diagnostics,
+    ecs::{
+        command::Commands,
+        entity::Entities,
+        resource::Resources,
+        FrameState,
+        SchedulerStep,
+        SystemScheduler,
+    },
+    input::Input,
+    time::{FixedTime, FrameRate, Time},
+};
+
+use super::{register_default_systems, run_fixed_update, ApplicationLoop, InitFn};
+
+pub type StopCondition = Box<dyn FnMut(&Entities, &Resources) -> bool>;
+
+/// Runs without window or GPU, stepping a simulated clock by a constant frame time.
+///
+/// Each frame ticks `Input`, `FixedUpdate`, `Update` and `Render` steps, it stops after
+/// a number of frames or when stop condition is met, whichever happens first.
+/// When neither is set, it runs indefinitely, as a dedicated server would.
+///
+/// Frames are paced in real time by `FrameRate` resource, at 60 per second by default.
+/// Without a target they run as fast as possible, which suits tests.
+///
+/// `Application::run` creates one which runs indefinitely, configure it and pass it
+/// to `Application::run_with` to stop it.
+pub struct HeadlessLoop {
+    config: AppConfig,
+    entities: Entities,
+    commands: Commands,
+    resources: Resources,
+    sys_scheduler: SystemScheduler,
+    frame_time: Duration,
+    max_frames: Option<u64>,
+    stop_condition: Option<StopCondition>,
+    frame: u64,
+}
+
+impl HeadlessLoop {
+    pub const DEFAULT_FRAME_RATE: f64 = 60.0;
+    pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+    /// Simulated time between frames.
+    pub fn with_frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    pub fn with_max_frames(mut self, max_frames: u64) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    pub fn with_stop_condition<F>(mut self, condition: F) -> Self where
+        F: 'static + FnMut(&Entities, &Resources) -> bool
+    {
+        self.stop_condition = Some(Box::new(condition));
+        self
+    }
+
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Frames ran so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn entities(&self) -> &Entities {
+        &self.entities
+    }
+
+    pub fn entities_mut(&mut self) -> &mut Entities {
+        &mut self.entities
+    }
+
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
+    pub fn system_scheduler(&mut self) -> &mut SystemScheduler {
+        &mut self.sys_scheduler
+    }
+
+    pub fn init_state(&mut self) -> InitState<'_> {
+        InitState {
+            window_context: None,
+            system_scheduler: &mut self.sys_scheduler,
+            entities: &mut self.entities,
+            resources: &mut self.resources,
+        }
+    }
+
+    /// Runs a single frame.
+    pub fn step(&mut self) {
+        let delta_time = self.resources
+                             .get_mut::<Time>()
+                             .unwrap()
+                             .advance(self.frame_time);
+
+        let mut frame_state = FrameState {
+            delta: delta_time,
+            app: None,
+            commands: &mut self.commands,
+        };
+
+        self.sys_scheduler.run(&SchedulerStep::Input, &mut self.entities, &self.resources, &mut frame_state);
+        run_fixed_update(&mut self.sys_scheduler, &mut self.entities, &self.resources, &mut frame_state);
+        self.sys_scheduler.run(&SchedulerStep::Update, &mut self.entities, &self.resources, &mut frame_state);
+
+        self.resources.get_mut::<Diagnostics>().unwrap().register_frame(delta_time.duration);
+        self.sys_scheduler.run(&SchedulerStep::Render, &mut self.entities, &self.resources, &mut frame_state);
+
+        self.frame += 1;
+    }
+
+    /// Runs frames until it should stop, waiting between them to keep `FrameRate` target.
+    pub fn run_until_stopped(&mut self) {
+        while !self.should_stop() {
+            let frame_start = Time::now();
+            self.step();
+
+            let next_frame = self.resources
+                                 .get::<FrameRate>()
+                                 .unwrap()
+                                 .next_frame(frame_start);
+
+            if let Some(next_frame) = next_frame {
+                thread::sleep(next_frame.saturating_duration_since(Time::now()));
+            }
+        }
+    }
+
+    fn should_stop(&mut self) -> bool {
+        if self.max_frames.is_some_and(|max_frames| self.frame >= max_frames) {
+            return true;
+        }
+
+        match self.stop_condition {
+            Some(ref mut condition) => condition(&self.entities, &self.resources),
+            None => false,
+        }
+    }
+}
+
+impl ApplicationLoop for HeadlessLoop {
+    fn new(config: AppConfig) -> Result<Self, ApplicationError> {
+        let mut resources = Resources::new();
+        resources.insert_resource(Time::simulated());
+        resources.insert_resource(FixedTime::default());
+        resources.insert_resource(FrameRate::with_target(Some(Self::DEFAULT_FRAME_RATE)).unwrap());
+        resources.insert_resource(Input::default());
+        resources.insert_resource(Diagnostics::default());
+
+        let entities = Entities::new();
+        let mut sys_scheduler = SystemScheduler::new();
+        register_default_systems(&mut sys_scheduler);
+
+        Ok(Self {
+            config,
+            commands: Commands::new(&entities),
+            entities,
+            resources,
+            sys_scheduler,
+            frame_time: Self::DEFAULT_FRAME_TIME,
+            max_frames: None,
+            stop_condition: None,
+            frame: 0,
+        })
+    }
+
+    fn run(mut self, init_fn: Option<InitFn>) -> Result<(), ApplicationError> {
+        if let Some(init) = init_fn {
+            init(None, self.init_state())
+                .map_err(ApplicationError::InitFailed)?;
+        }
+
+        self.run_until_stopped();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        base::{Application, ApplicationState},
+        components::transform::Transform,
+        ecs::{
+            component::{self, BaseQuery, FnQuery},
+            resource::ResMut,
+            scheduler::SystemConfig,
+            system::System,
+        },
+        math::Vector2,
+        systems::labels,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        fixed_updates: u32,
+        updates: u32,
+    }
+
+    struct CounterSystem {
+        fixed: bool,
+    }
+
+    impl System for CounterSystem {
+        type Query<'q> = FnQuery<'q>;
+        type Resources<'r> = ResMut<'r, Counter>;
+
+        fn setup(&mut self) {
+        }
+
+        fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+        }
+
+        fn run<'q, 'r>(&mut self, _query: Self::Query<'q>, mut counter: Self::Resources<'r>, state: &mut FrameState) {
+            assert!(state.app.is_none());
+
+            if self.fixed {
+                counter.fixed_updates += 1;
+            } else {
+                counter.updates += 1;
+            }
+        }
+
+        fn create_query<'q>(&self) -> Self::Query<'q> {
+            FnQuery::new(|_| false)
+        }
+    }
+
+    /// Records global positions as seen by systems labeled `labels::RENDER`.
+    struct PositionProbe;
+
+    impl System for PositionProbe {
+        type Query<'q> = component::Query<'q, Transform>;
+        type Resources<'r> = ResMut<'r, Vec<Vector2<f32>>>;
+
+        fn setup(&mut self) {
+        }
+
+        fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+        }
+
+        fn run<'q, 'r>(&mut self, query: Self::Query<'q>, mut positions: Self::Resources<'r>, _state: &mut FrameState) {
+            positions.extend(query.iter_components().map(|entry| entry.component.position()));
+        }
+
+        fn create_query<'q>(&self) -> Self::Query<'q> {
+            Self::Query::default()
+        }
+    }
+
+    fn headless_loop() -> HeadlessLoop {
+        let mut headless = HeadlessLoop::new(AppConfig::default()).unwrap();
+        headless.resources_mut().insert_resource(Counter::default());
+        headless.resources_mut().insert_resource(FrameRate::default());
+
+        let scheduler = headless.system_scheduler();
+        scheduler.register(SchedulerStep::FixedUpdate, CounterSystem { fixed: true });
+        scheduler.register(SchedulerStep::Update, CounterSystem { fixed: false });
+
+        headless
+    }
+
+    #[test]
+    fn simulated_frames() {
+        let mut headless = headless_loop()
+            .with_frame_time(Duration::from_millis(25))
+            .with_max_frames(40);
+
+        headless.resources_mut()
+                .get_mut::<FixedTime>()
+                .unwrap()
+                .set_rate(20.0)
+                .unwrap();
+
+        headless.run_until_stopped();
+
+        let counter = headless.resources().get::<Counter>().unwrap();
+        assert_eq!(headless.frame(), 40);
+        assert_eq!(headless.resources().get::<Time>().unwrap().elapsed(), Duration::from_secs(1));
+        assert_eq!(counter.updates, 40);
+        assert_eq!(counter.fixed_updates, 20);
+    }
+
+    #[test]
+    fn stop_condition() {
+        let mut headless = headless_loop()
+            .with_stop_condition(|_, resources| resources.get::<Counter>().unwrap().updates >= 3);
+
+        headless.run_until_stopped();
+
+        assert_eq!(headless.frame(), 3);
+    }
+
+    #[test]
+    fn run_with_application() {
+        let updates = Rc::new(Cell::new(0));
+        let seen_updates = updates.clone();
+
+        let headless = HeadlessLoop::new(AppConfig::default())
+            .unwrap()
+            .with_stop_condition(move |_, resources| {
+                seen_updates.set(resources.get::<Counter>().unwrap().updates);
+                seen_updates.get() >= 3
+            });
+
+        Application::default()
+            .initialize_headless_with(|app_state, init_state| {
+                assert!(app_state.is_none());
+
+                init_state.resources.insert_resource(Counter::default());
+                init_state.resources.insert_resource(FrameRate::default());
+                init_state.system_scheduler.register(SchedulerStep::Update, CounterSystem { fixed: false });
+
+                Ok::<_, ApplicationError>(())
+            })
+            .run_with(headless)
+            .unwrap();
+
+        assert_eq!(updates.get(), 3);
+    }
+
+    #[test]
+    fn paced_frames() {
+        let mut headless = headless_loop().with_max_frames(3);
+
+        headless.resources_mut()
+                .get_mut::<FrameRate>()
+                .unwrap()
+                .set_target(Some(50.0))
+                .unwrap();
+
+        let start = Time::now();
+        headless.run_until_stopped();
+
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn transforms_before_render() {
+        let mut headless = headless_loop().with_max_frames(1);
+        headless.resources_mut().insert_resource(Vec::<Vector2<f32>>::new());
+
+        let entities = headless.entities_mut();
+        let mut parent = entities.create();
+        parent.register_component(Transform::new(Vector2::new(2.0, 0.0)));
+        let parent_id = parent.id();
+        parent.build();
+
+        let mut child = entities.create();
+        child.register_component(Transform::new(Vector2::new(0.0, 3.0)));
+        let child_id = child.id();
+        child.build();
+
+        assert!(entities.set_parent(child_id, parent_id));
+
+        headless.system_scheduler()
+                .register_with(SchedulerStep::Render, PositionProbe, SystemConfig::labeled(labels::RENDER))
+                .unwrap();
+
+        headless.run_until_stopped();
+
+        let mut positions = headless.resources().get::<Vec<Vector2<f32>>>().unwrap().clone();
+        positions.sort_by(|a, b| a.y.total_cmp(&b.y));
+        assert_eq!(positions, vec![Vector2::new(2.0, 0.0), Vector2::new(2.0, 3.0)]);
+    }
+}
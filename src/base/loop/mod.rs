@@ -1,20 +1,70 @@
 mod continuous;
+mod headless;
 
 pub use continuous::ContinuousLoop;
+pub use headless::{HeadlessLoop, StopCondition};
 
 use std::error::Error;
-use crate::{window::WindowContext, ecs::{SystemScheduler, entity::Entities}};
-use super::{ApplicationError, ApplicationState};
+use crate::{
+    window::WindowContext,
+    ecs::{
+        entity::Entities,
+        resource::Resources,
+        scheduler::SystemConfig,
+        FrameState,
+        SchedulerStep,
+        SystemScheduler,
+    },
+    systems::{labels, TransformSystem},
+    time::{DeltaTime, FixedTime},
+};
+use super::{AppConfig, ApplicationError, ApplicationState};
 
-pub type InitFn = Box<dyn for<'a> FnOnce(&'a mut ApplicationState, InitState<'a>) -> Result<(), Box<dyn Error + 'static>>>;
+/// Application initialization, `ApplicationState` is `None` when running headless.
+pub type InitFn = Box<dyn for<'a> FnOnce(Option<&'a mut ApplicationState>, InitState<'a>) -> Result<(), Box<dyn Error + 'static>>>;
 
 pub trait ApplicationLoop {
-    fn new(window_context: WindowContext) -> Self;
+    fn new(config: AppConfig) -> Result<Self, ApplicationError> where Self: Sized;
     fn run(self, init_fn: Option<InitFn>) -> Result<(), ApplicationError>;
 }
 
 pub struct InitState<'a> {
-    pub window_context: &'a mut WindowContext,
+    /// `None` when running headless.
+    pub window_context: Option<&'a mut WindowContext>,
     pub system_scheduler: &'a mut SystemScheduler,
     pub entities: &'a mut Entities,
+    pub resources: &'a mut Resources,
+}
+
+/// Built-in systems every loop starts with.
+fn register_default_systems(scheduler: &mut SystemScheduler) {
+    // scheduler is empty, so it can't form a cycle
+    scheduler.register_with(
+        SchedulerStep::Render,
+        TransformSystem,
+        SystemConfig::labeled(labels::TRANSFORM).before(labels::RENDER),
+    ).unwrap();
+}
+
+/// Runs `SchedulerStep::FixedUpdate` as many times as `FixedTime` accumulated for frame's delta.
+fn run_fixed_update(
+    scheduler: &mut SystemScheduler,
+    entities: &mut Entities,
+    resources: &Resources,
+    state: &mut FrameState,
+) {
+    let (fixed_steps, timestep) = {
+        let mut fixed_time = resources.get_mut::<FixedTime>().unwrap();
+        (fixed_time.accumulate(state.delta.duration), fixed_time.timestep())
+    };
+
+    for _ in 0..fixed_steps {
+        let mut fixed_frame_state = FrameState {
+            delta: DeltaTime::new(timestep),
+            app: state.app.as_deref_mut(),
+            commands: state.commands,
+        };
+
+        scheduler.run(&SchedulerStep::FixedUpdate, entities, resources, &mut fixed_frame_state);
+    }
 }
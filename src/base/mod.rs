@@ -1,3 +1,9 @@
+mod app_config;
+pub use app_config::AppConfig;
+
+mod app_config_error;
+pub use app_config_error::AppConfigError;
+
 mod application;
 pub use application::Application;
 
@@ -13,20 +19,21 @@ use std::{
 };
 
 use crate::{
-    diag::Diagnostics,
-    input::Input,
     rendering::{GraphicAdapter, Texture},
     resources::AssetResources,
-    time::Time,
     window::Window,
 };
 
+/// Application state which is tied to window and rendering.
+/// Shared state, such as `Input`, `Time` and `Diagnostics`, lives at ECS `Resources`.
+///
+/// [`Input`]: crate::input::Input
+/// [`Time`]: crate::time::Time
+/// [`Diagnostics`]: crate::diag::Diagnostics
+/// [`Resources`]: crate::ecs::resource::Resources
 pub struct ApplicationState {
     pub main_window: Window,
-    pub time: Time,
     pub graphic_adapter: Rc<RefCell<GraphicAdapter>>,
-    pub input: Input,
-    pub diagnostics: Diagnostics,
     pub asset_resources: AssetResources,
 }
 
@@ -38,10 +45,7 @@ impl ApplicationState {
 
         Self {
             main_window: window,
-            time: Time::new(),
             graphic_adapter: Rc::new(RefCell::new(graphic_adapter)),
-            input: Input::default(),
-            diagnostics: Default::default(),
             asset_resources,
         }
     }
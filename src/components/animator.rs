@@ -0,0 +1,302 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::{
+    animation::{
+        AnimationClip,
+        AnimationError,
+        AnimationEvent,
+        AnimationFrame,
+        PlaybackMode,
+    },
+    ecs::component::{
+        Component,
+        ComponentAttribute,
+        Components,
+    },
+};
+
+/// Plays named clips, advanced every frame by `AnimatorSystem`.
+///
+/// [`AnimatorSystem`]: crate::systems::AnimatorSystem
+pub struct Animator {
+    clips: HashMap<String, AnimationClip>,
+    playback: Option<Playback>,
+    speed: f32,
+    events: Vec<AnimationEvent>,
+}
+
+struct Playback {
+    clip: String,
+    frame: usize,
+    elapsed: Duration,
+    backwards: bool,
+    finished: bool,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Self {
+            clips: HashMap::new(),
+            playback: None,
+            speed: 1.0,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_clip<N: Into<String>>(mut self, name: N, clip: AnimationClip) -> Self {
+        self.add_clip(name, clip);
+        self
+    }
+
+    pub fn with_clips<I, N>(mut self, clips: I) -> Self where
+        I: IntoIterator<Item = (N, AnimationClip)>,
+        N: Into<String>,
+    {
+        for (name, clip) in clips {
+            self.add_clip(name, clip);
+        }
+
+        self
+    }
+
+    pub fn add_clip<N: Into<String>>(&mut self, name: N, clip: AnimationClip) {
+        self.clips.insert(name.into(), clip);
+    }
+
+    pub fn clip<S: AsRef<str>>(&self, name: S) -> Option<&AnimationClip> {
+        self.clips.get(name.as_ref())
+    }
+
+    /// Starts playing a clip from it's first frame, unless it's already playing.
+    pub fn play<S: AsRef<str>>(&mut self, name: S) -> Result<(), AnimationError> {
+        let name = name.as_ref();
+
+        match self.playback {
+            Some(ref playback) if playback.clip == name && !playback.finished => Ok(()),
+            _ => self.restart(name),
+        }
+    }
+
+    /// Starts playing a clip from it's first frame.
+    pub fn restart<S: AsRef<str>>(&mut self, name: S) -> Result<(), AnimationError> {
+        let name = name.as_ref();
+
+        if !self.clips.contains_key(name) {
+            return Err(AnimationError::ClipNotFound(name.to_owned()));
+        }
+
+        self.playback = Some(Playback {
+            clip: name.to_owned(),
+            frame: 0,
+            elapsed: Duration::ZERO,
+            backwards: false,
+            finished: false,
+        });
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.playback = None;
+    }
+
+    pub fn current_clip(&self) -> Option<&str> {
+        self.playback
+            .as_ref()
+            .map(|playback| playback.clip.as_str())
+    }
+
+    pub fn current_frame_index(&self) -> Option<usize> {
+        self.playback
+            .as_ref()
+            .map(|playback| playback.frame)
+    }
+
+    pub fn current_frame(&self) -> Option<&AnimationFrame> {
+        let playback = self.playback.as_ref()?;
+
+        self.clips
+            .get(&playback.clip)?
+            .frame(playback.frame)
+    }
+
+    /// Whether a `PlaybackMode::Once` clip reached it's last frame.
+    pub fn is_finished(&self) -> bool {
+        self.playback
+            .as_ref()
+            .is_some_and(|playback| playback.finished)
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Playback speed multiplier.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Events raised at last advance.
+    pub fn events(&self) -> &[AnimationEvent] {
+        &self.events
+    }
+
+    /// Moves playback forward, going through as many frames as needed.
+    pub fn advance(&mut self, delta: Duration) {
+        self.events.clear();
+
+        let Some(ref mut playback) = self.playback else {
+            return;
+        };
+
+        let Some(clip) = self.clips.get(&playback.clip) else {
+            return;
+        };
+
+        // a clip without duration would never leave it's frame
+        if playback.finished || clip.duration().is_zero() {
+            return;
+        }
+
+        playback.elapsed += delta.mul_f32(self.speed);
+
+        while let Some(frame) = clip.frame(playback.frame) {
+            if playback.elapsed < frame.duration {
+                break;
+            }
+
+            playback.elapsed -= frame.duration;
+
+            if let Some(event) = playback.next_frame(clip) {
+                self.events.push(event);
+            }
+
+            if playback.finished {
+                playback.elapsed = Duration::ZERO;
+                break;
+            }
+        }
+    }
+}
+
+impl Playback {
+    fn next_frame(&mut self, clip: &AnimationClip) -> Option<AnimationEvent> {
+        let last = clip.len() - 1;
+
+        match clip.mode() {
+            PlaybackMode::Once if self.frame == last => {
+                self.finished = true;
+                Some(AnimationEvent::Finished(self.clip.clone()))
+            },
+            PlaybackMode::Loop if self.frame == last => {
+                self.frame = 0;
+                Some(AnimationEvent::Looped(self.clip.clone()))
+            },
+            PlaybackMode::PingPong if self.backwards && self.frame == 0 => {
+                self.backwards = false;
+                self.frame = 1.min(last);
+                Some(AnimationEvent::Looped(self.clip.clone()))
+            },
+            PlaybackMode::PingPong if self.backwards => {
+                self.frame -= 1;
+                None
+            },
+            PlaybackMode::PingPong if self.frame == last => {
+                self.backwards = true;
+                self.frame = last.saturating_sub(1);
+                None
+            },
+            _ => {
+                self.frame += 1;
+                None
+            },
+        }
+    }
+}
+
+impl Component for Animator {
+    fn attributes(&self) -> ComponentAttribute {
+        ComponentAttribute::Unique
+    }
+
+    fn registered(&mut self, _components: &mut Components) {
+    }
+
+    fn unregistered(&mut self) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::Size2,
+        rendering::{atlas::SpriteRegion, Texture, TextureFormat},
+        resources::Asset,
+    };
+
+    use super::*;
+
+    fn clip(frames: usize, mode: PlaybackMode) -> AnimationClip {
+        let texture = Asset::new(Texture::new(TextureFormat::Rgba8UnormSrgb, Size2::new(1, 1), &[0; 4]));
+        let regions = (0..frames).map(|_| SpriteRegion::full(&texture));
+
+        AnimationClip::from_regions(regions, Duration::from_millis(100)).with_mode(mode)
+    }
+
+    fn frames(animator: &mut Animator, steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                animator.advance(Duration::from_millis(100));
+                animator.current_frame_index().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn playback_modes() {
+        let mut animator = Animator::new()
+            .with_clip("once", clip(3, PlaybackMode::Once))
+            .with_clip("loop", clip(3, PlaybackMode::Loop))
+            .with_clip("ping_pong", clip(3, PlaybackMode::PingPong));
+
+        animator.play("loop").unwrap();
+        assert_eq!(frames(&mut animator, 4), vec![1, 2, 0, 1]);
+
+        animator.play("ping_pong").unwrap();
+        assert_eq!(frames(&mut animator, 6), vec![1, 2, 1, 0, 1, 2]);
+
+        animator.play("once").unwrap();
+        assert_eq!(frames(&mut animator, 2), vec![1, 2]);
+        assert!(!animator.is_finished());
+
+        animator.advance(Duration::from_millis(100));
+        assert!(animator.is_finished());
+        assert_eq!(animator.events(), &[AnimationEvent::Finished("once".to_owned())]);
+
+        animator.advance(Duration::from_millis(100));
+        assert_eq!(animator.current_frame_index(), Some(2));
+        assert!(animator.events().is_empty());
+
+        assert!(matches!(animator.play("missing"), Err(AnimationError::ClipNotFound(_))));
+    }
+
+    #[test]
+    fn skips_frames() {
+        let mut animator = Animator::new().with_clip("loop", clip(4, PlaybackMode::Loop));
+        animator.play("loop").unwrap();
+        animator.set_speed(2.0);
+
+        animator.advance(Duration::from_millis(250));
+        assert_eq!(animator.current_frame_index(), Some(1));
+
+        animator.advance(Duration::from_millis(150));
+        assert_eq!(animator.current_frame_index(), Some(0));
+        assert_eq!(animator.events(), &[AnimationEvent::Looped("loop".to_owned())]);
+    }
+}
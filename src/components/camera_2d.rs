@@ -0,0 +1,230 @@
+use crate::{
+    ecs::component::{
+        Component,
+        ComponentAttribute,
+        Components,
+    },
+    math::{
+        Affine2,
+        Matrix4x4,
+        Rectangle,
+        Size2,
+        Vector2,
+    },
+    rendering::RenderLayers,
+};
+
+/// Orthographic view of the world, drawn by `RenderSystem` into it's viewport.
+///
+/// Position is at viewport's center and one world unit is one pixel at zoom 1.
+/// Every active camera draws the scene, in ascending `order`.
+///
+/// [`RenderSystem`]: crate::systems::render_system::RenderSystem
+#[derive(Debug, Clone)]
+pub struct Camera2D {
+    pub position: Vector2<f32>,
+
+    /// In radians.
+    pub rotation: f32,
+
+    /// Greater than 1 zooms in.
+    pub zoom: f32,
+
+    /// Normalized rect of the target, so `(0.0, 0.0, 0.5, 1.0)` is it's left half.
+    pub viewport: Rectangle<f32>,
+
+    /// Only graphics sharing a layer with it are drawn.
+    pub layers: RenderLayers,
+
+    pub order: i32,
+    pub active: bool,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            position: Vector2::zero(),
+            rotation: 0.0,
+            zoom: 1.0,
+            viewport: Rectangle::new(Vector2::zero(), Size2::new(1.0, 1.0)),
+            layers: RenderLayers::DEFAULT,
+            order: 0,
+            active: true,
+        }
+    }
+}
+
+impl Camera2D {
+    pub fn new(position: Vector2<f32>) -> Self {
+        Self {
+            position,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: Rectangle<f32>) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn with_layers(mut self, layers: RenderLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Viewport in pixels, for a target with provided size.
+    pub fn viewport_rect(&self, target_size: Size2<u32>) -> Rectangle<f32> {
+        let target_size: Size2<f32> = target_size.convert();
+
+        Rectangle::new(
+            Vector2::new(self.viewport.left() * target_size.width, self.viewport.top() * target_size.height),
+            self.viewport.size * target_size,
+        )
+    }
+
+    /// Places camera space, centered at viewport with pixel units, into world.
+    pub fn camera_to_world(&self) -> Affine2 {
+        Affine2::from_transform(self.position, self.rotation, Vector2::one() * self.zoom.recip(), Vector2::zero())
+    }
+
+    pub fn view(&self) -> Affine2 {
+        self.camera_to_world()
+            .inverse()
+            .unwrap_or_default()
+    }
+
+    /// Maps camera space into clip space, y points down.
+    pub fn projection(&self, viewport_size: Size2<f32>) -> Matrix4x4<f32> {
+        let half = viewport_size / 2.0;
+
+        Matrix4x4::ortho(half.height, -half.height, -half.width, half.width, -1.0, 1.0)
+    }
+
+    pub fn view_projection(&self, target_size: Size2<u32>) -> Matrix4x4<f32> {
+        self.projection(self.viewport_rect(target_size).size) * Matrix4x4::from(self.view())
+    }
+
+    /// Whether a point, in target pixels, is inside camera's viewport.
+    pub fn contains_screen_point(&self, screen: Vector2<f32>, target_size: Size2<u32>) -> bool {
+        self.viewport_rect(target_size).contains_point(screen)
+    }
+
+    /// Converts a point in target pixels, such as mouse position, into world.
+    pub fn screen_to_world(&self, screen: Vector2<f32>, target_size: Size2<u32>) -> Vector2<f32> {
+        let center = Self::center(self.viewport_rect(target_size));
+
+        self.camera_to_world().transform_point(screen - center)
+    }
+
+    /// Converts a world point into target pixels.
+    pub fn world_to_screen(&self, world: Vector2<f32>, target_size: Size2<u32>) -> Vector2<f32> {
+        let center = Self::center(self.viewport_rect(target_size));
+
+        self.view().transform_point(world) + center
+    }
+
+    fn center(rect: Rectangle<f32>) -> Vector2<f32> {
+        rect.position + Vector2::from(rect.size / 2.0)
+    }
+}
+
+impl Component for Camera2D {
+    fn attributes(&self) -> ComponentAttribute {
+        ComponentAttribute::Unique
+    }
+
+    fn registered(&mut self, _components: &mut Components) {
+    }
+
+    fn unregistered(&mut self) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::{
+        math::{Rectangle, Vector4},
+        rendering::{batchers::DrawBatcher, Color},
+        test_util::{assert_near, check_golden, colored, fill, TestScene},
+    };
+
+    use super::*;
+
+    fn clip(matrix: &Matrix4x4<f32>, point: Vector2<f32>) -> Vector2<f32> {
+        let dot = |row: &Vector4<f32>| row.x * point.x + row.y * point.y + row.w;
+        Vector2::new(dot(&matrix.row_0), dot(&matrix.row_1))
+    }
+
+    #[test]
+    fn conversions() {
+        let target = Size2::new(800, 600);
+
+        let camera = Camera2D::new(Vector2::new(100.0, 50.0))
+            .with_zoom(2.0)
+            .with_viewport(Rectangle::new(Vector2::new(0.5, 0.0), Size2::new(0.5, 1.0)));
+
+        assert_eq!(camera.viewport_rect(target), Rectangle::new(Vector2::new(400.0, 0.0), Size2::new(400.0, 600.0)));
+
+        // viewport center is camera position
+        assert_near(camera.screen_to_world(Vector2::new(600.0, 300.0), target), Vector2::new(100.0, 50.0));
+        assert_near(camera.screen_to_world(Vector2::new(700.0, 300.0), target), Vector2::new(150.0, 50.0));
+        assert_near(camera.world_to_screen(Vector2::new(150.0, 75.0), target), Vector2::new(700.0, 350.0));
+        assert!(!camera.contains_screen_point(Vector2::new(100.0, 300.0), target));
+
+        // viewport's right edge and top edge
+        let view_projection = camera.view_projection(target);
+        assert_near(clip(&view_projection, Vector2::new(200.0, 50.0)), Vector2::new(1.0, 0.0));
+        assert_near(clip(&view_projection, Vector2::new(100.0, -100.0)), Vector2::new(0.0, 1.0));
+
+        let rotated = Camera2D::default().with_rotation(FRAC_PI_2);
+        let point = rotated.screen_to_world(Vector2::new(410.0, 300.0), target);
+        assert_near(point, Vector2::new(0.0, 10.0));
+        assert_near(rotated.world_to_screen(point, target), Vector2::new(410.0, 300.0));
+    }
+
+    /// Same rectangle seen by two cameras, each one at half of the target.
+    #[test]
+    fn split_viewports_golden() {
+        let scene = TestScene::new();
+
+        let cameras = [
+            Camera2D::default().with_viewport(Rectangle::new(Vector2::zero(), Size2::new(0.5, 1.0))),
+            Camera2D::new(Vector2::new(2.0, 2.0))
+                .with_zoom(2.0)
+                .with_viewport(Rectangle::new(Vector2::new(0.5, 0.0), Size2::new(0.5, 1.0))),
+        ];
+
+        let rectangle = Rectangle::new(Vector2::new(-2.0, -2.0), Size2::new(4.0, 4.0));
+
+        let image = scene.render(&scene.render_target(), 0xFF236EFFu32, |draw_command| {
+            for camera in cameras {
+                let target_size = draw_command.target_size();
+                scene.shader.borrow_mut().uniforms_mut().view = camera.view_projection(target_size);
+                draw_command.set_viewport(Some(camera.viewport_rect(target_size)));
+
+                let mut draw_batcher = DrawBatcher::new(draw_command);
+                fill(&mut draw_batcher, rectangle, colored(Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0), scene.shader_config));
+                draw_batcher.flush().unwrap();
+            }
+        });
+
+        check_golden(&image, "split_viewports");
+    }
+}
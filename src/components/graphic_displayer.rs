@@ -6,6 +6,9 @@ use crate::{
     },
     rendering::{
         graphics::Graphic,
+        BlendMode,
+        DepthStencilConfig,
+        RenderLayers,
         ShaderConfig,
         TextureConfig,
         Vertex,
@@ -20,6 +23,17 @@ pub struct GraphicDisplayer<V> where
     pub graphic: Option<Box<dyn Graphic<V>>>,
     pub shader_config: Option<ShaderConfig>,
     pub texture_config: Option<TextureConfig>,
+
+    /// Overrides shader configuration's blend mode.
+    pub blend_mode: Option<BlendMode>,
+
+    /// Overrides shader configuration's depth and stencil, such as to draw inside a mask.
+    pub depth_stencil: Option<DepthStencilConfig>,
+
+    /// Cameras which draw it, see `Camera2D::layers`.
+    ///
+    /// [`Camera2D::layers`]: crate::components::Camera2D::layers
+    pub render_layers: RenderLayers,
 }
 
 impl<V: Vertex> GraphicDisplayer<V> {
@@ -28,6 +42,9 @@ impl<V: Vertex> GraphicDisplayer<V> {
             graphic: None,
             shader_config: Default::default(),
             texture_config: Default::default(),
+            blend_mode: None,
+            depth_stencil: None,
+            render_layers: Default::default(),
         }
     }
 
@@ -38,9 +55,27 @@ impl<V: Vertex> GraphicDisplayer<V> {
             graphic: Some(Box::new(graphic)),
             shader_config: Default::default(),
             texture_config: Default::default(),
+            blend_mode: None,
+            depth_stencil: None,
+            render_layers: Default::default(),
         }
     }
 
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = Some(blend_mode);
+        self
+    }
+
+    pub fn with_depth_stencil(mut self, depth_stencil: DepthStencilConfig) -> Self {
+        self.depth_stencil = Some(depth_stencil);
+        self
+    }
+
+    pub fn with_render_layers(mut self, render_layers: RenderLayers) -> Self {
+        self.render_layers = render_layers;
+        self
+    }
+
     pub fn retrieve_graphic<G>(&self) -> Option<&G> where
         G: Graphic<V>
     {
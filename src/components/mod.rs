@@ -5,3 +5,9 @@ pub use graphic_displayer::GraphicDisplayer;
 
 mod diag_component;
 pub use diag_component::DiagComponent;
+
+mod animator;
+pub use animator::Animator;
+
+mod camera_2d;
+pub use camera_2d::Camera2D;
@@ -1,46 +1,111 @@
-use std::rc::Weak;
-
 use crate::{
     ecs::component::{
         Component,
         Components,
         ComponentAttribute,
     },
-    math::Vector2,
+    math::{Affine2, Vector2},
 };
 
-macro_rules! retrieve_parent {
-    ($self:expr, $identifier:ident) => {
-        match &$self.parent {
-            Some(p) => p.upgrade().unwrap().$identifier(),
-            None => Default::default(),
-        }
-    };
-}
-
 pub type Position = Vector2<f32>;
 pub type Rotation = f32;
 pub type Scale = Vector2<f32>;
 
-#[derive(Default, Debug)]
+/// Placement relative to entity's `Parent`, or to world when it has none.
+///
+/// Global matrix is cached by `TransformSystem`, until it runs once
+/// it's the same as local matrix.
+///
+/// [`Parent`]: crate::ecs::hierarchy::Parent
+/// [`TransformSystem`]: crate::systems::TransformSystem
+#[derive(Debug, Clone)]
 pub struct Transform {
     pub local_position: Position,
+
+    /// In radians.
     pub local_rotation: Rotation,
+
     pub local_scale: Scale,
-    parent: Option<Weak<Transform>>,
+
+    /// Local point which is placed at position, rotation and scale happen around it.
+    pub pivot: Position,
+
+    /// Added to parent's z, greater values are drawn on top when batcher sorts by it.
+    pub local_z: f32,
+
+    global: Option<(Affine2, f32)>,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            local_position: Vector2::zero(),
+            local_rotation: 0.0,
+            local_scale: Vector2::one(),
+            pivot: Vector2::zero(),
+            local_z: 0.0,
+            global: None,
+        }
+    }
 }
 
 impl Transform {
+    pub fn new(local_position: Position) -> Self {
+        Self {
+            local_position,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_rotation(mut self, local_rotation: Rotation) -> Self {
+        self.local_rotation = local_rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, local_scale: Scale) -> Self {
+        self.local_scale = local_scale;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Position) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn with_z(mut self, local_z: f32) -> Self {
+        self.local_z = local_z;
+        self
+    }
+
+    pub fn local_matrix(&self) -> Affine2 {
+        Affine2::from_transform(self.local_position, self.local_rotation, self.local_scale, self.pivot)
+    }
+
+    /// Parent's global matrix combined with local matrix, as of last propagation.
+    pub fn global_matrix(&self) -> Affine2 {
+        self.global.map_or_else(|| self.local_matrix(), |(matrix, _)| matrix)
+    }
+
+    /// Sum of every ancestor's z with local z, as of last propagation.
+    pub fn z(&self) -> f32 {
+        self.global.map_or(self.local_z, |(_, z)| z)
+    }
+
+    pub(crate) fn set_global(&mut self, matrix: Affine2, z: f32) {
+        self.global = Some((matrix, z));
+    }
+
+    /// Where pivot ends up in world.
     pub fn position(&self) -> Position {
-        self.local_position + retrieve_parent!(self, position)
+        self.global_matrix().transform_point(self.pivot)
     }
 
     pub fn rotation(&self) -> Rotation {
-        self.local_rotation + retrieve_parent!(self, rotation)
+        self.global_matrix().rotation()
     }
 
     pub fn scale(&self) -> Scale {
-        self.local_scale + retrieve_parent!(self, scale)
+        self.global_matrix().scale()
     }
 }
 
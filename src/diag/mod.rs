@@ -6,4 +6,46 @@ pub struct Diagnostics {
     pub update_timer: Duration,
     pub render_timer: Duration,
     pub draw_calls: usize,
+
+    /// Frames rendered during last second.
+    pub fps: u32,
+
+    /// Average, min and max frame time during last second.
+    pub frame_time: Duration,
+    pub min_frame_time: Duration,
+    pub max_frame_time: Duration,
+
+    sample: FrameSample,
+}
+
+impl Diagnostics {
+    /// Registers a rendered frame, stats are refreshed every second.
+    pub fn register_frame(&mut self, frame_time: Duration) {
+        self.sample.register(frame_time);
+
+        if self.sample.elapsed >= Duration::from_secs(1) {
+            self.fps = self.sample.count;
+            self.frame_time = self.sample.elapsed / self.sample.count;
+            self.min_frame_time = self.sample.min;
+            self.max_frame_time = self.sample.max;
+            self.sample = FrameSample::default();
+        }
+    }
+}
+
+#[derive(Default)]
+struct FrameSample {
+    count: u32,
+    elapsed: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl FrameSample {
+    fn register(&mut self, frame_time: Duration) {
+        self.min = if self.count == 0 { frame_time } else { self.min.min(frame_time) };
+        self.max = self.max.max(frame_time);
+        self.count += 1;
+        self.elapsed += frame_time;
+    }
 }
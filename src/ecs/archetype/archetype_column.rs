@@ -0,0 +1,57 @@
+use std::slice::ChunksExact;
+
+use crate::ecs::component::ComponentEntry;
+
+/// Contiguous storage for every component of a single kind at an `Archetype`.
+///
+/// Each row holds `stride` entries, since an entity may have more than one
+/// component with the same kind.
+///
+/// [`Archetype`]: super::Archetype
+pub struct ArchetypeColumn {
+    stride: usize,
+    entries: Vec<ComponentEntry>,
+}
+
+impl ArchetypeColumn {
+    pub(super) fn new(stride: usize) -> Self {
+        Self {
+            stride,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Entries at provided row.
+    pub fn get(&self, row: usize) -> Option<&[ComponentEntry]> {
+        let start = row * self.stride;
+        self.entries.get(start..start + self.stride)
+    }
+
+    /// Iterate through every row, in the same order as `Archetype::entities`.
+    ///
+    /// [`Archetype::entities`]: super::Archetype::entities
+    pub fn iter(&self) -> ChunksExact<'_, ComponentEntry> {
+        self.entries.chunks_exact(self.stride)
+    }
+
+    pub(super) fn push(&mut self, entry: ComponentEntry) {
+        self.entries.push(entry)
+    }
+
+    /// Removes a row, replacing it with the last one.
+    pub(super) fn swap_remove(&mut self, row: usize) -> Vec<ComponentEntry> {
+        let last = (self.entries.len() / self.stride) - 1;
+
+        if row != last {
+            for i in 0..self.stride {
+                self.entries.swap(row * self.stride + i, last * self.stride + i);
+            }
+        }
+
+        self.entries.split_off(last * self.stride)
+    }
+}
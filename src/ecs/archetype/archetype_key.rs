@@ -0,0 +1,37 @@
+use std::any::TypeId;
+
+/// Identifies an `Archetype` by it's component set.
+/// Types are kept sorted and may repeat, when an entity holds more than one
+/// component of the same kind.
+///
+/// [`Archetype`]: super::Archetype
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ArchetypeKey {
+    kinds: Vec<TypeId>,
+}
+
+impl ArchetypeKey {
+    pub fn new(mut kinds: Vec<TypeId>) -> Self {
+        kinds.sort_unstable();
+
+        Self {
+            kinds,
+        }
+    }
+
+    pub fn contains(&self, kind: &TypeId) -> bool {
+        self.kinds.binary_search(kind).is_ok()
+    }
+
+    /// How many components of provided kind each entity holds.
+    pub fn count(&self, kind: &TypeId) -> usize {
+        self.kinds
+            .iter()
+            .filter(|k| *k == kind)
+            .count()
+    }
+
+    pub fn kinds(&self) -> &[TypeId] {
+        &self.kinds
+    }
+}
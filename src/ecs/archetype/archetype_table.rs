@@ -0,0 +1,119 @@
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+};
+
+use crate::ecs::{
+    component::{
+        AnyComponent,
+        ComponentEntry,
+        Components,
+    },
+    entity::EntityId,
+};
+
+use super::{
+    ArchetypeColumn,
+    ArchetypeId,
+    ArchetypeKey,
+};
+
+/// A table which stores every entity sharing the same component set.
+pub struct Archetype {
+    id: ArchetypeId,
+    key: ArchetypeKey,
+    entities: Vec<EntityId>,
+    /// Ordered by kind, so rows are always visited in the same order.
+    columns: BTreeMap<TypeId, ArchetypeColumn>,
+}
+
+impl Archetype {
+    pub(super) fn new(id: ArchetypeId, key: ArchetypeKey) -> Self {
+        let mut columns = BTreeMap::new();
+
+        for kind in key.kinds() {
+            columns.entry(*kind)
+                   .or_insert_with(|| ArchetypeColumn::new(key.count(kind)));
+        }
+
+        Self {
+            id,
+            key,
+            entities: Vec::new(),
+            columns,
+        }
+    }
+
+    pub fn id(&self) -> ArchetypeId {
+        self.id
+    }
+
+    pub fn key(&self) -> &ArchetypeKey {
+        &self.key
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Entities stored, row ordered.
+    pub fn entities(&self) -> &[EntityId] {
+        &self.entities
+    }
+
+    pub fn contains<C>(&self) -> bool where
+        C: AnyComponent + 'static
+    {
+        self.columns.contains_key(&TypeId::of::<C>())
+    }
+
+    pub fn column<C>(&self) -> Option<&ArchetypeColumn> where
+        C: AnyComponent + 'static
+    {
+        self.columns.get(&TypeId::of::<C>())
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = &ArchetypeColumn> {
+        self.columns.values()
+    }
+
+    /// Every component entry at provided row, ordered by kind.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &ComponentEntry> {
+        self.columns
+            .values()
+            .filter_map(move |column| column.get(row))
+            .flatten()
+    }
+
+    /// Moves every component into a new row.
+    pub(in crate::ecs) fn push(&mut self, components: Components) -> usize {
+        let row = self.entities.len();
+        self.entities.push(*components.entity_id());
+
+        for entry in components.into_entries() {
+            self.columns
+                .get_mut(&entry.kind())
+                .expect("Component kind doesn't belong to archetype.")
+                .push(entry);
+        }
+
+        row
+    }
+
+    /// Removes a row, moving it's components out.
+    /// Last row takes it's place, so it's entity location should be updated.
+    pub(in crate::ecs) fn swap_remove(&mut self, row: usize) -> Components {
+        let entity_id = self.entities.swap_remove(row);
+        let mut entries = Vec::with_capacity(self.key.kinds().len());
+
+        for column in self.columns.values_mut() {
+            entries.append(&mut column.swap_remove(row));
+        }
+
+        Components::from_entries(entity_id, entries)
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use super::{
+    Archetype,
+    ArchetypeId,
+    ArchetypeKey,
+};
+
+#[derive(Default)]
+pub struct Archetypes {
+    tables: Vec<Archetype>,
+    index: HashMap<ArchetypeKey, ArchetypeId>,
+}
+
+impl Archetypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: ArchetypeId) -> Option<&Archetype> {
+        self.tables.get(id)
+    }
+
+    pub fn find(&self, key: &ArchetypeKey) -> Option<&Archetype> {
+        self.index
+            .get(key)
+            .and_then(|id| self.tables.get(*id))
+    }
+
+    pub fn count(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Archetype> {
+        self.tables.iter()
+    }
+
+    pub(in crate::ecs) fn get_mut(&mut self, id: ArchetypeId) -> Option<&mut Archetype> {
+        self.tables.get_mut(id)
+    }
+
+    /// Retrieve archetype which matches key, creating it when needed.
+    pub(in crate::ecs) fn get_or_insert(&mut self, key: ArchetypeKey) -> ArchetypeId {
+        if let Some(id) = self.index.get(&key) {
+            return *id;
+        }
+
+        let id = self.tables.len();
+        self.tables.push(Archetype::new(id, key.clone()));
+        self.index.insert(key, id);
+
+        id
+    }
+}
+
+impl<'a> IntoIterator for &'a Archetypes {
+    type Item = &'a Archetype;
+    type IntoIter = std::slice::Iter<'a, Archetype>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,20 @@
+mod archetype_table;
+pub use archetype_table::Archetype;
+
+mod archetype_column;
+pub use archetype_column::ArchetypeColumn;
+
+mod archetype_key;
+pub use archetype_key::ArchetypeKey;
+
+mod archetypes;
+pub use archetypes::Archetypes;
+
+pub type ArchetypeId = usize;
+
+/// Where an entity's components are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityLocation {
+    pub archetype: ArchetypeId,
+    pub row: usize,
+}
@@ -0,0 +1,108 @@
+use std::rc::Rc;
+
+use crate::ecs::{
+    component::AnyComponent,
+    entity::{Entities, EntityAllocator, EntityId},
+};
+
+use super::EntityCommands;
+
+pub(super) type Command = Box<dyn FnOnce(&mut Entities)>;
+
+/// Records structural changes requested while a `System` is running.
+/// They're applied, in order, at next sync point, which happens after every `SchedulerStep`.
+///
+/// [`System`]: crate::ecs::system::System
+/// [`SchedulerStep`]: crate::ecs::SchedulerStep
+pub struct Commands {
+    queue: Vec<Command>,
+    ids: Rc<EntityAllocator>,
+}
+
+impl Commands {
+    /// Commands for provided entities, spawned entity ids are reserved from them.
+    pub fn new(entities: &Entities) -> Self {
+        Self {
+            queue: Vec::new(),
+            ids: entities.ids().clone(),
+        }
+    }
+
+    /// Starts a new entity, it'll be spawned when `EntityCommands::build` is called.
+    /// It's id is reserved right away.
+    ///
+    /// [`EntityCommands::build`]: super::EntityCommands::build
+    pub fn spawn(&mut self) -> EntityCommands<'_> {
+        let id = self.ids.allocate();
+        EntityCommands::new(id, self)
+    }
+
+    /// Despawns an entity along with it's descendants.
+    pub fn despawn(&mut self, id: EntityId) -> &mut Self {
+        self.add(move |entities| {
+            entities.despawn(id);
+        })
+    }
+
+    /// Attaches `child` to `parent`, see `Entities::set_parent`.
+    ///
+    /// [`Entities::set_parent`]: crate::ecs::entity::Entities::set_parent
+    pub fn set_parent(&mut self, child: EntityId, parent: EntityId) -> &mut Self {
+        self.add(move |entities| {
+            entities.set_parent(child, parent);
+        })
+    }
+
+    pub fn remove_parent(&mut self, child: EntityId) -> &mut Self {
+        self.add(move |entities| {
+            entities.remove_parent(child);
+        })
+    }
+
+    /// Register a component to an existing entity.
+    pub fn insert<C>(&mut self, id: EntityId, component: C) -> &mut Self where
+        C: AnyComponent + 'static
+    {
+        self.add(move |entities| {
+            if let Some(mut entity) = entities.get_mut(id) {
+                entity.register_component(component);
+            }
+        })
+    }
+
+    /// Removes first component, from an existing entity, which matches provided type.
+    pub fn remove<C>(&mut self, id: EntityId) -> &mut Self where
+        C: AnyComponent + 'static
+    {
+        self.add(move |entities| {
+            entities.remove_component::<C>(id);
+        })
+    }
+
+    /// Records a custom command.
+    pub fn add<F>(&mut self, command: F) -> &mut Self where
+        F: 'static + FnOnce(&mut Entities)
+    {
+        self.queue.push(Box::new(command));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub(super) fn release(&self, id: EntityId) {
+        self.ids.release(id);
+    }
+
+    /// Apply every recorded command, leaving it empty.
+    pub fn apply(&mut self, entities: &mut Entities) {
+        for command in self.queue.drain(..) {
+            command(entities);
+        }
+    }
+}
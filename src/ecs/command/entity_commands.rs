@@ -0,0 +1,85 @@
+use crate::ecs::{
+    component::AnyComponent,
+    entity::{Entity, EntityId},
+};
+
+use super::Commands;
+
+type Registration = Box<dyn FnOnce(&mut Entity)>;
+
+/// Deferred version of `EntityBuilder`.
+/// It's id is reserved when it's created, it's released again when it's dropped without `build`.
+///
+/// [`EntityBuilder`]: crate::ecs::entity::EntityBuilder
+pub struct EntityCommands<'a> {
+    id: EntityId,
+    registrations: Vec<Registration>,
+    parent: Option<EntityId>,
+    built: bool,
+    commands: &'a mut Commands,
+}
+
+impl<'a> EntityCommands<'a> {
+    pub(super) fn new(id: EntityId, commands: &'a mut Commands) -> Self {
+        Self {
+            id,
+            registrations: Vec::new(),
+            parent: None,
+            built: false,
+            commands,
+        }
+    }
+
+    /// Id entity will be spawned with.
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+
+    pub fn register_component<C>(&mut self, component: C) -> &mut Self where
+        C: AnyComponent + 'static
+    {
+        self.registrations.push(Box::new(move |entity| {
+            entity.register_component(component);
+        }));
+
+        self
+    }
+
+    /// Spawned entity will be attached to `parent`, if it still exists by then.
+    pub fn set_parent(&mut self, parent: EntityId) -> &mut Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Records entity spawn, returning it's id.
+    pub fn build(mut self) -> EntityId {
+        let id = self.id;
+        let registrations = std::mem::take(&mut self.registrations);
+        let parent = self.parent;
+        self.built = true;
+
+        self.commands.add(move |entities| {
+            let mut entity = entities.create_reserved(id);
+
+            for register in registrations {
+                register(&mut entity);
+            }
+
+            entity.build();
+
+            if let Some(parent) = parent {
+                entities.set_parent(id, parent);
+            }
+        });
+
+        id
+    }
+}
+
+impl<'a> Drop for EntityCommands<'a> {
+    fn drop(&mut self) {
+        if !self.built {
+            self.commands.release(self.id);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod commands;
+pub use commands::Commands;
+
+mod entity_commands;
+pub use entity_commands::EntityCommands;
@@ -1,5 +1,5 @@
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::RefCell,
     rc::Rc,
 };
@@ -8,8 +8,10 @@ use crate::ecs::entity::EntityId;
 use super::{
     AnyComponent,
     Component,
+    ComponentAttribute,
     ComponentRef,
     ComponentAnyRef, ComponentStrongRef,
+    ComponentTicks,
 };
 
 pub type RawComponentEntry = Rc<RefCell<(dyn AnyComponent + 'static)>>;
@@ -17,7 +19,10 @@ pub type RawComponentEntry = Rc<RefCell<(dyn AnyComponent + 'static)>>;
 /// Describes a component entry at Components
 pub struct ComponentEntry {
     entity_id: EntityId,
+    kind: TypeId,
+    attributes: ComponentAttribute,
     component: RawComponentEntry,
+    ticks: Rc<ComponentTicks>,
 }
 
 impl ComponentEntry {
@@ -26,21 +31,42 @@ impl ComponentEntry {
     {
         Self {
             entity_id,
+            kind: TypeId::of::<C>(),
+            attributes: component.attributes(),
             component: Rc::new(RefCell::new(component)),
+            ticks: Rc::default(),
         }
     }
 
-    pub fn get<'a, C>(&'a self) -> ComponentStrongRef<'a, C> where
+    pub fn entity_id(&self) -> EntityId {
+        self.entity_id
+    }
+
+    /// Component's `TypeId`.
+    pub fn kind(&self) -> TypeId {
+        self.kind
+    }
+
+    /// Attributes at the moment component was registered.
+    pub fn attributes(&self) -> &ComponentAttribute {
+        &self.attributes
+    }
+
+    pub fn get<'a, C>(&self) -> ComponentStrongRef<'a, C> where
         C: Component + 'static
     {
 
-        ComponentStrongRef::new(self.component.clone())
+        ComponentStrongRef::new(self.component.clone(), self.ticks.clone())
     }
 
     pub fn get_ref<C>(&self) -> ComponentRef<C> where
         C: Component + 'static
     {
-        ComponentRef::new(self.entity_id, Rc::downgrade(&self.component))
+        ComponentRef::new(
+            self.entity_id,
+            Rc::downgrade(&self.component),
+            Rc::downgrade(&self.ticks),
+        )
     }
 
     pub fn get_any_ref(&self) -> ComponentAnyRef {
@@ -60,6 +86,15 @@ impl ComponentEntry {
         }
     }
 
+    /// Notifies component it's being unregistered from it's entity.
+    pub(in crate::ecs) fn unregister(&self) {
+        self.component.borrow_mut().unregistered()
+    }
+
+    pub fn ticks(&self) -> &Rc<ComponentTicks> {
+        &self.ticks
+    }
+
     pub fn inner(&self) -> &RawComponentEntry {
         &self.component
     }
@@ -67,6 +102,6 @@ impl ComponentEntry {
     pub fn is<C>(&self) -> bool where
         C: AnyComponent + 'static
     {
-        self.component.borrow().as_any().is::<C>()
+        self.kind == TypeId::of::<C>()
     }
 }
@@ -3,7 +3,10 @@ use std::{
     collections::HashMap,
 };
 
-use crate::ecs::entity::EntityId;
+use crate::ecs::{
+    archetype::ArchetypeKey,
+    entity::EntityId,
+};
 use super::{
     AnyComponent,
     ComponentAttribute,
@@ -91,6 +94,63 @@ impl Components {
             })
     }
 
+    /// Removes first component which matches provided type.
+    /// Outstanding `ComponentRef` to it will fail to retrieve.
+    ///
+    /// [`ComponentRef`]: super::ComponentRef
+    pub fn remove<C>(&mut self) -> bool where
+        C: AnyComponent + 'static
+    {
+        self.remove_kind(TypeId::of::<C>())
+    }
+
+    /// Key of the `Archetype` which these components belong to.
+    ///
+    /// [`Archetype`]: crate::ecs::archetype::Archetype
+    pub fn archetype_key(&self) -> ArchetypeKey {
+        ArchetypeKey::new(self.iter().map(ComponentEntry::kind).collect())
+    }
+
+    pub(in crate::ecs) fn from_entries(entity_id: EntityId, entries: Vec<ComponentEntry>) -> Self {
+        let mut components = Self::new(entity_id);
+
+        for entry in entries {
+            if entry.attributes().contains(ComponentAttribute::Unique) {
+                components.unique_entries.insert(entry.kind(), entry);
+            } else {
+                components.entries.push(entry);
+            }
+        }
+
+        components
+    }
+
+    pub(in crate::ecs) fn remove_kind(&mut self, kind: TypeId) -> bool {
+        let entry = match self.unique_entries.remove(&kind) {
+            Some(entry) => entry,
+            None => match self.entries.iter().position(|e| e.kind() == kind) {
+                Some(index) => self.entries.remove(index),
+                None => return false,
+            },
+        };
+
+        entry.unregister();
+        true
+    }
+
+    /// Unregister every component.
+    pub(in crate::ecs) fn clear(&mut self) {
+        for entry in self.unique_entries.drain().map(|(_, e)| e).chain(self.entries.drain(..)) {
+            entry.unregister();
+        }
+    }
+
+    pub(in crate::ecs) fn into_entries(self) -> impl Iterator<Item = ComponentEntry> {
+        self.unique_entries
+            .into_values()
+            .chain(self.entries)
+    }
+
     pub(in crate::ecs) fn internal_register<C>(
         &mut self,
         mut component: C,
@@ -104,10 +164,14 @@ impl Components {
             match component.attributes() {
                 ComponentAttribute::Unique => {
                     // as unique
-                    self.unique_entries.insert(
+                    let replaced = self.unique_entries.insert(
                             TypeId::of::<C>(),
                             ComponentEntry::new(entity_id, component)
                         );
+
+                    if let Some(entry) = replaced {
+                        entry.unregister();
+                    }
                 },
                 ComponentAttribute::None => {
                     // as regular
@@ -13,6 +13,9 @@ pub use queries::*;
 mod refs;
 pub use refs::*;
 
+mod ticks;
+pub use ticks::{ComponentTicks, SystemTicks, Tick};
+
 //
 
 use bitflags::bitflags;
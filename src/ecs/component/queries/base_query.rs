@@ -1,4 +1,8 @@
-use crate::ecs::component::Components;
+use crate::ecs::{
+    archetype::{Archetype, EntityLocation},
+    component::SystemTicks,
+    entity::EntityId,
+};
 
 use super::{
     ComponentQueryIterator,
@@ -8,16 +12,27 @@ use super::{
 /// Describes a `System` component query.
 /// It'll run at `System` registered `Domain`.
 ///
+/// Captured components are borrowed from archetypes for `'a`, while system runs.
+///
 /// [`System`]: crate::ecs::System
 /// [`System`]: crate::ecs::Domain
-pub trait BaseQuery {
-    type Target<'t> where Self: 't;
+pub trait BaseQuery<'a> {
+    type Target<'t> where Self: 't, 'a: 't;
 
-    /// Capture `Component` using query conditions.
+    /// Capture `Component` from an `Archetype` using query conditions.
     /// Zero or more `Component` can be captured.
+    /// `SystemTicks` are used to detect changes since last time query's system ran.
     ///
     /// [`Component`]: crate::ecs::components::Component
-    fn capture_components(&mut self, components: &Components);
+    /// [`Archetype`]: crate::ecs::archetype::Archetype
+    /// [`SystemTicks`]: crate::ecs::component::SystemTicks
+    fn capture_archetype(&mut self, archetype: &'a Archetype, ticks: SystemTicks);
+
+    /// Captured component of provided entity, if any.
+    /// A known location, such as `QueryEntry::location`, avoids searching for it.
+    ///
+    /// [`QueryEntry::location`]: super::QueryEntry::location
+    fn fetch<'i>(&'i self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<Self::Target<'i>>;
 
     /// Returns a `ComponentQueryIterator` which works as an interface to access query results.
     ///
@@ -28,92 +43,172 @@ pub trait BaseQuery {
 }
 
 
-impl<'a, T, U> BaseQuery for (T, U) where
-    T: BaseQuery,
-    U: BaseQuery,
-{
-    type Target<'t> = (Option<T::Target<'t>>, Option<U::Target<'t>>) where T: 't, U: 't;
+/// Tuple queries only yields entities which have every member.
+///
+/// First member is iterated, every other one fetches the same entity at the location it was found,
+/// since an entity's components share an archetype row.
+///
+/// So it's a join by location rather than a merge join over entity ids: each fetch is a direct
+/// row lookup, which keeps the whole join linear in first member's results, without sorting them.
+macro_rules! impl_base_query_tuple {
+    ($first:ident 0, $($query:ident $index:tt),+) => {
+        impl<'a, $first, $($query),+> BaseQuery<'a> for ($first, $($query,)+) where
+            $first: BaseQuery<'a>,
+            $($query: BaseQuery<'a>,)+
+        {
+            type Target<'t> = ($first::Target<'t>, $($query::Target<'t>,)+) where $first: 't, $($query: 't,)+ 'a: 't;
 
-    fn capture_components(&mut self, components: &Components) {
-        self.0.capture_components(components);
-        self.1.capture_components(components);
-    }
+            fn capture_archetype(&mut self, archetype: &'a Archetype, ticks: SystemTicks) {
+                self.0.capture_archetype(archetype, ticks);
+                $(self.$index.capture_archetype(archetype, ticks);)+
+            }
 
-    fn iter_components<'i>(
-        &'i self
-    ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
-        ComponentQueryIterator::new(
-            self.0.iter_components()
-            .map(|entry_a| {
-                for entry_b in self.1.iter_components() {
-                    if entry_b.entity_id().eq(entry_a.entity_id()) {
-                        return QueryEntry::new(
-                            entry_a.entity_id().clone(),
-                            (Some(entry_a.component), Some(entry_b.component)),
-                        );
-                    }
-                }
+            fn fetch<'i>(&'i self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<Self::Target<'i>> {
+                Some((
+                    self.0.fetch(entity_id, location)?,
+                    $(self.$index.fetch(entity_id, location)?,)+
+                ))
+            }
 
-                return QueryEntry::new(
-                    entry_a.entity_id().clone(),
-                    (Some(entry_a.component), None),
-                );
-            })
-        )
-    }
+            fn iter_components<'i>(
+                &'i self
+            ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
+                ComponentQueryIterator::new(
+                    self.0
+                        .iter_components()
+                        .filter_map(move |entry| {
+                            let entity_id = *entry.entity_id();
+                            let location = entry.location();
+
+                            Some(QueryEntry::new(
+                                entity_id,
+                                (entry.component, $(self.$index.fetch(entity_id, location)?,)+),
+                            ).with_location(location))
+                        })
+                )
+            }
+        }
+    };
 }
 
-impl<'a, T, U, V> BaseQuery for (T, U, V) where
-    T: BaseQuery,
-    U: BaseQuery,
-    V: BaseQuery,
-{
-    type Target<'t> = (Option<T::Target<'t>>, Option<U::Target<'t>>, Option<V::Target<'t>>) where T: 't, U: 't, V: 't;
-
-    fn capture_components(&mut self, components: &Components) {
-        self.0.capture_components(components);
-        self.1.capture_components(components);
-        self.2.capture_components(components);
-    }
+impl_base_query_tuple!(A 0, B 1);
+impl_base_query_tuple!(A 0, B 1, C 2);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_base_query_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
 
-    fn iter_components<'i>(
-        &'i self
-    ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
-        ComponentQueryIterator::new(
-            self.0.iter_components()
-            .map(|entry_a| {
-                for entry_b in self.1.iter_components() {
-                    if entry_b.entity_id().eq(entry_a.entity_id()) {
-                        for entry_c in self.2.iter_components() {
-                            if entry_c.entity_id().eq(entry_a.entity_id()) {
-                                return QueryEntry::new(
-                                    entry_a.entity_id().clone(),
-                                    (Some(entry_a.component), Some(entry_b.component), Some(entry_c.component)),
-                                );
-                            }
-                        }
-
-                        return QueryEntry::new(
-                            entry_a.entity_id().clone(),
-                            (Some(entry_a.component), Some(entry_b.component), None),
-                        );
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{
+        component::{
+            Component,
+            ComponentAttribute,
+            Components,
+            Query,
+        },
+        entity::Entities,
+    };
+
+    use super::*;
+
+    macro_rules! test_components {
+        ($($name:ident),+) => {
+            $(
+                struct $name(u32);
+
+                impl Component for $name {
+                    fn attributes(&self) -> ComponentAttribute {
+                        ComponentAttribute::Unique
+                    }
+
+                    fn registered(&mut self, _components: &mut Components) {
                     }
-                }
 
-                for entry_c in self.2.iter_components() {
-                    if entry_c.entity_id().eq(entry_a.entity_id()) {
-                        return QueryEntry::new(
-                            entry_a.entity_id().clone(),
-                            (Some(entry_a.component), None, Some(entry_c.component)),
-                        );
+                    fn unregistered(&mut self) {
                     }
                 }
+            )+
+        };
+    }
+
+    test_components!(A, B, C, D, E);
+
+    #[test]
+    fn tuple_query_join() {
+        let mut entities = Entities::new();
+
+        for i in 0..20 {
+            let mut e = entities.create();
+            e.register_component(A(i));
+
+            if i % 2 == 0 { e.register_component(B(i)); }
+            if i % 3 == 0 { e.register_component(C(i)); }
+            if i % 4 == 0 { e.register_component(D(i)); }
+            e.register_component(E(i));
+
+            e.build();
+        }
+
+        let mut query = (
+            Query::<A>::default(),
+            Query::<B>::default(),
+            Query::<C>::default(),
+            Query::<D>::default(),
+            Query::<E>::default(),
+        );
+
+        for archetype in entities.archetypes() {
+            query.capture_archetype(archetype, SystemTicks::default());
+        }
 
-                return QueryEntry::new(
-                    entry_a.entity_id().clone(),
-                    (Some(entry_a.component), None, None),
-                );
+        let values: Vec<u32> = query.iter_components()
+            .map(|QueryEntry { component: (a, b, c, d, e), .. }| {
+                assert!(a.0 == b.0 && b.0 == c.0 && c.0 == d.0 && d.0 == e.0);
+                a.0
             })
-        )
+            .collect();
+
+        assert_eq!(values, vec![0, 12]);
+    }
+
+    #[test]
+    fn query_borrows_archetype_columns() {
+        let mut entities = Entities::new();
+
+        for i in 0..6 {
+            let mut e = entities.create();
+            e.register_component(A(i));
+
+            if i % 2 == 0 { e.register_component(B(i)); }
+
+            e.build();
+        }
+
+        let mut query = Query::<A>::default();
+
+        for archetype in entities.archetypes() {
+            query.capture_archetype(archetype, SystemTicks::default());
+        }
+
+        // components aren't shared with query, it reads them from columns
+        for archetype in entities.archetypes() {
+            for entries in archetype.column::<A>().unwrap().iter() {
+                assert_eq!(std::rc::Rc::strong_count(entries[0].inner()), 1);
+            }
+        }
+
+        // table by table, in row order
+        let values: Vec<u32> = query.iter_components().map(|entry| entry.0).collect();
+        assert_eq!(values, vec![0, 2, 4, 1, 3, 5]);
+        assert_eq!(query.count(), 6);
+        assert_eq!(query.get(*query.component().unwrap().entity_id()).map(|a| a.0), Some(0));
     }
 }
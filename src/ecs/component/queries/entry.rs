@@ -1,6 +1,9 @@
 use std::ops::{Deref, DerefMut};
 
-use crate::ecs::entity::EntityId;
+use crate::ecs::{
+    archetype::EntityLocation,
+    entity::EntityId,
+};
 
 /// Entry result from a `BaseQuery`.
 /// Component type is determined by `BaseQuery::Target`.
@@ -19,6 +22,9 @@ pub struct QueryEntry<C> {
     //      let a = component.0;
     //      let b = component.1;
     pub component: C,
+
+    /// Where entry was read from, when it was captured from an archetype.
+    location: Option<EntityLocation>,
 }
 
 impl<C> QueryEntry<C> {
@@ -26,13 +32,23 @@ impl<C> QueryEntry<C> {
         Self {
             entity_id,
             component,
+            location: None,
         }
     }
 
+    pub(super) fn with_location(mut self, location: Option<EntityLocation>) -> Self {
+        self.location = location;
+        self
+    }
+
     /// Entity Id which owns queried component.
     pub fn entity_id(&self) -> &EntityId {
         &self.entity_id
     }
+
+    pub fn location(&self) -> Option<EntityLocation> {
+        self.location
+    }
 }
 
 impl<C> Deref for QueryEntry<C> {
@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use crate::ecs::{
+    archetype::Archetype,
+    component::{
+        AnyComponent,
+        ComponentEntry,
+        SystemTicks,
+    },
+};
+
+/// Narrows which entities a query captures, without accessing their components.
+pub trait QueryFilter {
+    /// Whether any entity at an `Archetype` could match.
+    ///
+    /// [`Archetype`]: crate::ecs::archetype::Archetype
+    fn matches_archetype(archetype: &Archetype) -> bool;
+
+    /// Whether entity at archetype row matches.
+    fn matches(archetype: &Archetype, row: usize, ticks: SystemTicks) -> bool;
+}
+
+/// Entity should have a component of type `C`.
+pub struct With<C: AnyComponent>(PhantomData<C>);
+
+/// Entity shouldn't have a component of type `C`.
+pub struct Without<C: AnyComponent>(PhantomData<C>);
+
+/// Component of type `C` has been changed since system last run.
+pub struct Changed<C: AnyComponent>(PhantomData<C>);
+
+/// Component of type `C` has been added since system last run.
+pub struct Added<C: AnyComponent>(PhantomData<C>);
+
+fn first_entry<C: AnyComponent>(archetype: &Archetype, row: usize) -> Option<&ComponentEntry> {
+    archetype.column::<C>()?
+             .get(row)?
+             .first()
+}
+
+impl QueryFilter for () {
+    fn matches_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn matches(_archetype: &Archetype, _row: usize, _ticks: SystemTicks) -> bool {
+        true
+    }
+}
+
+impl<C: AnyComponent> QueryFilter for With<C> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains::<C>()
+    }
+
+    fn matches(_archetype: &Archetype, _row: usize, _ticks: SystemTicks) -> bool {
+        true
+    }
+}
+
+impl<C: AnyComponent> QueryFilter for Without<C> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        !archetype.contains::<C>()
+    }
+
+    fn matches(_archetype: &Archetype, _row: usize, _ticks: SystemTicks) -> bool {
+        true
+    }
+}
+
+impl<C: AnyComponent> QueryFilter for Changed<C> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains::<C>()
+    }
+
+    fn matches(archetype: &Archetype, row: usize, ticks: SystemTicks) -> bool {
+        first_entry::<C>(archetype, row).is_some_and(|e| e.ticks().is_changed(ticks))
+    }
+}
+
+impl<C: AnyComponent> QueryFilter for Added<C> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains::<C>()
+    }
+
+    fn matches(archetype: &Archetype, row: usize, ticks: SystemTicks) -> bool {
+        first_entry::<C>(archetype, row).is_some_and(|e| e.ticks().is_added(ticks))
+    }
+}
+
+/// Every filter at tuple should match.
+macro_rules! impl_query_filter_tuple {
+    ($($filter:ident),+) => {
+        impl<$($filter: QueryFilter),+> QueryFilter for ($($filter,)+) {
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                $($filter::matches_archetype(archetype))&&+
+            }
+
+            fn matches(archetype: &Archetype, row: usize, ticks: SystemTicks) -> bool {
+                $($filter::matches(archetype, row, ticks))&&+
+            }
+        }
+    };
+}
+
+impl_query_filter_tuple!(A);
+impl_query_filter_tuple!(A, B);
+impl_query_filter_tuple!(A, B, C);
+impl_query_filter_tuple!(A, B, C, D);
+impl_query_filter_tuple!(A, B, C, D, E);
+impl_query_filter_tuple!(A, B, C, D, E, F);
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{
+        component::{
+            BaseQuery,
+            EmptyComponent,
+            Query,
+        },
+        entity::Entities,
+    };
+
+    use super::*;
+
+    fn capture<F: QueryFilter>(entities: &Entities, ticks: SystemTicks) -> usize {
+        let mut query = Query::<EmptyComponent, F>::default();
+
+        for archetype in entities.archetypes() {
+            query.capture_archetype(archetype, ticks);
+        }
+
+        query.count()
+    }
+
+    #[test]
+    fn change_detection_filters() {
+        let mut entities = Entities::new();
+
+        for _ in 0..2 {
+            let mut e = entities.create();
+            e.register_component(EmptyComponent::default());
+            e.build();
+        }
+
+        let first_run = SystemTicks { last_run: 0, this_run: entities.change_tick() };
+        entities.increment_change_tick();
+        assert_eq!(capture::<Added<EmptyComponent>>(&entities, first_run), 2);
+        assert_eq!(capture::<Changed<EmptyComponent>>(&entities, first_run), 2);
+        assert_eq!(capture::<Without<EmptyComponent>>(&entities, first_run), 0);
+
+        // mutably dereference a single component
+        {
+            let entity = entities.iter().next().unwrap();
+            let component = entity.get::<EmptyComponent>().unwrap();
+            let _ = &mut *component.borrow_mut();
+
+            // immutable access doesn't count as change
+            let other = entities.iter().nth(1).unwrap();
+            let component = other.get::<EmptyComponent>().unwrap();
+            let _ = &*component.borrow_mut();
+        }
+
+        let second_run = SystemTicks { last_run: first_run.this_run, this_run: entities.change_tick() };
+        assert_eq!(capture::<Added<EmptyComponent>>(&entities, second_run), 0);
+        assert_eq!(capture::<Changed<EmptyComponent>>(&entities, second_run), 1);
+        assert_eq!(capture::<(With<EmptyComponent>, Changed<EmptyComponent>)>(&entities, second_run), 1);
+    }
+}
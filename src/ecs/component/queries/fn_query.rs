@@ -1,14 +1,13 @@
-use std::{
-    cell,
-    collections::BTreeMap,
-};
+use std::cell;
 
 use crate::ecs::{
+    archetype::{Archetype, EntityLocation},
     component::{
         AnyComponent,
-        Components,
+        ComponentEntry,
         ComponentQueryIterator,
         ComponentStrongAnyRef, QueryEntry,
+        SystemTicks,
     },
     entity::EntityId,
 };
@@ -21,52 +20,85 @@ use super::BaseQuery;
 ///
 /// [`Component`]: crate::ecs::components::Component
 /// [`Entry`]: crate::ecs::entity::Entry
-pub struct FnQuery {
-    container: BTreeMap<EntityId, ComponentStrongAnyRef>,
+pub struct FnQuery<'a> {
+    /// Sorted by archetype id.
+    tables: Vec<&'a Archetype>,
     filter: Box<dyn Fn(&ComponentStrongAnyRef) -> bool>,
 }
 
-impl FnQuery {
+impl<'a> FnQuery<'a> {
     pub fn new<F: 'static + Fn(&ComponentStrongAnyRef) -> bool>(filter: F) -> Self {
         Self {
-            container: BTreeMap::default(),
+            tables: Vec::new(),
             filter: Box::new(filter),
         }
     }
 
     pub fn count(&self) -> usize {
-        self.container.len()
+        self.iter().count()
+    }
+
+    /// First entry at row which passes filter.
+    fn find(&self, archetype: &'a Archetype, row: usize) -> Option<&'a ComponentEntry> {
+        archetype.row(row).find(|entry| (self.filter)(entry.inner()))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (EntityLocation, EntityId, &'a ComponentEntry)> + '_ {
+        self.tables
+            .iter()
+            .flat_map(move |&archetype| {
+                archetype.entities()
+                         .iter()
+                         .enumerate()
+                         .filter_map(move |(row, entity_id)| Some((
+                             EntityLocation { archetype: archetype.id(), row },
+                             *entity_id,
+                             self.find(archetype, row)?,
+                         )))
+            })
     }
 }
 
-impl BaseQuery for FnQuery {
-    type Target<'t> = cell::Ref<'t, dyn AnyComponent> where Self : 't;
+impl<'a> BaseQuery<'a> for FnQuery<'a> {
+    type Target<'t> = cell::Ref<'t, dyn AnyComponent> where Self: 't, 'a: 't;
+
+    fn capture_archetype(&mut self, archetype: &'a Archetype, _ticks: SystemTicks) {
+        if !archetype.is_empty() {
+            let index = self.tables.partition_point(|a| a.id() < archetype.id());
+            self.tables.insert(index, archetype);
+        }
+    }
+
+    fn fetch<'i>(&'i self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<Self::Target<'i>> {
+        let (archetype, row) = match location {
+            Some(location) => {
+                let index = self.tables
+                    .binary_search_by_key(&location.archetype, |a| a.id())
+                    .ok()?;
 
-    fn capture_components(&mut self, components: &Components) {
-        for entry in components.iter() {
-            let reference = entry.get_any_ref();
+                (self.tables[index], location.row)
+            },
+            None => self.tables
+                .iter()
+                .find_map(|&archetype| Some((archetype, archetype.entities().iter().position(|id| *id == entity_id)?)))?,
+        };
 
-            match reference.consume() {
-                Ok(strong_ref) => {
-                    if (self.filter)(&strong_ref) {
-                        self.container.insert(*components.entity_id(), strong_ref);
-                    }
-                },
-                Err(_) => (),
-            }
+        if archetype.entities().get(row) != Some(&entity_id) {
+            return None;
         }
+
+        self.find(archetype, row).map(|entry| entry.inner().borrow())
     }
 
     fn iter_components<'i>(
         &'i self
     ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
         ComponentQueryIterator::new(
-            self.container
-                .iter()
-                .map(|c| QueryEntry::new(
-                    c.0.clone(),
-                    c.1.borrow(),
-                ))
+            self.iter()
+                .map(|(location, entity_id, entry)| QueryEntry::new(
+                    entity_id,
+                    entry.inner().borrow(),
+                ).with_location(Some(location)))
         )
     }
 }
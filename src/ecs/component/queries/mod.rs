@@ -4,6 +4,9 @@ pub use base_query::BaseQuery;
 mod query;
 pub use query::*;
 
+mod filter;
+pub use filter::{Added, Changed, QueryFilter, With, Without};
+
 mod fn_query;
 pub use fn_query::FnQuery;
 
@@ -15,6 +18,9 @@ mod compound_query;
 pub use compound_query::CompoundQuery;
 */
 
+mod table_capture;
+use table_capture::TableCapture;
+
 mod iterator;
 pub use iterator::ComponentQueryIterator;
 
@@ -1,13 +1,11 @@
-use std::collections::BTreeMap;
-
 use crate::ecs::{
+    archetype::{Archetype, EntityLocation},
     component::{
         AnyComponent,
         ComponentQueryIterator,
-        ComponentStrongRef,
         ComponentValueMutRef,
         ComponentValueRef,
-        Components,
+        SystemTicks,
     },
     entity::EntityId,
 };
@@ -15,78 +13,88 @@ use crate::ecs::{
 use super::{
     BaseQuery,
     QueryEntry,
+    QueryFilter,
+    TableCapture,
 };
 
 /// Multiple entry query, but only one `Component` per `Entity`.
-/// Uses component type to filter it's entries.
+/// Uses component type to filter it's entries, optionally narrowed by a `QueryFilter`.
+///
+/// Matching archetype tables are iterated directly, in row order.
 ///
 /// [`Component`]: crate::ecs::components::Component
 /// [`Entry`]: crate::ecs::entity::Entry
-pub struct Query<'a, C: 'static + AnyComponent> {
-    container: BTreeMap<EntityId, ComponentStrongRef<'a, C>>,
+/// [`QueryFilter`]: super::QueryFilter
+pub struct Query<'a, C: 'static + AnyComponent, F: QueryFilter = ()> {
+    tables: TableCapture<'a, C, F>,
 }
 
-impl<'a, C: 'static + AnyComponent> Default for Query<'a, C> {
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> Default for Query<'a, C, F> {
     fn default() -> Self {
         Self {
-            container: BTreeMap::default(),
+            tables: TableCapture::default(),
         }
     }
 }
 
-impl<'a, C: 'static + AnyComponent> Query<'a, C> {
-    /// Retrieve first component found
-    pub fn component<'r>(
-        &'r self
-    ) -> Result<QueryEntry<<Self as BaseQuery>::Target<'r>>, &'static str> {
-        for element in self.container.iter().take(1) {
-            return Ok(QueryEntry::new(
-                element.0.clone(),
-                element.1.borrow(),
-            ))
-        }
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> Query<'a, C, F> {
+    /// Component captured for provided entity, if any.
+    pub fn get(&self, entity_id: EntityId) -> Option<ComponentValueRef<'a, C>> {
+        self.tables
+            .get(entity_id, None)
+            .map(|entry| ComponentValueRef::<C>::new(entry.inner().borrow()))
+    }
 
-        Err("Not found")
+    pub fn contains(&self, entity_id: EntityId) -> bool {
+        self.tables.get(entity_id, None).is_some()
     }
 
-    pub fn component_mut<'r>(
-        &'r self
-    ) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
-        for element in self.container.iter().take(1) {
-            return Ok(QueryEntry::new(
-                element.0.clone(),
-                element.1.borrow_mut(),
-            ))
-        }
+    /// Retrieve first component found
+    pub fn component<'r>(&'r self) -> Result<QueryEntry<ComponentValueRef<'r, C>>, &'static str> {
+        self.iter_components()
+            .next()
+            .ok_or("Not found")
+    }
 
-        Err("Not found")
+    pub fn component_mut<'r>(&'r self) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
+        self.tables
+            .iter()
+            .next()
+            .map(|(location, entity_id, entry)| QueryEntry::new(
+                entity_id,
+                ComponentValueMutRef::<C>::new(entry.inner().borrow_mut(), entry.ticks()),
+            ).with_location(Some(location)))
+            .ok_or("Not found")
     }
 
     pub fn count(&self) -> usize {
-        self.container.len()
+        self.tables.iter().count()
     }
 }
 
-impl<'a, C: 'static + AnyComponent> BaseQuery for Query<'a, C> {
-    type Target<'t> = ComponentValueRef<'t, C> where Self: 't;
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> BaseQuery<'a> for Query<'a, C, F> {
+    type Target<'t> = ComponentValueRef<'t, C> where Self: 't, 'a: 't;
 
-    fn capture_components(&mut self, components: &Components) {
-        for component in components.iter_kind::<C>() {
-            self.container.insert(*components.entity_id(), component.consume().unwrap());
-            break;
-        }
+    fn capture_archetype(&mut self, archetype: &'a Archetype, ticks: SystemTicks) {
+        self.tables.capture(archetype, ticks);
+    }
+
+    fn fetch<'i>(&'i self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<Self::Target<'i>> {
+        self.tables
+            .get(entity_id, location)
+            .map(|entry| ComponentValueRef::<C>::new(entry.inner().borrow()))
     }
 
     fn iter_components<'i>(
         &'i self
     ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
         ComponentQueryIterator::new(
-            self.container
+            self.tables
                 .iter()
-                .map(|c| QueryEntry::new(
-                    c.0.clone(),
-                    c.1.borrow(),
-                ))
+                .map(|(location, entity_id, entry)| QueryEntry::new(
+                    entity_id,
+                    ComponentValueRef::<C>::new(entry.inner().borrow()),
+                ).with_location(Some(location)))
         )
     }
 }
@@ -95,72 +103,69 @@ impl<'a, C: 'static + AnyComponent> BaseQuery for Query<'a, C> {
 //
 //
 
-pub struct MutQuery<'a, C: 'static + AnyComponent> {
-    container: BTreeMap<EntityId, ComponentStrongRef<'a, C>>,
+pub struct MutQuery<'a, C: 'static + AnyComponent, F: QueryFilter = ()> {
+    tables: TableCapture<'a, C, F>,
 }
 
-
-impl<'a, C: 'static + AnyComponent> Default for MutQuery<'a, C> {
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> Default for MutQuery<'a, C, F> {
     fn default() -> Self {
         Self {
-            container: BTreeMap::default(),
+            tables: TableCapture::default(),
         }
     }
 }
 
-impl<'a, C: 'static + AnyComponent> MutQuery<'a, C> {
-    /// Retrieve first component found
-    pub fn component<'r>(
-        &'r self
-    ) -> Result<QueryEntry<<Self as BaseQuery>::Target<'r>>, &'static str> {
-        for element in self.container.iter().take(1) {
-            return Ok(QueryEntry::new(
-                element.0.clone(),
-                element.1.borrow_mut(),
-            ))
-        }
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> MutQuery<'a, C, F> {
+    /// Component captured for provided entity, if any.
+    pub fn get(&self, entity_id: EntityId) -> Option<ComponentValueMutRef<'a, C>> {
+        self.tables
+            .get(entity_id, None)
+            .map(|entry| ComponentValueMutRef::<C>::new(entry.inner().borrow_mut(), entry.ticks()))
+    }
 
-        Err("Not found")
+    pub fn contains(&self, entity_id: EntityId) -> bool {
+        self.tables.get(entity_id, None).is_some()
     }
 
-    pub fn component_mut<'r>(
-        &'r self
-    ) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
-        for element in self.container.iter().take(1) {
-            return Ok(QueryEntry::new(
-                element.0.clone(),
-                element.1.borrow_mut(),
-            ))
-        }
+    /// Retrieve first component found
+    pub fn component<'r>(&'r self) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
+        self.iter_components()
+            .next()
+            .ok_or("Not found")
+    }
 
-        Err("Not found")
+    pub fn component_mut<'r>(&'r self) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
+        self.component()
     }
 
     pub fn count(&self) -> usize {
-        self.container.len()
+        self.tables.iter().count()
     }
 }
 
-impl<'a, C: 'static + AnyComponent> BaseQuery for MutQuery<'a, C> {
-    type Target<'t> = ComponentValueMutRef<'t, C> where Self: 't;
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> BaseQuery<'a> for MutQuery<'a, C, F> {
+    type Target<'t> = ComponentValueMutRef<'t, C> where Self: 't, 'a: 't;
 
-    fn capture_components(&mut self, components: &Components) {
-        for component in components.iter_kind::<C>() {
-            self.container.insert(*components.entity_id(), component.consume().unwrap());
-            break;
-        }
+    fn capture_archetype(&mut self, archetype: &'a Archetype, ticks: SystemTicks) {
+        self.tables.capture(archetype, ticks);
+    }
+
+    fn fetch<'i>(&'i self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<Self::Target<'i>> {
+        self.tables
+            .get(entity_id, location)
+            .map(|entry| ComponentValueMutRef::<C>::new(entry.inner().borrow_mut(), entry.ticks()))
     }
 
     fn iter_components<'i>(
         &'i self
     ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
         ComponentQueryIterator::new(
-            self.container
+            self.tables
                 .iter()
-                .map(|c| QueryEntry::new(
-                    c.0.clone(),
-                    c.1.borrow_mut(),
-                ))
+                .map(|(location, entity_id, entry)| QueryEntry::new(
+                    entity_id,
+                    ComponentValueMutRef::<C>::new(entry.inner().borrow_mut(), entry.ticks()),
+                ).with_location(Some(location)))
         )
     }
 }
@@ -0,0 +1,97 @@
+use std::marker::PhantomData;
+
+use crate::ecs::{
+    archetype::{
+        Archetype,
+        ArchetypeColumn,
+        EntityLocation,
+    },
+    component::{
+        AnyComponent,
+        ComponentEntry,
+        SystemTicks,
+    },
+    entity::EntityId,
+};
+
+use super::QueryFilter;
+
+/// Archetype tables captured by a query, with their column of component `C`.
+/// Entries are read straight from columns, nothing is copied at capture.
+pub(super) struct TableCapture<'a, C, F> {
+    /// Sorted by archetype id, so a location is found with a binary search.
+    tables: Vec<(&'a Archetype, &'a ArchetypeColumn)>,
+    ticks: SystemTicks,
+    phantom: PhantomData<(C, F)>,
+}
+
+impl<'a, C, F> Default for TableCapture<'a, C, F> {
+    fn default() -> Self {
+        Self {
+            tables: Vec::new(),
+            ticks: SystemTicks::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: 'static + AnyComponent, F: QueryFilter> TableCapture<'a, C, F> {
+    pub fn capture(&mut self, archetype: &'a Archetype, ticks: SystemTicks) {
+        self.ticks = ticks;
+
+        if archetype.is_empty() || !F::matches_archetype(archetype) {
+            return;
+        }
+
+        if let Some(column) = archetype.column::<C>() {
+            let index = self.tables.partition_point(|(a, _)| a.id() < archetype.id());
+            self.tables.insert(index, (archetype, column));
+        }
+    }
+
+    /// Every matching entry, table by table in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityLocation, EntityId, &'a ComponentEntry)> + '_ {
+        let ticks = self.ticks;
+
+        self.tables
+            .iter()
+            .flat_map(move |&(archetype, column)| {
+                archetype.entities()
+                         .iter()
+                         .zip(column.iter())
+                         .enumerate()
+                         .filter(move |(row, _)| F::matches(archetype, *row, ticks))
+                         .map(move |(row, (entity_id, entries))| (
+                             EntityLocation { archetype: archetype.id(), row },
+                             *entity_id,
+                             &entries[0],
+                         ))
+            })
+    }
+
+    /// Entry of provided entity, it's found directly when it's location is known.
+    pub fn get(&self, entity_id: EntityId, location: Option<EntityLocation>) -> Option<&'a ComponentEntry> {
+        let (archetype, column, row) = match location {
+            Some(location) => {
+                let index = self.tables
+                    .binary_search_by_key(&location.archetype, |(a, _)| a.id())
+                    .ok()?;
+
+                let (archetype, column) = self.tables[index];
+                (archetype, column, location.row)
+            },
+            None => self.tables
+                .iter()
+                .find_map(|&(archetype, column)| {
+                    let row = archetype.entities().iter().position(|id| *id == entity_id)?;
+                    Some((archetype, column, row))
+                })?,
+        };
+
+        if archetype.entities().get(row) != Some(&entity_id) || !F::matches(archetype, row, self.ticks) {
+            return None;
+        }
+
+        column.get(row)?.first()
+    }
+}
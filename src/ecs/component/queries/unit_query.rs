@@ -1,15 +1,16 @@
 use std::ops::Deref;
 
 use crate::ecs::{
+    archetype::{Archetype, EntityLocation},
     component::{
         AnyComponent,
-        Components,
         ComponentQueryIterator,
         ComponentRef,
         ComponentStrongRef,
         ComponentValueRef,
         ComponentValueMutRef,
         QueryEntry,
+        SystemTicks,
     },
     entity::EntityId,
 };
@@ -44,10 +45,10 @@ impl<'a, C: 'static + AnyComponent> UnitQuery<'a, C> {
     /// Retrieve first component found
     pub fn component<'r>(
         &'r self
-    ) -> Result<QueryEntry<<Self as BaseQuery>::Target<'r>>, &'static str> {
+    ) -> Result<QueryEntry<ComponentValueRef<'r, C>>, &'static str> {
         if let Some(element) = &self.container {
             return Ok(QueryEntry::new(
-                element.0.clone(),
+                element.0,
                 element.1.borrow(),
             ))
         }
@@ -60,7 +61,7 @@ impl<'a, C: 'static + AnyComponent> UnitQuery<'a, C> {
     ) -> Result<QueryEntry<ComponentValueMutRef<'r, C>>, &'static str> {
         if let Some(element) = &self.container {
             return Ok(QueryEntry::new(
-                element.0.clone(),
+                element.0,
                 element.1.borrow_mut(),
             ))
         }
@@ -80,16 +81,26 @@ impl<'a, C: 'static + AnyComponent> UnitQuery<'a, C> {
     }
 }
 
-impl<'a, C: 'static + AnyComponent> BaseQuery for UnitQuery<'a, C> {
-    type Target<'t> = ComponentValueRef<'t, C> where Self: 't;
+impl<'a, C: 'static + AnyComponent> BaseQuery<'a> for UnitQuery<'a, C> {
+    type Target<'t> = ComponentValueRef<'t, C> where Self: 't, 'a: 't;
 
-    fn capture_components(&mut self, components: &Components) {
-        match components.iter_kind::<C>().next() {
-            Some(component) => self.container.replace((component.entity_id(), component.consume().unwrap())),
+    fn capture_archetype(&mut self, archetype: &'a Archetype, _ticks: SystemTicks) {
+        let first = archetype.column::<C>()
+                             .and_then(|column| column.iter().next());
+
+        match first {
+            Some(entries) => self.container.replace((entries[0].entity_id(), entries[0].get())),
             None => None,
         };
     }
 
+    fn fetch<'i>(&'i self, entity_id: EntityId, _location: Option<EntityLocation>) -> Option<Self::Target<'i>> {
+        self.container
+            .as_ref()
+            .filter(|c| c.0 == entity_id)
+            .map(|c| c.1.borrow())
+    }
+
     fn iter_components<'i>(
         &'i self
     ) -> ComponentQueryIterator<'i, QueryEntry<Self::Target<'i>>> {
@@ -97,7 +108,7 @@ impl<'a, C: 'static + AnyComponent> BaseQuery for UnitQuery<'a, C> {
             self.container
                 .iter()
                 .map(|c| QueryEntry::new(
-                    c.0.clone(),
+                    c.0,
                     c.1.borrow(),
                 ))
         )
@@ -30,6 +30,11 @@ impl ComponentAnyRef {
         self.entity_id
     }
 
+    /// Component still exists, it'll stop being valid when removed from it's entity.
+    pub fn is_valid(&self) -> bool {
+        self.weak.strong_count() > 0
+    }
+
     pub fn retrieve(&self) -> Result<ComponentStrongAnyRef, &'static str> {
         match self.weak.upgrade() {
             Some(strong) => Ok(strong),
@@ -9,6 +9,7 @@ use crate::ecs::{
         AnyComponent,
         Component,
         ComponentStrongRef,
+        ComponentTicks,
     },
     entity::EntityId,
 };
@@ -16,6 +17,7 @@ use crate::ecs::{
 pub struct ComponentRef<C: Component> {
     entity_id: EntityId,
     weak: Weak<RefCell<(dyn AnyComponent + 'static)>>,
+    ticks: Weak<ComponentTicks>,
     phantom: PhantomData<C>,
 }
 
@@ -23,10 +25,12 @@ impl<C: 'static + Component> ComponentRef<C> {
     pub(in crate::ecs::component) fn new(
         entity_id: EntityId,
         weak: Weak<RefCell<(dyn AnyComponent + 'static)>>,
+        ticks: Weak<ComponentTicks>,
     ) -> Self {
         Self {
             entity_id,
             weak,
+            ticks,
             phantom: PhantomData::default(),
         }
     }
@@ -35,17 +39,22 @@ impl<C: 'static + Component> ComponentRef<C> {
         self.entity_id
     }
 
+    /// Component still exists, it'll stop being valid when removed from it's entity.
+    pub fn is_valid(&self) -> bool {
+        self.weak.strong_count() > 0
+    }
+
     pub fn retrieve(&self) -> Result<ComponentStrongRef<C>, &'static str> {
-        match self.weak.upgrade() {
-            Some(strong) => Ok(ComponentStrongRef::new(strong)),
-            None => Err("Can't upgrade from weak ref"),
+        match (self.weak.upgrade(), self.ticks.upgrade()) {
+            (Some(strong), Some(ticks)) => Ok(ComponentStrongRef::new(strong, ticks)),
+            _ => Err("Can't upgrade from weak ref"),
         }
     }
 
     pub fn consume<'a>(self) -> Result<ComponentStrongRef<'a, C>, &'static str> {
-        match self.weak.upgrade() {
-            Some(strong) => Ok(ComponentStrongRef::new(strong)),
-            None => Err("Can't upgrade from weak ref"),
+        match (self.weak.upgrade(), self.ticks.upgrade()) {
+            (Some(strong), Some(ticks)) => Ok(ComponentStrongRef::new(strong, ticks)),
+            _ => Err("Can't upgrade from weak ref"),
         }
     }
 }
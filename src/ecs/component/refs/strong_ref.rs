@@ -7,28 +7,35 @@ use std::{
 use crate::ecs::component::{
     AnyComponent,
     Component,
+    ComponentTicks,
     ComponentValueRef,
     ComponentValueMutRef,
 };
 
 pub struct ComponentStrongRef<'a, C: 'static + Component> {
     strong: Rc<RefCell<dyn AnyComponent>>,
+    ticks: Rc<ComponentTicks>,
     phantom: PhantomData<&'a C>,
 }
 
 impl<'a, C: 'static + Component> ComponentStrongRef<'a, C> {
-    pub(crate) fn new(strong: Rc<RefCell<dyn AnyComponent>>) -> Self {
+    pub(crate) fn new(strong: Rc<RefCell<dyn AnyComponent>>, ticks: Rc<ComponentTicks>) -> Self {
         Self {
             strong,
+            ticks,
             phantom: Default::default(),
         }
     }
 
+    pub fn ticks(&self) -> &ComponentTicks {
+        &self.ticks
+    }
+
     pub fn borrow(&self) -> ComponentValueRef<C> {
         ComponentValueRef::new(self.strong.borrow())
     }
 
     pub fn borrow_mut(&self) -> ComponentValueMutRef<C> {
-        ComponentValueMutRef::new(self.strong.borrow_mut())
+        ComponentValueMutRef::new(self.strong.borrow_mut(), &self.ticks)
     }
 }
@@ -7,17 +7,22 @@ use std::{
 use crate::ecs::component::{
     AnyComponent,
     Component,
+    ComponentTicks,
 };
 
+/// Mutable access to a component.
+/// Component is marked as changed only when mutably dereferenced.
 pub struct ComponentValueMutRef<'a, C> {
     value: RefMut<'a, dyn AnyComponent>,
+    ticks: &'a ComponentTicks,
     phantom: PhantomData<C>,
 }
 
 impl<'a, C: 'static + Component> ComponentValueMutRef<'a, C> {
-    pub(super) fn new(value: RefMut<'a, dyn AnyComponent>) -> Self {
+    pub(in crate::ecs) fn new(value: RefMut<'a, dyn AnyComponent>, ticks: &'a ComponentTicks) -> Self {
         Self {
             value,
+            ticks,
             phantom: PhantomData::default(),
         }
     }
@@ -33,6 +38,7 @@ impl<'a, C: 'static + Component> Deref for ComponentValueMutRef<'a, C> {
 
 impl<'a, C: 'static + Component> DerefMut for ComponentValueMutRef<'a, C> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ticks.mark_changed();
         self.value.as_any_mut().downcast_mut().unwrap()
     }
 }
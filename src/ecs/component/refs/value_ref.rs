@@ -15,7 +15,7 @@ pub struct ComponentValueRef<'a, C> {
 }
 
 impl<'a, C: 'static + Component> ComponentValueRef<'a, C> {
-    pub(super) fn new(value: Ref<'a, dyn AnyComponent>) -> Self {
+    pub(in crate::ecs) fn new(value: Ref<'a, dyn AnyComponent>) -> Self {
         Self {
             value,
             phantom: PhantomData::default(),
@@ -0,0 +1,61 @@
+use std::{
+    cell::{Cell, OnceCell},
+    rc::Rc,
+};
+
+/// Change tick, it's incremented before every system run.
+pub type Tick = u64;
+
+/// Tracks when a component was added and last changed.
+#[derive(Debug, Default)]
+pub struct ComponentTicks {
+    added: Cell<Tick>,
+    changed: Cell<Tick>,
+    clock: OnceCell<Rc<Cell<Tick>>>,
+}
+
+impl ComponentTicks {
+    pub fn added(&self) -> Tick {
+        self.added.get()
+    }
+
+    pub fn changed(&self) -> Tick {
+        self.changed.get()
+    }
+
+    pub fn is_added(&self, system_ticks: SystemTicks) -> bool {
+        system_ticks.is_newer(self.added.get())
+    }
+
+    pub fn is_changed(&self, system_ticks: SystemTicks) -> bool {
+        system_ticks.is_newer(self.changed.get())
+    }
+
+    /// Starts following world clock, only the first call has effect.
+    pub(in crate::ecs) fn attach(&self, clock: &Rc<Cell<Tick>>) {
+        if self.clock.set(clock.clone()).is_ok() {
+            self.added.set(clock.get());
+            self.changed.set(clock.get());
+        }
+    }
+
+    pub(in crate::ecs) fn mark_changed(&self) {
+        if let Some(clock) = self.clock.get() {
+            self.changed.set(clock.get());
+        }
+    }
+}
+
+/// Ticks of a running system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SystemTicks {
+    pub last_run: Tick,
+    pub this_run: Tick,
+}
+
+impl SystemTicks {
+    /// Tick happened after system's last run.
+    pub fn is_newer(&self, tick: Tick) -> bool {
+        tick > self.last_run
+    }
+}
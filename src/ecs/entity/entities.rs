@@ -1,21 +1,40 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    any::TypeId,
+    cell::Cell,
     collections::hash_map::HashMap,
     rc::Rc,
 };
-use super::{Entity, EntityBuilder, EntityId, EntitiesIter};
+
+use crate::ecs::{
+    archetype::{Archetypes, EntityLocation},
+    component::{AnyComponent, Components, Tick},
+    hierarchy::{Children, Parent},
+};
+use super::{
+    Entity,
+    EntityAllocator,
+    EntityBuilder,
+    EntityId,
+    EntityMut,
+    EntityRef,
+    EntitiesIter,
+};
 
 pub struct Entities {
-    entries: HashMap<EntityId, Rc<RefCell<Entity>>>,
-    next_id: EntityId,
+    archetypes: Archetypes,
+    locations: HashMap<EntityId, EntityLocation>,
+    ids: Rc<EntityAllocator>,
+    change_tick: Rc<Cell<Tick>>,
     pub(super) setup_entity: Option<Box<dyn FnMut(&mut Entity)>>,
 }
 
 impl Entities {
     pub fn new() -> Self {
         Self {
-            entries: HashMap::new(),
-            next_id: EntityId::default(),
+            archetypes: Archetypes::new(),
+            locations: HashMap::new(),
+            ids: Rc::default(),
+            change_tick: Rc::new(Cell::new(1)),
             setup_entity: None,
         }
     }
@@ -25,58 +44,269 @@ impl Entities {
         self
     }
 
-    pub fn create(&mut self) -> EntityBuilder {
-        let id = self.next_id();
+    pub fn create(&mut self) -> EntityBuilder<'_> {
+        let id = self.ids.allocate();
         EntityBuilder::new(Entity::new(id), self)
     }
 
-    pub fn get(&self, id: EntityId) -> Option<Ref<Entity>> {
-        self.entries
-            .get(&id)
-            .map(|e| e.borrow())
+    /// Creates an entity with an id reserved from `Entities::ids`.
+    pub(in crate::ecs) fn create_reserved(&mut self, id: EntityId) -> EntityBuilder<'_> {
+        EntityBuilder::new(Entity::new(id), self)
     }
 
-    pub fn get_mut(&mut self, id: EntityId) -> Option<RefMut<Entity>> {
-        self.entries
-            .get_mut(&id)
-            .map(|e| e.borrow_mut())
+    /// Id allocator, ids reserved from it can be used later with their entity.
+    pub fn ids(&self) -> &Rc<EntityAllocator> {
+        &self.ids
     }
 
-    pub fn count(&self) -> usize {
-        self.entries.len()
+    pub fn get(&self, id: EntityId) -> Option<EntityRef<'_>> {
+        let location = self.locations.get(&id)?;
+        let archetype = self.archetypes.get(location.archetype)?;
+
+        Some(EntityRef::new(id, archetype, location.row))
+    }
+
+    pub fn get_mut(&mut self, id: EntityId) -> Option<EntityMut<'_>> {
+        match self.locations.contains_key(&id) {
+            true => Some(EntityMut::new(id, self)),
+            false => None,
+        }
     }
 
-    pub fn iter(&self) -> EntitiesIter {
-        self.entries.values().into()
+    /// Removes an entity and it's descendants, unregistering every component they have.
+    /// Their id indices will be recycled with a new generation.
+    pub fn despawn(&mut self, id: EntityId) -> bool {
+        if !self.contains(id) {
+            return false;
+        }
+
+        self.detach(id);
+
+        for descendant in self.descendants(id) {
+            self.despawn_single(descendant);
+        }
+
+        self.despawn_single(id)
+    }
+
+    /// Attaches `child` to `parent`, detaching it from it's previous parent.
+    /// Fails when either entity doesn't exist, or `parent` is `child` itself or one of it's descendants.
+    pub fn set_parent(&mut self, child: EntityId, parent: EntityId) -> bool {
+        if !self.contains(child) || !self.contains(parent) {
+            return false;
+        }
+
+        if self.ancestors(parent).any(|ancestor| ancestor == child) || parent == child {
+            return false;
+        }
+
+        self.detach(child);
+        self.register_component(child, Parent(parent));
+
+        let children = self.get(parent)
+                           .and_then(|entity| entity.get::<Children>());
+
+        match children {
+            Some(children) => children.borrow_mut().0.push(child),
+            None => {
+                self.register_component(parent, Children(vec![child]));
+            },
+        }
+
+        true
+    }
+
+    /// Detaches `child` from it's parent, it becomes a root entity.
+    pub fn remove_parent(&mut self, child: EntityId) -> bool {
+        self.remove_component::<Parent>(child)
+    }
+
+    pub fn parent(&self, id: EntityId) -> Option<EntityId> {
+        self.get(id)?
+            .get::<Parent>()
+            .map(|parent| parent.borrow().id())
+    }
+
+    pub fn children(&self, id: EntityId) -> Vec<EntityId> {
+        self.get(id)
+            .and_then(|entity| entity.get::<Children>())
+            .map(|children| children.borrow().0.clone())
+            .unwrap_or_default()
+    }
+
+    /// Parent, grandparent and so on, up to a root entity.
+    pub fn ancestors(&self, id: EntityId) -> impl Iterator<Item = EntityId> + '_ {
+        std::iter::successors(self.parent(id), |ancestor| self.parent(*ancestor))
+    }
+
+    /// Every entity below provided one, depth first.
+    pub fn descendants(&self, id: EntityId) -> Vec<EntityId> {
+        let mut descendants = Vec::new();
+        let mut pending = self.children(id);
+        pending.reverse();
+
+        while let Some(descendant) = pending.pop() {
+            descendants.push(descendant);
+            pending.extend(self.children(descendant).into_iter().rev());
+        }
+
+        descendants
     }
 
-    /*
-    pub fn iter<'a, F>(&'a self) -> EntitiesIter<'a, F> where
-        F: FnMut(Rc<RefCell<Entity>>) -> Ref<'a, Entity>
+    /// Removes first component, from an entity, which matches provided type.
+    pub fn remove_component<C>(&mut self, id: EntityId) -> bool where
+        C: AnyComponent + 'static
     {
-        self.entries
-            .values()
-            .map(|e| e.borrow())
-            .into()
+        self.remove_component_kind(id, TypeId::of::<C>())
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.locations.contains_key(&id)
+    }
+
+    pub fn location(&self, id: EntityId) -> Option<EntityLocation> {
+        self.locations.get(&id).copied()
+    }
+
+    pub fn count(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn iter(&self) -> EntitiesIter<'_> {
+        EntitiesIter::new(&self.archetypes)
+    }
+
+    pub fn archetypes(&self) -> &Archetypes {
+        &self.archetypes
+    }
+
+    /// Current change tick, components changed now are stamped with it.
+    /// It's incremented after every system run.
+    pub fn change_tick(&self) -> Tick {
+        self.change_tick.get()
+    }
+
+    pub(in crate::ecs) fn increment_change_tick(&self) -> Tick {
+        let tick = self.change_tick.get() + 1;
+        self.change_tick.set(tick);
+        tick
     }
-    */
 
     pub(super) fn register(&mut self, entity: Entity) {
         let id = entity.id();
 
         assert!(
-            self.entries
-                .insert(id, Rc::new(RefCell::new(entity)))
-                .is_none(),
+            !self.locations.contains_key(&id),
             "Something very wrong happened when registering an entity with id {}.",
             id,
         );
+
+        self.insert_components(entity.into_components());
     }
 
-    fn next_id(&mut self) -> EntityId {
-        let id = self.next_id;
-        self.next_id += 1;
-        id
+    pub(super) fn register_component<C>(&mut self, id: EntityId, component: C) -> Option<C> where
+        C: AnyComponent + 'static
+    {
+        let mut components = self.take_components(id)?;
+        let previous = components.internal_register(component, id);
+        self.insert_components(components);
+
+        previous
+    }
+
+    fn remove_component_kind(&mut self, id: EntityId, kind: TypeId) -> bool {
+        let location = match self.locations.get(&id) {
+            Some(location) => *location,
+            None => return false,
+        };
+
+        let has_kind = self.archetypes
+                           .get(location.archetype)
+                           .is_some_and(|a| a.key().contains(&kind));
+
+        if !has_kind {
+            return false;
+        }
+
+        // hierarchy is kept consistent, whichever side of it is removed
+        if kind == TypeId::of::<Parent>() {
+            self.detach(id);
+        } else if kind == TypeId::of::<Children>() {
+            for child in self.children(id) {
+                let mut components = self.take_components(child).unwrap();
+                components.remove_kind(TypeId::of::<Parent>());
+                self.insert_components(components);
+            }
+        }
+
+        let mut components = self.take_components(id).unwrap();
+        let removed = components.remove_kind(kind);
+        self.insert_components(components);
+
+        removed
+    }
+
+    fn despawn_single(&mut self, id: EntityId) -> bool {
+        match self.take_components(id) {
+            Some(mut components) => {
+                components.clear();
+                self.ids.release(id);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Removes entity from it's parent's children, it's `Parent` component is kept.
+    /// Parent's `Children` is removed once it's empty.
+    fn detach(&mut self, child: EntityId) -> bool {
+        let Some(parent) = self.parent(child) else {
+            return false;
+        };
+
+        let is_empty = self.get(parent)
+                           .and_then(|entity| entity.get::<Children>())
+                           .map(|children| {
+                               let mut children = children.borrow_mut();
+                               children.0.retain(|id| *id != child);
+                               children.0.is_empty()
+                           });
+
+        if is_empty == Some(true) {
+            self.remove_component_kind(parent, TypeId::of::<Children>());
+        }
+
+        true
+    }
+
+    /// Moves entity's components out of it's archetype.
+    /// Entity location is released and should be inserted again.
+    fn take_components(&mut self, id: EntityId) -> Option<Components> {
+        let location = self.locations.remove(&id)?;
+        let archetype = self.archetypes.get_mut(location.archetype)?;
+        let components = archetype.swap_remove(location.row);
+
+        // entity which was at last row has been moved
+        if let Some(moved_id) = archetype.entities().get(location.row) {
+            self.locations.insert(*moved_id, location);
+        }
+
+        Some(components)
+    }
+
+    fn insert_components(&mut self, components: Components) {
+        for entry in components.iter() {
+            entry.ticks().attach(&self.change_tick);
+        }
+
+        let id = *components.entity_id();
+        let archetype_id = self.archetypes.get_or_insert(components.archetype_key());
+        let row = self.archetypes
+                      .get_mut(archetype_id)
+                      .unwrap()
+                      .push(components);
+
+        self.locations.insert(id, EntityLocation { archetype: archetype_id, row });
     }
 }
 
@@ -85,3 +315,204 @@ impl Default for Entities {
         Self::new()
     }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{
+        command::Commands,
+        component::{
+            Component,
+            ComponentAttribute,
+            Components,
+            EmptyComponent,
+        },
+    };
+
+    use super::*;
+
+    thread_local! {
+        static UNREGISTERED: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+    }
+
+    #[derive(Default)]
+    struct Marker(u32);
+
+    impl Component for Marker {
+        fn attributes(&self) -> ComponentAttribute {
+            ComponentAttribute::Unique
+        }
+
+        fn registered(&mut self, _components: &mut Components) {
+        }
+
+        fn unregistered(&mut self) {
+            UNREGISTERED.with(|u| u.set(u.get() + 1));
+        }
+    }
+
+    #[test]
+    fn entities_share_archetype() {
+        let mut entities = Entities::new();
+
+        let ids: Vec<EntityId> = (0..4).map(|i| {
+            let mut e = entities.create();
+            e.register_component(Marker(i));
+
+            if i < 3 {
+                e.register_component(EmptyComponent::default());
+            }
+
+            let id = e.id();
+            e.build();
+            id
+        }).collect();
+
+        let archetype_of = |id| entities.location(id).unwrap().archetype;
+
+        assert_eq!(entities.count(), 4);
+        assert_eq!(entities.archetypes().count(), 2);
+        assert_eq!(archetype_of(ids[0]), archetype_of(ids[2]));
+        assert_ne!(archetype_of(ids[0]), archetype_of(ids[3]));
+    }
+
+    #[test]
+    fn register_component_moves_entity() {
+        let mut entities = Entities::new();
+
+        let ids: Vec<EntityId> = (0..2).map(|i| spawn_marker(&mut entities, i)).collect();
+
+        entities.get_mut(ids[0])
+                .unwrap()
+                .register_component(EmptyComponent::default());
+
+        let first = entities.get(ids[0]).unwrap();
+        assert!(first.has::<EmptyComponent>());
+        assert_eq!(first.get::<Marker>().unwrap().borrow().0, 0);
+
+        // second entity has been swapped into first entity old row
+        let second = entities.get(ids[1]).unwrap();
+        assert!(!second.has::<EmptyComponent>());
+        assert_eq!(second.get::<Marker>().unwrap().borrow().0, 1);
+        assert_eq!(entities.location(ids[1]).unwrap().row, 0);
+    }
+
+    #[test]
+    fn despawn_recycles_id() {
+        let mut entities = Entities::new();
+        let id = spawn_marker(&mut entities, 0);
+        let marker_ref = entities.get(id).unwrap().get_kind::<Marker>().unwrap();
+
+        assert!(entities.despawn(id));
+        assert!(!entities.despawn(id));
+        assert!(!marker_ref.is_valid());
+        assert!(UNREGISTERED.with(|u| u.get()) > 0);
+
+        let new_id = spawn_marker(&mut entities, 1);
+        assert_eq!(new_id.index(), id.index());
+        assert_ne!(new_id, id);
+        assert!(entities.get(id).is_none());
+
+        // dropped without being built, it's id is released
+        let discarded = entities.create().id();
+        let next_id = spawn_marker(&mut entities, 2);
+        assert_eq!(next_id.index(), discarded.index());
+        assert_ne!(next_id, discarded);
+    }
+
+    #[test]
+    fn deferred_commands() {
+        let mut entities = Entities::new();
+        let a = spawn_marker(&mut entities, 0);
+        let b = spawn_marker(&mut entities, 1);
+
+        let mut commands = Commands::new(&entities);
+        commands.despawn(a)
+                .remove::<Marker>(b)
+                .insert(b, EmptyComponent::default());
+
+        let mut spawn = commands.spawn();
+        spawn.register_component(Marker(2));
+        let spawned = spawn.build();
+
+        // dropped without being built, it's id is released
+        let discarded = commands.spawn().id();
+
+        assert_eq!(entities.count(), 2);
+
+        commands.apply(&mut entities);
+
+        assert!(commands.is_empty());
+        assert!(!entities.contains(a));
+        assert!(!entities.get(b).unwrap().has::<Marker>());
+        assert!(entities.get(b).unwrap().has::<EmptyComponent>());
+        assert_eq!(entities.count(), 2);
+        assert_eq!(entities.get(spawned).unwrap().get::<Marker>().unwrap().borrow().0, 2);
+        assert!(!entities.contains(discarded));
+
+        // reserved ids never alias an entity spawned directly
+        let reserved = commands.spawn().build();
+        commands.apply(&mut entities);
+        let direct = spawn_marker(&mut entities, 3);
+        assert_ne!(reserved, direct);
+    }
+
+    #[test]
+    fn hierarchy() {
+        let mut entities = Entities::new();
+        let ids: Vec<EntityId> = (0..5).map(|i| spawn_marker(&mut entities, i)).collect();
+
+        // 0 -> 1 -> 2, 0 -> 3
+        assert!(entities.set_parent(ids[1], ids[0]));
+        assert!(entities.set_parent(ids[2], ids[1]));
+        assert!(entities.set_parent(ids[3], ids[0]));
+
+        assert!(!entities.set_parent(ids[0], ids[2]));
+        assert!(!entities.set_parent(ids[0], ids[0]));
+
+        assert_eq!(entities.children(ids[0]), vec![ids[1], ids[3]]);
+        assert_eq!(entities.descendants(ids[0]), vec![ids[1], ids[2], ids[3]]);
+        assert_eq!(entities.ancestors(ids[2]).collect::<Vec<_>>(), vec![ids[1], ids[0]]);
+
+        // moving 3 below 4
+        assert!(entities.set_parent(ids[3], ids[4]));
+        assert_eq!(entities.children(ids[0]), vec![ids[1]]);
+        assert_eq!(entities.parent(ids[3]), Some(ids[4]));
+
+        assert!(entities.despawn(ids[1]));
+        assert!(!entities.contains(ids[2]));
+        assert!(entities.children(ids[0]).is_empty());
+
+        // last child is gone, so is parent's `Children`
+        assert!(!entities.get(ids[0]).unwrap().has::<Children>());
+
+        assert!(entities.remove_parent(ids[3]));
+        assert_eq!(entities.parent(ids[3]), None);
+        assert!(!entities.get(ids[4]).unwrap().has::<Children>());
+
+        // removing `Children` directly clears every child's `Parent`
+        assert!(entities.set_parent(ids[3], ids[4]));
+        assert!(entities.set_parent(ids[0], ids[4]));
+        assert!(entities.remove_component::<Children>(ids[4]));
+        assert_eq!(entities.parent(ids[3]), None);
+        assert_eq!(entities.parent(ids[0]), None);
+
+        // and removing `Parent` directly detaches child
+        assert!(entities.set_parent(ids[3], ids[4]));
+        assert!(entities.remove_component::<Parent>(ids[3]));
+        assert!(entities.children(ids[4]).is_empty());
+        assert!(!entities.get(ids[4]).unwrap().has::<Children>());
+
+        assert!(entities.despawn(ids[0]));
+        assert_eq!(entities.count(), 2);
+    }
+
+    fn spawn_marker(entities: &mut Entities, value: u32) -> EntityId {
+        let mut e = entities.create();
+        e.register_component(Marker(value));
+        let id = e.id();
+        e.build();
+        id
+    }
+}
@@ -1,68 +1,39 @@
 use std::{
-    cell::RefCell,
-    collections::hash_map,
-    rc::Rc,
+    iter::Enumerate,
+    slice,
 };
 
-use super::{Entity, EntityId};
+use crate::ecs::archetype::{Archetype, Archetypes};
+use super::{EntityId, EntityRef};
 
+/// Iterates every entity, archetype by archetype.
 pub struct EntitiesIter<'a> {
-    values: hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>,
+    archetypes: slice::Iter<'a, Archetype>,
+    current: Option<(&'a Archetype, Enumerate<slice::Iter<'a, EntityId>>)>,
 }
 
-impl<'a> From<hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>> for EntitiesIter<'a> {
-    fn from(values: hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>) -> Self {
+impl<'a> EntitiesIter<'a> {
+    pub(super) fn new(archetypes: &'a Archetypes) -> Self {
         Self {
-            values,
+            archetypes: archetypes.iter(),
+            current: None,
         }
     }
 }
 
-impl<'a> IntoIterator for EntitiesIter<'a> {
-    type Item = &'a Rc<RefCell<Entity>>;
-    type IntoIter = hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>;
+impl<'a> Iterator for EntitiesIter<'a> {
+    type Item = EntityRef<'a>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.values
-    }
-}
-
-/*
-pub struct EntitiesIter<'a, F> where
-    F: FnMut(Rc<RefCell<Entity>>) -> Ref<'a, Entity>
-{
-    values: std::iter::Map<std::collections::hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>, F>,
-}
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((archetype, rows)) = &mut self.current {
+                if let Some((row, id)) = rows.next() {
+                    return Some(EntityRef::new(*id, archetype, row));
+                }
+            }
 
-impl<'a, F> From<std::iter::Map<hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>, F>> for EntitiesIter<'a, F> where
-    F: FnMut(Rc<RefCell<Entity>>) -> Ref<'a, Entity>
-{
-    fn from(values: std::iter::Map<hash_map::Values<'a, EntityId, Rc<RefCell<Entity>>>, F>) -> Self {
-        Self {
-            values,
-        }
-    }
-}
-
-impl<'a, F> IntoIterator for EntitiesIter<'a, F> where
-    F: FnMut(Rc<RefCell<Entity>>) -> Ref<'a, Entity>
-{
-    type Item = &'a Ref<'a, Entity>;
-    type IntoIter = hash_map::Values<'a, EntityId, Ref<'a, Entity>>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.values
-    }
-}
-*/
-
-
-/*
-impl<'a> From<hash_map::Values<'a, EntityId, Ref<'a, Entity>>> for EntitiesIter<'a> {
-    fn from(values: hash_map::Values<'a, EntityId, Ref<'a, Entity>>) -> Self {
-        Self {
-            values,
+            let archetype = self.archetypes.next()?;
+            self.current = Some((archetype, archetype.entities().iter().enumerate()));
         }
     }
 }
-*/
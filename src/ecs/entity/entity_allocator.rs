@@ -0,0 +1,40 @@
+use std::cell::RefCell;
+
+use super::EntityId;
+
+/// Hands out entity ids, recycling indices of despawned entities with a new generation.
+///
+/// It's shared between `Entities` and `Commands`, so an id can be reserved while systems run.
+///
+/// [`Commands`]: crate::ecs::command::Commands
+#[derive(Debug, Default)]
+pub struct EntityAllocator {
+    generations: RefCell<Vec<u32>>,
+    free_indices: RefCell<Vec<u32>>,
+}
+
+impl EntityAllocator {
+    pub(in crate::ecs) fn allocate(&self) -> EntityId {
+        let mut generations = self.generations.borrow_mut();
+
+        match self.free_indices.borrow_mut().pop() {
+            Some(index) => EntityId::new(index, generations[index as usize]),
+            None => {
+                let index = generations.len() as u32;
+                generations.push(0);
+                EntityId::new(index, 0)
+            },
+        }
+    }
+
+    /// Makes id's index available again, id itself becomes stale.
+    pub(in crate::ecs) fn release(&self, id: EntityId) {
+        let mut generations = self.generations.borrow_mut();
+        let generation = &mut generations[id.index() as usize];
+
+        if *generation == id.generation() {
+            *generation = generation.wrapping_add(1);
+            self.free_indices.borrow_mut().push(id.index());
+        }
+    }
+}
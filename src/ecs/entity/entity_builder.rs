@@ -1,9 +1,11 @@
-use std::ops::{Deref, DerefMut};
+use std::{mem, ops::{Deref, DerefMut}};
 
 use super::{Entity, Entities};
 
+/// It's id is allocated when it's created, it's released again when it's dropped without `build`.
 pub struct EntityBuilder<'a> {
     entity: Entity,
+    built: bool,
     entities: &'a mut Entities,
 }
 
@@ -16,12 +18,25 @@ impl<'a> EntityBuilder<'a> {
 
         Self {
             entity,
+            built: false,
             entities,
         }
     }
 
-    pub fn build(self) {
-        self.entities.register(self.entity)
+    pub fn build(mut self) {
+        let id = self.entity.id();
+        let entity = mem::replace(&mut self.entity, Entity::new(id));
+        self.built = true;
+
+        self.entities.register(entity)
+    }
+}
+
+impl<'a> Drop for EntityBuilder<'a> {
+    fn drop(&mut self) {
+        if !self.built {
+            self.entities.ids().release(self.entity.id());
+        }
     }
 }
 
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Identifies an entity.
+///
+/// Indices are recycled after an entity is despawned, generation is bumped
+/// every time, so a stale id will never alias a newer entity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityId {
+    index: u32,
+    generation: u32,
+}
+
+impl EntityId {
+    pub(super) fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
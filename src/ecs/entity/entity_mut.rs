@@ -0,0 +1,44 @@
+use crate::ecs::component::AnyComponent;
+
+use super::{Entities, EntityId, EntityRef};
+
+/// Write access to a registered entity.
+/// Structural changes moves it to another archetype.
+pub struct EntityMut<'a> {
+    id: EntityId,
+    entities: &'a mut Entities,
+}
+
+impl<'a> EntityMut<'a> {
+    pub(super) fn new(id: EntityId, entities: &'a mut Entities) -> Self {
+        Self {
+            id,
+            entities,
+        }
+    }
+
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+
+    pub fn entity_ref(&self) -> EntityRef<'_> {
+        self.entities.get(self.id).unwrap()
+    }
+
+    pub fn register_component<C>(&mut self, component: C) -> Option<C> where
+        C: AnyComponent + 'static
+    {
+        self.entities.register_component(self.id, component)
+    }
+
+    /// Removes first component which matches provided type, if any.
+    pub fn remove_component<C>(&mut self) -> bool where
+        C: AnyComponent + 'static
+    {
+        self.entities.remove_component::<C>(self.id)
+    }
+
+    pub fn despawn(self) {
+        self.entities.despawn(self.id);
+    }
+}
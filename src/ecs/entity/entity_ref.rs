@@ -0,0 +1,74 @@
+use crate::ecs::{
+    archetype::Archetype,
+    component::{
+        AnyComponent,
+        ComponentEntry,
+        ComponentRef,
+        ComponentStrongRef,
+    },
+};
+
+use super::EntityId;
+
+/// Read access to a registered entity.
+pub struct EntityRef<'a> {
+    id: EntityId,
+    archetype: &'a Archetype,
+    row: usize,
+}
+
+impl<'a> EntityRef<'a> {
+    pub(super) fn new(id: EntityId, archetype: &'a Archetype, row: usize) -> Self {
+        Self {
+            id,
+            archetype,
+            row,
+        }
+    }
+
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+
+    pub fn archetype(&self) -> &'a Archetype {
+        self.archetype
+    }
+
+    pub fn has<C>(&self) -> bool where
+        C: AnyComponent + 'static
+    {
+        self.archetype.contains::<C>()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a ComponentEntry> {
+        self.archetype.row(self.row)
+    }
+
+    pub fn iter_kind<C>(&self) -> impl Iterator<Item = ComponentRef<C>> + 'a where
+        C: AnyComponent + 'static
+    {
+        self.archetype
+            .column::<C>()
+            .and_then(|column| column.get(self.row))
+            .unwrap_or_default()
+            .iter()
+            .map(ComponentEntry::get_ref)
+    }
+
+    /// Get first component with matches provided type.
+    pub fn get_kind<C>(&self) -> Option<ComponentRef<C>> where
+        C: AnyComponent + 'static
+    {
+        self.iter_kind().next()
+    }
+
+    pub fn get<C>(&self) -> Option<ComponentStrongRef<'a, C>> where
+        C: AnyComponent + 'static
+    {
+        self.archetype
+            .column::<C>()?
+            .get(self.row)?
+            .first()
+            .map(ComponentEntry::get)
+    }
+}
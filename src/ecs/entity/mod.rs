@@ -4,16 +4,26 @@ pub use entities::Entities;
 mod entities_iter;
 pub use entities_iter::EntitiesIter;
 
+mod entity_allocator;
+pub use entity_allocator::EntityAllocator;
+
 mod entity_builder;
 pub use entity_builder::EntityBuilder;
 
+mod entity_id;
+pub use entity_id::EntityId;
+
+mod entity_mut;
+pub use entity_mut::EntityMut;
+
+mod entity_ref;
+pub use entity_ref::EntityRef;
+
 use crate::ecs::component::{
     AnyComponent,
     Components
 };
 
-pub type EntityId = u32;
-
 pub struct Entity {
     id: EntityId,
     components: Components,
@@ -45,7 +55,18 @@ impl Entity {
         self.components.internal_register(component, self.id())
     }
 
+    /// Removes first component which matches provided type, if any.
+    pub fn remove_component<C>(&mut self) -> bool where
+        C: AnyComponent + 'static
+    {
+        self.components.remove::<C>()
+    }
+
     pub fn components(&self) -> &Components {
         &self.components
     }
+
+    pub(super) fn into_components(self) -> Components {
+        self.components
+    }
 }
@@ -0,0 +1,45 @@
+use crate::ecs::{
+    component::{
+        Component,
+        ComponentAttribute,
+        Components,
+    },
+    entity::EntityId,
+};
+
+/// Entities which have this one as their `Parent`, in the order they were attached.
+/// It's removed along with the last child, removing it detaches every child.
+///
+/// [`Parent`]: super::Parent
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Children(pub(crate) Vec<EntityId>);
+
+impl Children {
+    pub fn iter(&self) -> impl Iterator<Item = EntityId> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.0.contains(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Component for Children {
+    fn attributes(&self) -> ComponentAttribute {
+        ComponentAttribute::Unique
+    }
+
+    fn registered(&mut self, _components: &mut Components) {
+    }
+
+    fn unregistered(&mut self) {
+    }
+}
@@ -0,0 +1,5 @@
+mod parent;
+pub use parent::Parent;
+
+mod children;
+pub use children::Children;
@@ -0,0 +1,37 @@
+use crate::ecs::{
+    component::{
+        Component,
+        ComponentAttribute,
+        Components,
+    },
+    entity::EntityId,
+};
+
+/// Entity which owns this one, it's despawned along with it.
+///
+/// It's managed by `Entities::set_parent` and `Entities::remove_parent`,
+/// registering it directly won't update parent's `Children`.
+///
+/// [`Entities::set_parent`]: crate::ecs::entity::Entities::set_parent
+/// [`Entities::remove_parent`]: crate::ecs::entity::Entities::remove_parent
+/// [`Children`]: super::Children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(pub(crate) EntityId);
+
+impl Parent {
+    pub fn id(&self) -> EntityId {
+        self.0
+    }
+}
+
+impl Component for Parent {
+    fn attributes(&self) -> ComponentAttribute {
+        ComponentAttribute::Unique
+    }
+
+    fn registered(&mut self, _components: &mut Components) {
+    }
+
+    fn unregistered(&mut self) {
+    }
+}
@@ -0,0 +1,11 @@
+mod res;
+pub use res::{Res, ResMut};
+
+mod resource_error;
+pub use resource_error::ResourceError;
+
+mod resources;
+pub use resources::Resources;
+
+mod system_resources;
+pub use system_resources::SystemResources;
@@ -0,0 +1,52 @@
+use std::{
+    cell::{Ref, RefMut},
+    ops::{Deref, DerefMut},
+};
+
+/// Shared access to a resource of type `R`.
+pub struct Res<'a, R: 'static> {
+    value: Ref<'a, R>,
+}
+
+impl<'a, R: 'static> Res<'a, R> {
+    pub(super) fn new(value: Ref<'a, R>) -> Self {
+        Self {
+            value,
+        }
+    }
+}
+
+impl<'a, R: 'static> Deref for Res<'a, R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Exclusive access to a resource of type `R`.
+pub struct ResMut<'a, R: 'static> {
+    value: RefMut<'a, R>,
+}
+
+impl<'a, R: 'static> ResMut<'a, R> {
+    pub(super) fn new(value: RefMut<'a, R>) -> Self {
+        Self {
+            value,
+        }
+    }
+}
+
+impl<'a, R: 'static> Deref for ResMut<'a, R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'a, R: 'static> DerefMut for ResMut<'a, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
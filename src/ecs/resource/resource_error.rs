@@ -0,0 +1,12 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum ResourceError {
+    #[error("resource not found: {0:?}")]
+    NotFound(&'static str),
+
+    #[error("resource is already borrowed: {0:?}")]
+    AlreadyBorrowed(&'static str),
+}
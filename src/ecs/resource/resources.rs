@@ -0,0 +1,95 @@
+use std::{
+    any::{Any, TypeId, type_name},
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+};
+
+use super::{Res, ResMut, ResourceError};
+
+/// Typed map of singletons shared between systems.
+/// There is at most one resource per type.
+#[derive(Default)]
+pub struct Resources {
+    entries: HashMap<TypeId, RefCell<Box<dyn Any>>>,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a resource, returning previous one with same type.
+    pub fn insert_resource<R: 'static>(&mut self, resource: R) -> Option<R> {
+        self.entries
+            .insert(TypeId::of::<R>(), RefCell::new(Box::new(resource)))
+            .and_then(|previous| previous.into_inner().downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    pub fn remove_resource<R: 'static>(&mut self) -> Option<R> {
+        self.entries
+            .remove(&TypeId::of::<R>())
+            .and_then(|previous| previous.into_inner().downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    pub fn contains<R: 'static>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<R>())
+    }
+
+    pub fn get<R: 'static>(&self) -> Result<Res<'_, R>, ResourceError> {
+        let entry = self.entries
+            .get(&TypeId::of::<R>())
+            .ok_or_else(|| ResourceError::NotFound(type_name::<R>()))?;
+
+        let value = entry
+            .try_borrow()
+            .map_err(|_| ResourceError::AlreadyBorrowed(type_name::<R>()))?;
+
+        Ok(Res::new(Ref::map(value, |v| v.downcast_ref().unwrap())))
+    }
+
+    pub fn get_mut<R: 'static>(&self) -> Result<ResMut<'_, R>, ResourceError> {
+        let entry = self.entries
+            .get(&TypeId::of::<R>())
+            .ok_or_else(|| ResourceError::NotFound(type_name::<R>()))?;
+
+        let value = entry
+            .try_borrow_mut()
+            .map_err(|_| ResourceError::AlreadyBorrowed(type_name::<R>()))?;
+
+        Ok(ResMut::new(RefMut::map(value, |v| v.downcast_mut().unwrap())))
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::resource::SystemResources;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Score(u32);
+
+    #[test]
+    fn system_resources_fetch() {
+        let mut resources = Resources::new();
+        assert!(resources.insert_resource(Score(1)).is_none());
+        assert_eq!(resources.insert_resource(Score(2)), Some(Score(1)));
+
+        {
+            let (mut score, level) = <(ResMut<Score>, Option<Res<u8>>)>::fetch(&resources).unwrap();
+            score.0 += 1;
+            assert!(level.is_none());
+
+            assert!(matches!(resources.get::<Score>(), Err(ResourceError::AlreadyBorrowed(_))));
+        }
+
+        assert_eq!(*resources.get::<Score>().unwrap(), Score(3));
+        assert!(matches!(Res::<u8>::fetch(&resources), Err(ResourceError::NotFound(_))));
+        assert_eq!(resources.remove_resource::<Score>(), Some(Score(3)));
+        assert!(!resources.contains::<Score>());
+    }
+}
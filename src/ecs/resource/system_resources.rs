@@ -0,0 +1,57 @@
+use super::{Res, ResMut, Resources, ResourceError};
+
+/// Resources a `System` requires to run.
+/// They're retrieved before every run.
+///
+/// [`System`]: crate::ecs::system::System
+pub trait SystemResources<'r>: Sized {
+    fn fetch(resources: &'r Resources) -> Result<Self, ResourceError>;
+}
+
+impl<'r> SystemResources<'r> for () {
+    fn fetch(_resources: &'r Resources) -> Result<Self, ResourceError> {
+        Ok(())
+    }
+}
+
+impl<'r, R: 'static> SystemResources<'r> for Res<'r, R> {
+    fn fetch(resources: &'r Resources) -> Result<Self, ResourceError> {
+        resources.get()
+    }
+}
+
+impl<'r, R: 'static> SystemResources<'r> for ResMut<'r, R> {
+    fn fetch(resources: &'r Resources) -> Result<Self, ResourceError> {
+        resources.get_mut()
+    }
+}
+
+/// Optional resource, it's `None` when not found.
+impl<'r, S: SystemResources<'r>> SystemResources<'r> for Option<S> {
+    fn fetch(resources: &'r Resources) -> Result<Self, ResourceError> {
+        match S::fetch(resources) {
+            Ok(s) => Ok(Some(s)),
+            Err(ResourceError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+macro_rules! impl_system_resources_tuple {
+    ($($res:ident),+) => {
+        impl<'r, $($res: SystemResources<'r>),+> SystemResources<'r> for ($($res,)+) {
+            fn fetch(resources: &'r Resources) -> Result<Self, ResourceError> {
+                Ok(($($res::fetch(resources)?,)+))
+            }
+        }
+    };
+}
+
+impl_system_resources_tuple!(A);
+impl_system_resources_tuple!(A, B);
+impl_system_resources_tuple!(A, B, C);
+impl_system_resources_tuple!(A, B, C, D);
+impl_system_resources_tuple!(A, B, C, D, E);
+impl_system_resources_tuple!(A, B, C, D, E, F);
+impl_system_resources_tuple!(A, B, C, D, E, F, G);
+impl_system_resources_tuple!(A, B, C, D, E, F, G, H);
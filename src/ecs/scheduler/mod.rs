@@ -0,0 +1,27 @@
+mod scheduler_error;
+pub use scheduler_error::SchedulerError;
+
+mod step_systems;
+
+mod system_config;
+pub use system_config::{RunCondition, SystemConfig, SystemLabel};
+
+mod system_scheduler;
+pub use system_scheduler::SystemScheduler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulerStep {
+    Input,
+
+    /// Runs zero or more times per frame, with a constant delta.
+    /// See [`FixedTime`].
+    ///
+    /// [`FixedTime`]: crate::time::FixedTime
+    FixedUpdate,
+
+    Update,
+    Render,
+
+    /// User defined step, it runs right after the step it's been added to.
+    Custom(&'static str),
+}
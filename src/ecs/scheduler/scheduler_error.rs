@@ -0,0 +1,14 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+use super::SchedulerStep;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum SchedulerError {
+    #[error("systems ordering at {0:?} has a cycle between: {1:?}")]
+    Cycle(SchedulerStep, Vec<&'static str>),
+
+    #[error("steps runs after each other in a cycle: {0:?}")]
+    StepCycle(Vec<SchedulerStep>),
+}
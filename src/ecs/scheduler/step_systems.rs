@@ -0,0 +1,280 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::ecs::{
+    entity::Entities,
+    resource::Resources,
+    system::SystemInterface,
+    FrameState,
+};
+
+use super::{
+    SchedulerError,
+    SchedulerStep,
+    SystemConfig,
+    SystemLabel,
+};
+
+struct ScheduledSystem {
+    interface: SystemInterface,
+    config: SystemConfig,
+}
+
+/// Systems registered at a single `SchedulerStep`, kept in run order.
+///
+/// [`SchedulerStep`]: super::SchedulerStep
+#[derive(Default)]
+pub(super) struct StepSystems {
+    systems: Vec<ScheduledSystem>,
+    order: Vec<usize>,
+    sets: HashMap<SystemLabel, SystemConfig>,
+}
+
+impl StepSystems {
+    pub fn register(
+        &mut self,
+        step: SchedulerStep,
+        interface: SystemInterface,
+        config: SystemConfig,
+    ) -> Result<(), SchedulerError> {
+        self.systems.push(ScheduledSystem { interface, config });
+
+        match self.sort(step) {
+            Ok(order) => {
+                self.order = order;
+                Ok(())
+            },
+            Err(e) => {
+                self.systems.pop();
+                Err(e)
+            },
+        }
+    }
+
+    pub fn configure_set(
+        &mut self,
+        step: SchedulerStep,
+        set: SystemLabel,
+        config: SystemConfig,
+    ) -> Result<(), SchedulerError> {
+        let previous = self.sets.insert(set, config);
+
+        match self.sort(step) {
+            Ok(order) => {
+                self.order = order;
+                Ok(())
+            },
+            Err(e) => {
+                match previous {
+                    Some(previous) => self.sets.insert(set, previous),
+                    None => self.sets.remove(set),
+                };
+
+                Err(e)
+            },
+        }
+    }
+
+    pub fn run(&mut self, entities: &Entities, resources: &Resources, state: &mut FrameState) {
+        // sets conditions are checked once per run
+        let mut skipped_sets = Vec::new();
+
+        for (set, config) in self.sets.iter_mut() {
+            if !config.should_run(state, resources) {
+                skipped_sets.push(*set);
+            }
+        }
+
+        for index in &self.order {
+            let sys = &mut self.systems[index.to_owned()];
+
+            if sys.config.sets.iter().any(|set| skipped_sets.contains(set))
+              || !sys.config.should_run(state, resources) {
+                continue;
+            }
+
+            // changes made after system runs should be newer than it's tick
+            let tick = entities.change_tick();
+            sys.interface.run(entities.archetypes(), resources, tick, state);
+            entities.increment_change_tick();
+        }
+    }
+
+    /// Topological sort, systems without constraints between them keeps registration order.
+    fn sort(&self, step: SchedulerStep) -> Result<Vec<usize>, SchedulerError> {
+        let count = self.systems.len();
+        let mut edges = vec![Vec::new(); count];
+        let mut in_degree = vec![0usize; count];
+        let mut predecessors = vec![Vec::new(); count];
+
+        for (index, sys) in self.systems.iter().enumerate() {
+            let set_configs = sys.config.sets
+                                  .iter()
+                                  .filter_map(|set| self.sets.get(set));
+
+            for config in set_configs.chain(std::iter::once(&sys.config)) {
+                for label in &config.after {
+                    for other in self.find(label) {
+                        if other != index {
+                            edges[other].push(index);
+                            predecessors[index].push(other);
+                            in_degree[index] += 1;
+                        }
+                    }
+                }
+
+                for label in &config.before {
+                    for other in self.find(label) {
+                        if other != index {
+                            edges[index].push(other);
+                            predecessors[other].push(index);
+                            in_degree[other] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(index, _)| Reverse(index))
+            .collect();
+
+        let mut order = Vec::with_capacity(count);
+
+        while let Some(Reverse(index)) = ready.pop() {
+            order.push(index);
+
+            for next in &edges[index] {
+                in_degree[*next] -= 1;
+
+                if in_degree[*next] == 0 {
+                    ready.push(Reverse(*next));
+                }
+            }
+        }
+
+        if order.len() < count {
+            let cycle = Self::find_cycle(&in_degree, &predecessors)
+                .into_iter()
+                .map(|index| {
+                    let sys = &self.systems[index];
+                    sys.config.label().unwrap_or(sys.interface.name())
+                })
+                .collect();
+
+            return Err(SchedulerError::Cycle(step, cycle));
+        }
+
+        Ok(order)
+    }
+
+    /// Systems forming a cycle, in run order, from those left unsorted.
+    fn find_cycle(in_degree: &[usize], predecessors: &[Vec<usize>]) -> Vec<usize> {
+        let unsorted = |index: &usize| in_degree[*index] > 0;
+
+        // every unsorted system has an unsorted predecessor, walking back them ends up in a cycle
+        let mut path = Vec::new();
+        let mut current = (0..in_degree.len()).find(unsorted);
+
+        while let Some(index) = current {
+            if let Some(start) = path.iter().position(|visited| *visited == index) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                return cycle;
+            }
+
+            path.push(index);
+            current = predecessors[index].iter().copied().find(unsorted);
+        }
+
+        path
+    }
+
+    fn find<'a>(&'a self, label: &'a SystemLabel) -> impl Iterator<Item = usize> + 'a {
+        self.systems
+            .iter()
+            .enumerate()
+            .filter(|(_, sys)| sys.config.matches(label))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ecs::{component::FnQuery, system::System},
+        base::ApplicationState,
+    };
+
+    use super::*;
+
+    struct NoopSystem;
+
+    impl System for NoopSystem {
+        type Query<'q> = FnQuery<'q>;
+        type Resources<'r> = ();
+
+        fn setup(&mut self) {
+        }
+
+        fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+        }
+
+        fn run<'q, 'r>(&mut self, _query: Self::Query<'q>, _resources: Self::Resources<'r>, _state: &mut FrameState) {
+        }
+
+        fn create_query<'q>(&self) -> Self::Query<'q> {
+            FnQuery::new(|_| false)
+        }
+    }
+
+    fn register(systems: &mut StepSystems, config: SystemConfig) -> Result<(), SchedulerError> {
+        systems.register(SchedulerStep::Update, SystemInterface::wrap(NoopSystem), config)
+    }
+
+    fn labels(systems: &StepSystems) -> Vec<SystemLabel> {
+        systems.order
+               .iter()
+               .map(|index| systems.systems[*index].config.label().unwrap())
+               .collect()
+    }
+
+    #[test]
+    fn ordering() {
+        let mut systems = StepSystems::default();
+
+        register(&mut systems, SystemConfig::labeled("render").after("gameplay")).unwrap();
+        register(&mut systems, SystemConfig::labeled("physics").in_set("gameplay")).unwrap();
+        register(&mut systems, SystemConfig::labeled("input").before("physics")).unwrap();
+        register(&mut systems, SystemConfig::labeled("movement").in_set("gameplay")).unwrap();
+        assert_eq!(labels(&systems), ["input", "physics", "movement", "render"]);
+
+        systems.configure_set(
+            SchedulerStep::Update,
+            "gameplay",
+            SystemConfig::new().before("input"),
+        ).unwrap_err();
+
+        systems.configure_set(
+            SchedulerStep::Update,
+            "gameplay",
+            SystemConfig::new().after("audio"),
+        ).unwrap();
+
+        register(&mut systems, SystemConfig::labeled("audio")).unwrap();
+        assert_eq!(labels(&systems), ["input", "audio", "physics", "movement", "render"]);
+
+        register(&mut systems, SystemConfig::labeled("ui").after("render")).unwrap();
+        let result = register(&mut systems, SystemConfig::labeled("late").after("render").before("input"));
+        assert!(matches!(
+            result,
+            Err(SchedulerError::Cycle(SchedulerStep::Update, cycle)) if cycle == ["late", "input", "physics", "render"]
+        ));
+        assert_eq!(systems.systems.len(), 6);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::ecs::{
+    resource::Resources,
+    FrameState,
+};
+
+/// Identifies a system or a set of systems.
+pub type SystemLabel = &'static str;
+
+/// Decides if a system, or a set, should run at current frame.
+pub type RunCondition = Box<dyn FnMut(&FrameState, &Resources) -> bool>;
+
+/// Describes how a system, or a set, is scheduled at it's step.
+#[derive(Default)]
+pub struct SystemConfig {
+    pub(super) label: Option<SystemLabel>,
+    pub(super) sets: Vec<SystemLabel>,
+    pub(super) after: Vec<SystemLabel>,
+    pub(super) before: Vec<SystemLabel>,
+    pub(super) conditions: Vec<RunCondition>,
+}
+
+impl SystemConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn labeled(label: SystemLabel) -> Self {
+        Self::default().with_label(label)
+    }
+
+    pub fn with_label(mut self, label: SystemLabel) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Joins a set, every set config will also apply to it.
+    pub fn in_set(mut self, set: SystemLabel) -> Self {
+        self.sets.push(set);
+        self
+    }
+
+    /// Runs after every system labeled, or in a set, with provided label.
+    pub fn after(mut self, label: SystemLabel) -> Self {
+        self.after.push(label);
+        self
+    }
+
+    /// Runs before every system labeled, or in a set, with provided label.
+    pub fn before(mut self, label: SystemLabel) -> Self {
+        self.before.push(label);
+        self
+    }
+
+    /// Only runs when every condition is met.
+    pub fn run_if<F>(mut self, condition: F) -> Self where
+        F: 'static + FnMut(&FrameState, &Resources) -> bool
+    {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    pub fn label(&self) -> Option<SystemLabel> {
+        self.label
+    }
+
+    pub(super) fn matches(&self, label: SystemLabel) -> bool {
+        self.label == Some(label) || self.sets.contains(&label)
+    }
+
+    pub(super) fn should_run(&mut self, state: &FrameState, resources: &Resources) -> bool {
+        self.conditions
+            .iter_mut()
+            .all(|condition| condition(state, resources))
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::ecs::{
+    entity::Entities,
+    resource::Resources,
+    system::{System, SystemInterface},
+    FrameState,
+};
+
+use super::{
+    step_systems::StepSystems,
+    SchedulerError,
+    SchedulerStep,
+    SystemConfig,
+    SystemLabel,
+};
+
+#[derive(Default)]
+pub struct SystemScheduler {
+    steps: HashMap<SchedulerStep, StepSystems>,
+    steps_after: HashMap<SchedulerStep, Vec<SchedulerStep>>,
+}
+
+impl SystemScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S: 'static + System>(&mut self, step: SchedulerStep, sys: S) {
+        // without constraints it can't form a cycle
+        self.register_with(step, sys, SystemConfig::default())
+            .unwrap()
+    }
+
+    /// Register a system using it's config to determine when it should run.
+    /// Fails when it's ordering constraints forms a cycle, system isn't registered in that case.
+    pub fn register_with<S: 'static + System>(
+        &mut self,
+        step: SchedulerStep,
+        sys: S,
+        config: SystemConfig,
+    ) -> Result<(), SchedulerError> {
+        self.steps
+            .entry(step)
+            .or_default()
+            .register(step, SystemInterface::wrap(sys), config)
+    }
+
+    /// Apply a config to every system in a set, at provided step.
+    pub fn configure_set(
+        &mut self,
+        step: SchedulerStep,
+        set: SystemLabel,
+        config: SystemConfig,
+    ) -> Result<(), SchedulerError> {
+        self.steps
+            .entry(step)
+            .or_default()
+            .configure_set(step, set, config)
+    }
+
+    /// Runs a step right after another one finishes, at every time it runs.
+    /// Fails when `after` already runs after `step`, since they would run each other forever.
+    pub fn add_step_after(&mut self, after: SchedulerStep, step: SchedulerStep) -> Result<(), SchedulerError> {
+        if let Some(mut path) = self.path_between(step, after) {
+            path.push(step);
+            return Err(SchedulerError::StepCycle(path));
+        }
+
+        self.steps_after
+            .entry(after)
+            .or_default()
+            .push(step);
+
+        Ok(())
+    }
+
+    /// Runs every system at step, followed by steps added after it.
+    /// Commands are applied when each step finishes.
+    pub fn run(
+        &mut self,
+        step: &SchedulerStep,
+        entities: &mut Entities,
+        resources: &Resources,
+        state: &mut FrameState,
+    ) {
+        if let Some(step_systems) = self.steps.get_mut(step) {
+            step_systems.run(entities, resources, state);
+        }
+
+        state.commands.apply(entities);
+
+        let count = self.steps_after
+                        .get(step)
+                        .map_or(0, Vec::len);
+
+        // steps can't be added while running, indexing avoids borrowing the list
+        for index in 0..count {
+            let next_step = self.steps_after[step][index];
+            self.run(&next_step, entities, resources, state);
+        }
+    }
+
+    /// Steps from `from` to `to` following steps added after, both included.
+    fn path_between(&self, from: SchedulerStep, to: SchedulerStep) -> Option<Vec<SchedulerStep>> {
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        // cycles are rejected on insertion, so it always ends
+        self.steps_after
+            .get(&from)?
+            .iter()
+            .find_map(|next| self.path_between(*next, to))
+            .map(|mut path| {
+                path.insert(0, from);
+                path
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_cycles() {
+        let mut scheduler = SystemScheduler::new();
+
+        scheduler.add_step_after(SchedulerStep::Update, SchedulerStep::Custom("late")).unwrap();
+        scheduler.add_step_after(SchedulerStep::Custom("late"), SchedulerStep::Render).unwrap();
+        scheduler.add_step_after(SchedulerStep::Update, SchedulerStep::Render).unwrap();
+
+        let result = scheduler.add_step_after(SchedulerStep::Update, SchedulerStep::Update);
+        assert!(matches!(result, Err(SchedulerError::StepCycle(path)) if path == [SchedulerStep::Update, SchedulerStep::Update]));
+
+        let result = scheduler.add_step_after(SchedulerStep::Render, SchedulerStep::Update);
+        let expected = [
+            SchedulerStep::Update,
+            SchedulerStep::Custom("late"),
+            SchedulerStep::Render,
+            SchedulerStep::Update,
+        ];
+        assert!(matches!(result, Err(SchedulerError::StepCycle(path)) if path == expected));
+    }
+}
@@ -1,15 +1,24 @@
 mod system_interface;
 pub(crate) use system_interface::SystemInterface;
 
-use crate::{ecs::component::BaseQuery, base::ApplicationState};
+use crate::{
+    ecs::{
+        component::BaseQuery,
+        resource::SystemResources,
+    },
+    base::ApplicationState,
+};
 
 use super::FrameState;
 
 pub trait System {
-    type Query<'q>: BaseQuery;
+    type Query<'q>: BaseQuery<'q>;
+
+    /// Resources retrieved before every run, use `()` when none is needed.
+    type Resources<'r>: SystemResources<'r>;
 
     fn setup(&mut self);
     fn input<'q>(&mut self, query: Self::Query<'q>, state: &mut ApplicationState);
-    fn run<'q>(&mut self, query: Self::Query<'q>, state: &mut FrameState);
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, resources: Self::Resources<'r>, state: &mut FrameState);
     fn create_query<'q>(&self) -> Self::Query<'q>;
 }
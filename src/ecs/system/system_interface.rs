@@ -1,36 +1,141 @@
-use std::any::Any;
+use std::any::{Any, type_name};
 
 use crate::ecs::{
-    component::BaseQuery,
-    entity::EntitiesIter,
+    archetype::Archetypes,
+    component::{BaseQuery, SystemTicks, Tick},
+    resource::{ResourceError, Resources, SystemResources},
     FrameState,
 };
 
 use super::System;
 
+/// Returns `Err` when system couldn't run.
+type RunFn = Box<dyn FnMut(&mut Box<dyn Any>, &Archetypes, &Resources, SystemTicks, &mut FrameState<'_>) -> Result<(), ResourceError>>;
+
 pub(crate) struct SystemInterface {
     system: Box<dyn Any>,
-    run_fn: Box<dyn FnMut(&mut Box<dyn Any>, EntitiesIter<'_>, &mut FrameState<'_>)>,
+    name: &'static str,
+    last_run: Tick,
+
+    /// Whether it was skipped at last run, so a missing resource is only reported once.
+    skipped: bool,
+
+    run_fn: RunFn,
 }
 
 impl SystemInterface {
-    pub fn wrap<'a, Q: BaseQuery + 'static, S: System<Query<'a> = Q> + 'static>(system: S) -> Self {
+    pub fn wrap<S: System + 'static>(system: S) -> Self {
         SystemInterface {
             system: Box::new(system),
-            run_fn: Box::new(|boxed_system, entities, state| {
+            name: type_name::<S>(),
+            last_run: Tick::default(),
+            skipped: false,
+            run_fn: Box::new(|boxed_system, archetypes, resources, ticks, state| {
                 let sys = boxed_system.downcast_mut::<S>().unwrap();
-                let mut query = sys.create_query();
+                let resources = S::Resources::fetch(resources)?;
+                // components are borrowed from archetypes while system runs
+                let mut query: S::Query<'_> = sys.create_query();
 
-                for entity in entities {
-                    query.capture_components(entity.borrow().components());
+                for archetype in archetypes.iter().filter(|a| !a.is_empty()) {
+                    query.capture_archetype(archetype, ticks);
                 }
 
-                sys.run(query, state);
+                sys.run(query, resources, state);
+
+                Ok(())
             }),
         }
     }
 
-    pub fn run(&mut self, entities: EntitiesIter<'_>, state: &mut FrameState) {
-        (*self.run_fn)(&mut self.system, entities, state)
+    /// System's type name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn run(
+        &mut self,
+        archetypes: &Archetypes,
+        resources: &Resources,
+        this_run: Tick,
+        state: &mut FrameState,
+    ) {
+        let ticks = SystemTicks {
+            last_run: self.last_run,
+            this_run,
+        };
+
+        // a skipped system keeps its last run, so it still sees changes since then
+        match (*self.run_fn)(&mut self.system, archetypes, resources, ticks, state) {
+            Ok(()) => {
+                self.last_run = this_run;
+                self.skipped = false;
+            },
+            Err(e) => {
+                if !self.skipped {
+                    eprintln!("System {} skipped: {}", self.name, e);
+                }
+
+                self.skipped = true;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        base::ApplicationState,
+        ecs::{command::Commands, component::FnQuery, entity::Entities, resource::Res},
+        time::DeltaTime,
+    };
+
+    use super::*;
+
+    struct Counter(u32);
+
+    struct CountingSystem;
+
+    impl System for CountingSystem {
+        type Query<'q> = FnQuery<'q>;
+        type Resources<'r> = Res<'r, Counter>;
+
+        fn setup(&mut self) {
+        }
+
+        fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+        }
+
+        fn run<'q, 'r>(&mut self, _query: Self::Query<'q>, resources: Self::Resources<'r>, _state: &mut FrameState) {
+            assert_eq!(resources.0, 0);
+        }
+
+        fn create_query<'q>(&self) -> Self::Query<'q> {
+            FnQuery::new(|_| false)
+        }
+    }
+
+    #[test]
+    fn skips_missing_resources() {
+        let entities = Entities::new();
+        let mut resources = Resources::new();
+        let mut commands = Commands::new(&entities);
+        let mut state = FrameState {
+            delta: DeltaTime::new(Duration::ZERO),
+            app: None,
+            commands: &mut commands,
+        };
+
+        let mut system = SystemInterface::wrap(CountingSystem);
+        let this_run = 1;
+        system.run(entities.archetypes(), &resources, this_run, &mut state);
+        assert!(system.skipped);
+        assert_eq!(system.last_run, 0);
+
+        resources.insert_resource(Counter(0));
+        system.run(entities.archetypes(), &resources, this_run, &mut state);
+        assert!(!system.skipped);
+        assert_eq!(system.last_run, this_run);
     }
 }
@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod base;
 pub mod components;
 pub mod diag;
@@ -12,3 +13,6 @@ pub mod time;
 pub mod ui;
 pub mod util;
 pub mod window;
+
+#[cfg(test)]
+mod test_util;
@@ -0,0 +1,162 @@
+use std::ops;
+
+use super::{Approximable, Vector2};
+
+/// Bi-dimensional affine transformation, as a 3x2 matrix stored by columns.
+///
+/// Multiplication composes right to left, so `parent * child` applies `child` first.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Affine2 {
+    pub x_axis: Vector2<f32>,
+    pub y_axis: Vector2<f32>,
+    pub translation: Vector2<f32>,
+}
+
+impl Affine2 {
+    pub const IDENTITY: Self = Self {
+        x_axis: Vector2::new(1.0, 0.0),
+        y_axis: Vector2::new(0.0, 1.0),
+        translation: Vector2::new(0.0, 0.0),
+    };
+
+    pub fn from_translation(translation: Vector2<f32>) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Rotation in radians.
+    pub fn from_rotation(rotation: f32) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+
+        Self {
+            x_axis: Vector2::new(cos, sin),
+            y_axis: Vector2::new(-sin, cos),
+            translation: Vector2::zero(),
+        }
+    }
+
+    pub fn from_scale(scale: Vector2<f32>) -> Self {
+        Self {
+            x_axis: Vector2::new(scale.x, 0.0),
+            y_axis: Vector2::new(0.0, scale.y),
+            translation: Vector2::zero(),
+        }
+    }
+
+    /// Scales and rotates around `pivot`, which is then placed at `position`.
+    pub fn from_transform(position: Vector2<f32>, rotation: f32, scale: Vector2<f32>, pivot: Vector2<f32>) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        let x_axis = Vector2::new(cos, sin) * scale.x;
+        let y_axis = Vector2::new(-sin, cos) * scale.y;
+
+        Self {
+            x_axis,
+            y_axis,
+            translation: position - (x_axis * pivot.x + y_axis * pivot.y),
+        }
+    }
+
+    pub fn transform_point(&self, point: Vector2<f32>) -> Vector2<f32> {
+        self.transform_vector(point) + self.translation
+    }
+
+    /// Ignores translation.
+    pub fn transform_vector(&self, vector: Vector2<f32>) -> Vector2<f32> {
+        self.x_axis * vector.x + self.y_axis * vector.y
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+
+    /// `None` when it collapses space, such as a zero scale.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+
+        if determinant.is_equal_approx(&0.0) {
+            return None;
+        }
+
+        let inverse_determinant = 1.0 / determinant;
+        let x_axis = Vector2::new(self.y_axis.y, -self.x_axis.y) * inverse_determinant;
+        let y_axis = Vector2::new(-self.y_axis.x, self.x_axis.x) * inverse_determinant;
+
+        Some(Self {
+            x_axis,
+            y_axis,
+            translation: -(x_axis * self.translation.x + y_axis * self.translation.y),
+        })
+    }
+
+    /// Rotation in radians, assuming it doesn't have skew.
+    pub fn rotation(&self) -> f32 {
+        self.x_axis.y.atan2(self.x_axis.x)
+    }
+
+    /// Scale, assuming it doesn't have skew.
+    /// A negative determinant is represented as a negative y scale.
+    pub fn scale(&self) -> Vector2<f32> {
+        let x = self.x_axis.x.hypot(self.x_axis.y);
+
+        let y = match x.is_equal_approx(&0.0) {
+            true => self.y_axis.x.hypot(self.y_axis.y),
+            false => self.determinant() / x,
+        };
+
+        Vector2::new(x, y)
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ops::Mul for Affine2 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self {
+            x_axis: self.transform_vector(other.x_axis),
+            y_axis: self.transform_vector(other.y_axis),
+            translation: self.transform_point(other.translation),
+        }
+    }
+}
+
+impl ops::MulAssign for Affine2 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::test_util::assert_near;
+
+    use super::*;
+
+    #[test]
+    fn compose_and_decompose() {
+        let parent = Affine2::from_transform(Vector2::new(10.0, 0.0), FRAC_PI_2, Vector2::new(2.0, 2.0), Vector2::zero());
+        let child = Affine2::from_translation(Vector2::new(1.0, 0.0));
+        let global = parent * child;
+
+        assert_near(global.translation, Vector2::new(10.0, 2.0));
+        assert_near(global.scale(), Vector2::new(2.0, 2.0));
+        assert!((global.rotation() - FRAC_PI_2).abs() < 1e-4);
+
+        let pivoted = Affine2::from_transform(Vector2::new(5.0, 5.0), FRAC_PI_2, Vector2::one(), Vector2::new(1.0, 1.0));
+        assert_near(pivoted.transform_point(Vector2::new(1.0, 1.0)), Vector2::new(5.0, 5.0));
+        assert_near(pivoted.transform_point(Vector2::new(2.0, 1.0)), Vector2::new(5.0, 6.0));
+
+        let inverse = global.inverse().unwrap();
+        assert_near((inverse * global).transform_point(Vector2::new(3.0, 4.0)), Vector2::new(3.0, 4.0));
+        assert!(Affine2::from_scale(Vector2::zero()).inverse().is_none());
+    }
+}
@@ -66,3 +66,43 @@ impl<T> Rectangle<T> where
         Vector2::new(self.left(), self.bottom())
     }
 }
+
+impl<T> Rectangle<T> where
+    T: Num + Clone + Copy + PartialOrd
+{
+    /// Whether other rectangle is completely inside this one.
+    pub fn contains(&self, other: &Self) -> bool {
+        other.left() >= self.left()
+            && other.top() >= self.top()
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Whether point is inside, right and bottom edges are excluded.
+    pub fn contains_point(&self, point: Position<T>) -> bool {
+        point.x >= self.left()
+            && point.y >= self.top()
+            && point.x < self.right()
+            && point.y < self.bottom()
+    }
+
+    /// Whether both rectangles overlap, touching edges doesn't count.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.left() < other.right()
+            && other.left() < self.right()
+            && self.top() < other.bottom()
+            && other.top() < self.bottom()
+    }
+
+    /// Area shared by both rectangles, `None` when it's empty.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let max = |a: T, b: T| if a > b { a } else { b };
+        let min = |a: T, b: T| if a < b { a } else { b };
+
+        let top_left = Vector2::new(max(self.left(), other.left()), max(self.top(), other.top()));
+        let bottom_right = Vector2::new(min(self.right(), other.right()), min(self.bottom(), other.bottom()));
+
+        (top_left.x < bottom_right.x && top_left.y < bottom_right.y)
+            .then(|| Self::points(top_left, bottom_right))
+    }
+}
@@ -1,6 +1,9 @@
-use std::fmt::{
-    self,
-    Display,
+use std::{
+    fmt::{
+        self,
+        Display,
+    },
+    ops,
 };
 
 use bytemuck::{Pod, Zeroable};
@@ -10,7 +13,7 @@ use crate::math::num_traits::{
     Num,
 };
 
-use super::geometry::Vector4;
+use super::{geometry::Vector4, Affine2};
 
 #[repr(C)]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
@@ -45,6 +48,43 @@ impl Matrix4x4<f32> {
     }
 }
 
+impl<T> ops::Mul for Matrix4x4<T> where
+    T: Num + Copy
+{
+    type Output = Self;
+
+    /// Applies `other` first, then `self`.
+    fn mul(self, other: Self) -> Self::Output {
+        let a: [T; 16] = self.into();
+        let b: [T; 16] = other.into();
+
+        let at = |row: usize, column: usize| {
+            (0..4).fold(T::zero(), |sum, i| sum + a[row * 4 + i] * b[i * 4 + column])
+        };
+
+        let row = |r: usize| Vector4::new(at(r, 0), at(r, 1), at(r, 2), at(r, 3));
+
+        Self {
+            row_0: row(0),
+            row_1: row(1),
+            row_2: row(2),
+            row_3: row(3),
+        }
+    }
+}
+
+impl From<Affine2> for Matrix4x4<f32> {
+    /// Keeps z untouched.
+    fn from(affine: Affine2) -> Self {
+        Self::with_rows(
+            Vector4::new(affine.x_axis.x, affine.y_axis.x, 0.0, affine.translation.x),
+            Vector4::new(affine.x_axis.y, affine.y_axis.y, 0.0, affine.translation.y),
+            Vector4::new(0.0,             0.0,             1.0, 0.0),
+            Vector4::new(0.0,             0.0,             0.0, 1.0),
+        )
+    }
+}
+
 impl<T: Num + Display> Display for Matrix4x4<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(
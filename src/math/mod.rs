@@ -1,6 +1,9 @@
 mod geometry;
 pub use geometry::*;
 
+mod affine2;
+pub use affine2::Affine2;
+
 mod matrix4x4;
 pub use matrix4x4::Matrix4x4;
 
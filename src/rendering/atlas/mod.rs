@@ -0,0 +1,14 @@
+mod rect_packer;
+pub use rect_packer::RectPacker;
+
+mod sprite_region;
+pub use sprite_region::SpriteRegion;
+
+mod texture_atlas;
+pub use texture_atlas::TextureAtlas;
+
+mod texture_atlas_builder;
+pub use texture_atlas_builder::TextureAtlasBuilder;
+
+mod texture_atlas_error;
+pub use texture_atlas_error::TextureAtlasError;
@@ -0,0 +1,137 @@
+use crate::math::{Rectangle, Size2, Vector2};
+
+/// Places rectangles into a fixed area, using MaxRects with best short side fit.
+///
+/// Every free area is tracked as a maximal rectangle, which may overlap with others,
+/// and each rectangle is placed where it leaves the smallest leftover side.
+pub struct RectPacker {
+    size: Size2<u32>,
+    free: Vec<Rectangle<u32>>,
+}
+
+impl RectPacker {
+    pub fn new(size: Size2<u32>) -> Self {
+        Self {
+            size,
+            free: vec![Rectangle::new(Vector2::zero(), size)],
+        }
+    }
+
+    pub fn size(&self) -> Size2<u32> {
+        self.size
+    }
+
+    /// Finds a place to rectangle with provided size, `None` when there is no room left.
+    pub fn insert(&mut self, size: Size2<u32>) -> Option<Vector2<u32>> {
+        let placed = self.free
+            .iter()
+            .filter(|free| free.size.width >= size.width && free.size.height >= size.height)
+            .min_by_key(|free| {
+                let leftover_width = free.size.width - size.width;
+                let leftover_height = free.size.height - size.height;
+
+                (leftover_width.min(leftover_height), leftover_width.max(leftover_height))
+            })
+            .map(|free| Rectangle::new(free.position, size))?;
+
+        self.split(&placed);
+        self.prune();
+
+        Some(placed.position)
+    }
+
+    /// Replaces every free rectangle overlapped by used one with what remains around it.
+    fn split(&mut self, used: &Rectangle<u32>) {
+        let mut free = Vec::with_capacity(self.free.len());
+
+        for rect in self.free.drain(..) {
+            if !rect.intersects(used) {
+                free.push(rect);
+                continue;
+            }
+
+            if used.left() > rect.left() {
+                free.push(Rectangle::new(
+                    rect.position,
+                    Size2::new(used.left() - rect.left(), rect.size.height),
+                ));
+            }
+
+            if used.right() < rect.right() {
+                free.push(Rectangle::new(
+                    Vector2::new(used.right(), rect.top()),
+                    Size2::new(rect.right() - used.right(), rect.size.height),
+                ));
+            }
+
+            if used.top() > rect.top() {
+                free.push(Rectangle::new(
+                    rect.position,
+                    Size2::new(rect.size.width, used.top() - rect.top()),
+                ));
+            }
+
+            if used.bottom() < rect.bottom() {
+                free.push(Rectangle::new(
+                    Vector2::new(rect.left(), used.bottom()),
+                    Size2::new(rect.size.width, rect.bottom() - used.bottom()),
+                ));
+            }
+        }
+
+        self.free = free;
+    }
+
+    /// Removes free rectangles contained by another one.
+    fn prune(&mut self) {
+        let free = std::mem::take(&mut self.free);
+
+        for (i, rect) in free.iter().enumerate() {
+            let redundant = free
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && other.contains(rect) && (other != rect || j < i));
+
+            if !redundant {
+                self.free.push(*rect);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_overlaps() {
+        let mut packer = RectPacker::new(Size2::new(64, 64));
+        let area = Rectangle::new(Vector2::zero(), packer.size());
+        let sizes = [(32, 16), (16, 32), (8, 8), (24, 8), (16, 16), (8, 24), (32, 8)];
+
+        let placed: Vec<_> = sizes
+            .iter()
+            .map(|&(w, h)| {
+                let size = Size2::new(w, h);
+                Rectangle::new(packer.insert(size).unwrap(), size)
+            })
+            .collect();
+
+        for (i, rect) in placed.iter().enumerate() {
+            assert!(area.contains(rect));
+            assert!(placed[i + 1..].iter().all(|other| !rect.intersects(other)));
+        }
+    }
+
+    #[test]
+    fn full() {
+        let mut packer = RectPacker::new(Size2::new(16, 16));
+
+        for _ in 0..4 {
+            assert!(packer.insert(Size2::new(8, 8)).is_some());
+        }
+
+        assert_eq!(packer.insert(Size2::new(1, 1)), None);
+        assert_eq!(RectPacker::new(Size2::new(16, 16)).insert(Size2::new(17, 1)), None);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    math::{Rectangle, Size2, Vector2},
+    rendering::Texture,
+    resources::{Asset, AssetWeak},
+};
+
+/// A rectangle, in pixels, inside a texture.
+#[derive(Clone)]
+pub struct SpriteRegion {
+    texture: AssetWeak<Texture>,
+    texture_size: Size2<u32>,
+    rect: Rectangle<u32>,
+}
+
+impl SpriteRegion {
+    pub fn new(texture: &Asset<Texture>, rect: Rectangle<u32>) -> Self {
+        Self {
+            texture: texture.weak(),
+            texture_size: texture.get().size(),
+            rect,
+        }
+    }
+
+    /// Region covering the whole texture.
+    pub fn full(texture: &Asset<Texture>) -> Self {
+        let texture_size = texture.get().size();
+
+        Self {
+            texture: texture.weak(),
+            texture_size,
+            rect: Rectangle::new(Vector2::zero(), texture_size),
+        }
+    }
+
+    pub fn texture(&self) -> &AssetWeak<Texture> {
+        &self.texture
+    }
+
+    pub fn rect(&self) -> Rectangle<u32> {
+        self.rect
+    }
+
+    pub fn size(&self) -> Size2<u32> {
+        self.rect.size
+    }
+
+    /// Same texture with another rect.
+    pub fn with_rect(mut self, rect: Rectangle<u32>) -> Self {
+        self.rect = rect;
+        self
+    }
+
+    /// Another region inside this one, rect is relative to this region.
+    pub fn sub_region(&self, rect: Rectangle<u32>) -> Self {
+        Self {
+            texture: self.texture.clone(),
+            texture_size: self.texture_size,
+            rect: Rectangle::new(self.rect.position + rect.position, rect.size),
+        }
+    }
+
+    /// Texture coordinates, normalized by texture size.
+    pub fn uv(&self) -> Rectangle<f32> {
+        let texture_size: Size2<f32> = self.texture_size.convert();
+        let position: Vector2<f32> = Vector2::new(self.rect.left() as f32, self.rect.top() as f32);
+        let size: Size2<f32> = self.rect.size.convert();
+
+        Rectangle::new(
+            Vector2::new(position.x / texture_size.width, position.y / texture_size.height),
+            size / texture_size,
+        )
+    }
+}
+
+impl PartialEq for SpriteRegion {
+    fn eq(&self, other: &Self) -> bool {
+        self.texture.ptr_eq(&other.texture) && self.rect == other.rect
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::{
+    math::Size2,
+    rendering::Texture,
+    resources::Asset,
+};
+
+use super::{SpriteRegion, TextureAtlasBuilder};
+
+/// Many images packed into a single texture, each one available as a named `SpriteRegion`.
+///
+/// Everything drawn from the same atlas shares a texture, so it can be batched together.
+pub struct TextureAtlas {
+    texture: Asset<Texture>,
+    regions: HashMap<String, SpriteRegion>,
+}
+
+impl TextureAtlas {
+    pub fn builder(max_size: Size2<u32>) -> TextureAtlasBuilder {
+        TextureAtlasBuilder::new(max_size)
+    }
+
+    pub(super) fn new(texture: Asset<Texture>, regions: HashMap<String, SpriteRegion>) -> Self {
+        Self {
+            texture,
+            regions,
+        }
+    }
+
+    pub fn texture(&self) -> &Asset<Texture> {
+        &self.texture
+    }
+
+    pub fn region<S: AsRef<str>>(&self, name: S) -> Option<&SpriteRegion> {
+        self.regions.get(name.as_ref())
+    }
+
+    pub fn regions(&self) -> impl Iterator<Item = (&str, &SpriteRegion)> {
+        self.regions
+            .iter()
+            .map(|(name, region)| (name.as_str(), region))
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
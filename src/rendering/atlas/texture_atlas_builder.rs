@@ -0,0 +1,157 @@
+use std::{collections::HashMap, path::Path};
+
+use image::RgbaImage;
+
+use crate::{
+    math::{Rectangle, Size2},
+    rendering::Texture,
+    resources::Asset,
+};
+
+use super::{
+    RectPacker,
+    SpriteRegion,
+    TextureAtlas,
+    TextureAtlasError,
+};
+
+/// Collects images to be packed into a `TextureAtlas`.
+pub struct TextureAtlasBuilder {
+    max_size: Size2<u32>,
+    padding: u32,
+    images: Vec<(String, RgbaImage)>,
+}
+
+impl TextureAtlasBuilder {
+    pub fn new(max_size: Size2<u32>) -> Self {
+        Self {
+            max_size,
+            padding: 0,
+            images: Vec::new(),
+        }
+    }
+
+    /// Empty pixels kept between regions, avoids bleeding when sampling with filtering.
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_image<N: Into<String>>(mut self, name: N, image: RgbaImage) -> Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    pub fn load<N, P>(self, name: N, path: P) -> Result<Self, TextureAtlasError> where
+        N: Into<String>,
+        P: AsRef<Path>,
+    {
+        let image = image::open(path.as_ref())
+            .map_err(|e| TextureAtlasError::Load(path.as_ref().to_owned(), e))?
+            .into_rgba8();
+
+        Ok(self.with_image(name, image))
+    }
+
+    /// Packs every image, largest first, into a texture no bigger than it's needed.
+    pub fn build(self) -> Result<TextureAtlas, TextureAtlasError> {
+        let mut packer = RectPacker::new(self.max_size);
+        let mut order: Vec<_> = (0..self.images.len()).collect();
+
+        order.sort_by_key(|&i| {
+            let (width, height) = self.images[i].1.dimensions();
+            std::cmp::Reverse((width.max(height), width * height))
+        });
+
+        let mut placed = HashMap::with_capacity(self.images.len());
+        let mut bounds = Size2::new(1, 1);
+
+        for i in order {
+            let (ref name, ref image) = self.images[i];
+            let size = Size2::new(image.width(), image.height());
+
+            let position = packer
+                .insert(size + self.padding)
+                .ok_or_else(|| TextureAtlasError::DoesNotFit {
+                    name: name.clone(),
+                    size,
+                    max_size: self.max_size,
+                })?;
+
+            let rect = Rectangle::new(position, size);
+
+            bounds.width = bounds.width.max(rect.right());
+            bounds.height = bounds.height.max(rect.bottom());
+
+            if placed.insert(name.clone(), (rect, i)).is_some() {
+                return Err(TextureAtlasError::DuplicateRegion(name.clone()));
+            }
+        }
+
+        let mut canvas = RgbaImage::new(bounds.width, bounds.height);
+
+        for (rect, i) in placed.values() {
+            image::imageops::replace(&mut canvas, &self.images[*i].1, rect.left() as i64, rect.top() as i64);
+        }
+
+        let texture = Asset::new(Texture::from_image(canvas));
+
+        let regions = placed
+            .into_iter()
+            .map(|(name, (rect, _))| (name, SpriteRegion::new(&texture, rect)))
+            .collect();
+
+        Ok(TextureAtlas::new(texture, regions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use crate::math::Vector2;
+
+    use super::*;
+
+    #[test]
+    fn packed_regions() {
+        let atlas = TextureAtlas::builder(Size2::new(64, 64))
+            .with_image("wide", RgbaImage::from_pixel(32, 8, Rgba([255, 0, 0, 255])))
+            .with_image("tall", RgbaImage::from_pixel(8, 16, Rgba([0, 255, 0, 255])))
+            .with_image("dot", RgbaImage::from_pixel(1, 1, Rgba([0, 0, 255, 255])))
+            .with_padding(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(atlas.len(), 3);
+
+        let wide = atlas.region("wide").unwrap();
+        let tall = atlas.region("tall").unwrap();
+
+        assert_eq!(wide.size(), Size2::new(32, 8));
+        assert_eq!(wide.rect().top_left(), Vector2::zero());
+        assert!(!wide.rect().intersects(&tall.rect()));
+
+        let texture_size = atlas.texture().get().size();
+        let uv = tall.uv();
+
+        assert_eq!(uv.size.width, 8.0 / texture_size.width as f32);
+        assert_eq!(uv.left(), tall.rect().left() as f32 / texture_size.width as f32);
+    }
+
+    #[test]
+    fn errors() {
+        let too_big = TextureAtlas::builder(Size2::new(16, 16))
+            .with_image("big", RgbaImage::new(17, 4))
+            .build();
+
+        assert!(matches!(too_big, Err(TextureAtlasError::DoesNotFit { .. })));
+
+        let duplicated = TextureAtlas::builder(Size2::new(16, 16))
+            .with_image("a", RgbaImage::new(4, 4))
+            .with_image("a", RgbaImage::new(4, 4))
+            .build();
+
+        assert!(matches!(duplicated, Err(TextureAtlasError::DuplicateRegion(_))));
+    }
+}
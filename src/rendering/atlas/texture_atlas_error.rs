@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+use miette::Diagnostic;
+
+use crate::math::Size2;
+
+#[derive(Error, Diagnostic, Debug)]
+#[non_exhaustive]
+pub enum TextureAtlasError {
+    #[error("failed to load image '{0}': {1}")]
+    Load(PathBuf, image::ImageError),
+
+    #[error("region '{0}' is registered more than once")]
+    DuplicateRegion(String),
+
+    #[error("region '{name}' ({size}) doesn't fit into atlas with max size {max_size}")]
+    DoesNotFit {
+        name: String,
+        size: Size2<u32>,
+        max_size: Size2<u32>,
+    },
+}
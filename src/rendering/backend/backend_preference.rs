@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Which graphics API should be used, when available.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendPreference {
+    /// Any of Vulkan, Metal, DX12 or WebGPU.
+    #[default]
+    Primary,
+
+    Vulkan,
+    Metal,
+    Dx12,
+    Gl,
+}
+
+impl From<BackendPreference> for wgpu::Backends {
+    fn from(preference: BackendPreference) -> Self {
+        match preference {
+            BackendPreference::Primary => wgpu::Backends::PRIMARY,
+            BackendPreference::Vulkan => wgpu::Backends::VULKAN,
+            BackendPreference::Metal => wgpu::Backends::METAL,
+            BackendPreference::Dx12 => wgpu::Backends::DX12,
+            BackendPreference::Gl => wgpu::Backends::GL,
+        }
+    }
+}
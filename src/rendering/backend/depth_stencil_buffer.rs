@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use crate::math::Size2;
+
+/// Depth and stencil values of a draw target, which must have the same size and sample count as it.
+#[derive(Clone)]
+pub struct DepthStencilBuffer {
+    size: Size2<u32>,
+    sample_count: u32,
+    texture: Rc<wgpu::Texture>,
+}
+
+impl DepthStencilBuffer {
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+
+    /// Depth is cleared to it at every frame, it's the farthest value.
+    pub const CLEAR_DEPTH: f32 = 1.0;
+
+    pub const CLEAR_STENCIL: u32 = 0;
+
+    pub(in crate::rendering) fn new(device: &wgpu::Device, size: Size2<u32>, sample_count: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("depth stencil buffer"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        Self {
+            size,
+            sample_count,
+            texture: Rc::new(texture),
+        }
+    }
+
+    pub fn size(&self) -> Size2<u32> {
+        self.size
+    }
+
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    pub(in crate::rendering) fn create_view(&self) -> wgpu::TextureView {
+        self.texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+}
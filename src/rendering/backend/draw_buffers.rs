@@ -0,0 +1,22 @@
+use super::DynamicBuffer;
+
+/// Vertex and index buffers shared by every draw, reused across frames.
+pub struct DrawBuffers {
+    pub vertex: DynamicBuffer,
+    pub index: DynamicBuffer,
+}
+
+impl DrawBuffers {
+    pub(super) fn new() -> Self {
+        Self {
+            vertex: DynamicBuffer::new("vertex buffer", wgpu::BufferUsages::VERTEX),
+            index: DynamicBuffer::new("index buffer", wgpu::BufferUsages::INDEX),
+        }
+    }
+
+    /// Starts a new frame, previous allocations may be overwritten.
+    pub(in crate::rendering) fn reset(&mut self) {
+        self.vertex.reset();
+        self.index.reset();
+    }
+}
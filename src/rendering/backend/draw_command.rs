@@ -1,22 +1,131 @@
 use std::ops::Deref;
 
-use crate::rendering::{
-    shaders::{
-        builder::ShaderBuilder,
-        Bindings,
-        ShaderInstance,
+use crate::{
+    math::{Rectangle, Size2},
+    rendering::{
+        shaders::{
+            builder::{ShaderBuilder, TargetLayout},
+            Bindings,
+            ShaderInstance,
+        },
+        Color, RenderTarget, ShaderConfig, Vertex,
     },
-    Color, ShaderConfig, Vertex, Vertex2D,
 };
 
-use super::{ DrawError, RenderPass, RenderPresentationSurface };
+use super::{
+    draw_list::DrawList,
+    DepthStencilBuffer,
+    DrawBuffers,
+    DrawError,
+    PassError,
+    RenderPass,
+    RenderPresentationSurface,
+};
 
+/// Records every draw of a frame, which are sent to GPU together at `DrawCommand::present`,
+/// using a single command encoder and render pass.
 pub struct DrawCommand<'a> {
     queue: &'a wgpu::Queue,
-    surface_texture: wgpu::SurfaceTexture,
-    surface_view: wgpu::TextureView,
+    target: DrawTarget,
     device: &'a wgpu::Device,
     shader_builder: &'a mut ShaderBuilder,
+    draw_buffers: &'a mut DrawBuffers,
+    draw_list: DrawList,
+    clear_color: Option<Color<f32>>,
+    error_scope: ErrorScope<'a>,
+}
+
+/// Validation error scope, pushed when error checks are enabled.
+/// It's popped at `DrawCommand::present`, or when dropped without presenting.
+struct ErrorScope<'a> {
+    device: Option<&'a wgpu::Device>,
+}
+
+impl<'a> ErrorScope<'a> {
+    fn push(device: &'a wgpu::Device, error_checks: bool) -> Self {
+        if !error_checks {
+            return Self { device: None };
+        }
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        Self {
+            device: Some(device),
+        }
+    }
+
+    fn pop(&mut self) -> Option<wgpu::Error> {
+        self.device
+            .take()
+            .and_then(|device| futures_lite::future::block_on(device.pop_error_scope()))
+    }
+}
+
+impl Drop for ErrorScope<'_> {
+    fn drop(&mut self) {
+        self.pop();
+    }
+}
+
+/// Where a `DrawCommand` draws into.
+enum DrawTarget {
+    Surface {
+        texture: wgpu::SurfaceTexture,
+        view: wgpu::TextureView,
+        attachments: TargetAttachments,
+    },
+    Texture {
+        view: wgpu::TextureView,
+        size: Size2<u32>,
+        attachments: TargetAttachments,
+    },
+}
+
+/// Optional buffers drawn into along with target.
+struct TargetAttachments {
+    /// Multisampled color, resolved into target.
+    msaa: Option<(wgpu::TextureView, u32)>,
+    depth_stencil: Option<wgpu::TextureView>,
+}
+
+impl DrawTarget {
+    fn view(&self) -> &wgpu::TextureView {
+        match self {
+            DrawTarget::Surface { view, .. } => view,
+            DrawTarget::Texture { view, .. } => view,
+        }
+    }
+
+    fn attachments(&self) -> &TargetAttachments {
+        match self {
+            DrawTarget::Surface { attachments, .. } => attachments,
+            DrawTarget::Texture { attachments, .. } => attachments,
+        }
+    }
+
+    fn layout(&self) -> TargetLayout {
+        let attachments = self.attachments();
+
+        TargetLayout {
+            depth_stencil_format: attachments.depth_stencil.as_ref().map(|_| DepthStencilBuffer::FORMAT),
+            sample_count: attachments.msaa.as_ref().map_or(1, |(_, sample_count)| *sample_count),
+        }
+    }
+
+    /// Color attachment's view and resolve target, which are swapped when there is MSAA.
+    fn color_views(&self) -> (&wgpu::TextureView, Option<&wgpu::TextureView>) {
+        match self.attachments().msaa {
+            Some((ref msaa, _)) => (msaa, Some(self.view())),
+            None => (self.view(), None),
+        }
+    }
+
+    fn size(&self) -> Size2<u32> {
+        match self {
+            DrawTarget::Surface { texture, .. } => Size2::new(texture.texture.width(), texture.texture.height()),
+            DrawTarget::Texture { size, .. } => *size,
+        }
+    }
 }
 
 impl<'a> DrawCommand<'a> {
@@ -25,20 +134,81 @@ impl<'a> DrawCommand<'a> {
         queue: &'a wgpu::Queue,
         presentation_surface: &'a mut RenderPresentationSurface,
         shader_builder: &'a mut ShaderBuilder,
+        draw_buffers: &'a mut DrawBuffers,
+        error_checks: bool,
     ) -> Result<Self, DrawError> {
-        device.push_error_scope(wgpu::ErrorFilter::Validation);
         let (surface_texture, surface_view)
             = presentation_surface
                 .acquire_surface()
                 .map_err(|e| DrawError::AcquirePresentationSurface(e))?;
 
-        Ok(Self {
+        Ok(Self::with_draw_target(
+            device,
             queue,
-            surface_texture,
-            surface_view,
+            DrawTarget::Surface {
+                texture: surface_texture,
+                view: surface_view,
+                attachments: TargetAttachments {
+                    msaa: presentation_surface
+                        .msaa_view()
+                        .map(|view| (view, presentation_surface.active_sample_count())),
+                    depth_stencil: presentation_surface.depth_stencil_view(),
+                },
+            },
+            shader_builder,
+            draw_buffers,
+            error_checks,
+        ))
+    }
+
+    pub(in crate::rendering) fn with_target(
+        device: &'a wgpu::Device,
+        queue: &'a wgpu::Queue,
+        render_target: &RenderTarget,
+        shader_builder: &'a mut ShaderBuilder,
+        draw_buffers: &'a mut DrawBuffers,
+        error_checks: bool,
+    ) -> Self {
+        Self::with_draw_target(
             device,
+            queue,
+            DrawTarget::Texture {
+                view: render_target.create_view(),
+                size: render_target.size(),
+                attachments: TargetAttachments {
+                    msaa: render_target
+                        .create_msaa_view()
+                        .map(|view| (view, render_target.sample_count())),
+                    depth_stencil: render_target.create_depth_stencil_view(),
+                },
+            },
             shader_builder,
-        })
+            draw_buffers,
+            error_checks,
+        )
+    }
+
+    fn with_draw_target(
+        device: &'a wgpu::Device,
+        queue: &'a wgpu::Queue,
+        target: DrawTarget,
+        shader_builder: &'a mut ShaderBuilder,
+        draw_buffers: &'a mut DrawBuffers,
+        error_checks: bool,
+    ) -> Self {
+        let error_scope = ErrorScope::push(device, error_checks);
+        draw_buffers.reset();
+
+        Self {
+            queue,
+            target,
+            device,
+            shader_builder,
+            draw_buffers,
+            draw_list: DrawList::default(),
+            clear_color: None,
+            error_scope,
+        }
     }
 
     pub(in crate::rendering) fn device_queue(
@@ -51,49 +221,248 @@ impl<'a> DrawCommand<'a> {
         self.shader_builder
     }
 
+    /// Size, in pixels, of what's being drawn into.
+    pub fn target_size(&self) -> Size2<u32> {
+        self.target.size()
+    }
+
+    /// Whether target has a depth stencil buffer, otherwise depth and stencil configuration is ignored.
+    pub fn has_depth_stencil(&self) -> bool {
+        self.target.attachments().depth_stencil.is_some()
+    }
+
+    /// MSAA sample count of target, 1 when it isn't multisampled.
+    pub fn sample_count(&self) -> u32 {
+        self.target.layout().sample_count
+    }
+
+    /// Restricts next draws to a rect, in pixels, of the target.
+    /// `None` draws into the whole target.
+    ///
+    /// It's clamped to target bounds, next draws are skipped when nothing is left.
+    pub fn set_viewport(&mut self, viewport: Option<Rectangle<f32>>) {
+        let Some(viewport) = viewport else {
+            self.draw_list.set_viewport(None);
+            return;
+        };
+
+        let bounds = Rectangle::new(Default::default(), self.target.size().convert());
+
+        match viewport.intersection(&bounds) {
+            Some(clamped) => self.draw_list.set_viewport(Some(clamped)),
+            None => self.draw_list.cull(),
+        }
+    }
+
     pub fn begin<'p, V, S, R>(
         &'p mut self,
         shader: &'p R,
         config: &ShaderConfig,
-        label: wgpu::Label
+        _label: wgpu::Label
     ) -> Result<RenderPass<'p, V>, DrawError> where
         V: Vertex,
         S: 'p + ShaderInstance + ?Sized,
         R: Deref<Target = S>,
     {
+        let identifier = shader.identifier();
+
         let shader_context = self.shader_builder
-            .get_mut_context(&shader.identifier())
-            .ok_or_else(|| DrawError::ShaderNotFound { identifier: shader.identifier() })?;
+            .get_mut_context(&identifier)
+            .ok_or_else(|| DrawError::ShaderNotFound { identifier })?;
+
+        // pipeline is created now, so it's ready when frame is presented
+        shader_context.pipeline(self.device, config, self.target.layout());
 
         let bindings = shader.bindings(Bindings::new(
-            &self.device,
+            self.device,
             shader_context.bindings_descriptor().clone()
         )).map_err(|e| DrawError::BindingsFillFailed(e))?;
 
         Ok(RenderPass::new(
-            self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label,
-            }),
-            &self.queue,
-            &self.surface_view,
-            &self.device,
+            self.device,
+            &mut self.draw_list,
             bindings,
-            shader_context.pipeline(self.device, config)
+            identifier,
+            *config,
+            shader_context,
         ))
     }
 
-    pub fn present(self) {
-        self.surface_texture.present();
+    /// Clears target before anything is drawn.
+    /// Every draw recorded before is discarded, since it would be cleared as well.
+    ///
+    /// Depth stencil buffer, when there is one, is always cleared at the beginning of a frame.
+    pub fn clear<C: Into<Color<f32>>>(&mut self, color: C) {
+        self.clear_color = Some(color.into());
+        self.draw_list.clear();
     }
 
-    pub fn clear<'p, C, S, R>(&'p mut self, color: C, shader: &'p R) -> Result<(), DrawError> where
-        C: Into<Color<f32>>,
-        S: ShaderInstance,
-        R: Deref<Target = S>,
-    {
-        match self.begin::<Vertex2D, S, R>(shader, &ShaderConfig::default(), None) {
-            Ok(pass) => pass.clear_color(color).submit().map_err(DrawError::from),
-            Err(e) => Err(e),
+    /// Sends every recorded draw to GPU with a single submission and presents it.
+    /// When drawing into a `RenderTarget` there is nothing to present.
+    pub fn present(mut self) -> Result<(), DrawError> {
+        let submitted = self.submit();
+
+        // popped even when submit fails, so scopes don't pile up
+        if let Some(e) = self.error_scope.pop() {
+            return Err(DrawError::from(PassError::from(e)));
+        }
+
+        submitted?;
+
+        if let DrawTarget::Surface { texture, .. } = self.target {
+            texture.present();
+        }
+
+        Ok(())
+    }
+
+    fn submit(&mut self) -> Result<(), DrawError> {
+        if self.draw_list.is_empty() && self.clear_color.is_none() {
+            return Ok(());
         }
+
+        let (vertex_range, index_range) = self.draw_list.upload(
+            self.device,
+            self.queue,
+            self.draw_buffers,
+        );
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Frame Encoder"),
+        });
+
+        {
+            let load = match self.clear_color {
+                Some(clear_color) => wgpu::LoadOp::Clear(clear_color.into()),
+                None => wgpu::LoadOp::Load,
+            };
+
+            let (view, resolve_target) = self.target.color_views();
+
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Frame Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load,
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: self.target.attachments().depth_stencil.as_ref().map(|view| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(DepthStencilBuffer::CLEAR_DEPTH),
+                            store: true,
+                        }),
+                        stencil_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(DepthStencilBuffer::CLEAR_STENCIL),
+                            store: true,
+                        }),
+                    }
+                }),
+            });
+
+            let target_layout = self.target.layout();
+
+            let buffers = self.draw_buffers.vertex.buffer().zip(self.draw_buffers.index.buffer());
+
+            if let Some((vertex_buffer, index_buffer)) = buffers {
+                pass.set_index_buffer(index_buffer.slice(index_range), wgpu::IndexFormat::Uint32);
+
+                let mut current_pipeline = None;
+                let mut current_viewport = None;
+                let mut current_stencil_reference = None;
+
+                for draw in self.draw_list.draws() {
+                    let pipeline_key = (draw.shader, draw.config.pipeline_key());
+
+                    if current_pipeline != Some(pipeline_key) {
+                        // NOTE  every pipeline was created at `DrawCommand::begin`
+                        let pipeline = self.shader_builder
+                                           .get_context(&draw.shader)
+                                           .and_then(|c| c.cached_pipeline(&draw.config, target_layout));
+
+                        match pipeline {
+                            Some(pipeline) => pass.set_pipeline(pipeline),
+                            None => return Err(DrawError::PipelineNotFound { identifier: draw.shader }),
+                        }
+
+                        current_pipeline = Some(pipeline_key);
+                    }
+
+                    // depth range is collapsed into draw's depth, so it's what fragments are tested with
+                    if current_viewport != Some((draw.viewport, draw.depth)) {
+                        let target_size: Size2<f32> = self.target.size().convert();
+                        let viewport = draw.viewport.unwrap_or_else(|| Rectangle::new(Default::default(), target_size));
+
+                        pass.set_viewport(viewport.left(), viewport.top(), viewport.size.width, viewport.size.height, draw.depth, draw.depth);
+                        current_viewport = Some((draw.viewport, draw.depth));
+                    }
+
+                    let stencil_reference = draw.config.depth_stencil().stencil_reference;
+
+                    if target_layout.depth_stencil_format.is_some() && current_stencil_reference != Some(stencil_reference) {
+                        pass.set_stencil_reference(stencil_reference);
+                        current_stencil_reference = Some(stencil_reference);
+                    }
+
+                    let vertices = (vertex_range.start + draw.vertices.start)..(vertex_range.start + draw.vertices.end);
+
+                    pass.set_bind_group(0, &draw.bind_group, &[]);
+                    pass.set_vertex_buffer(0, vertex_buffer.slice(vertices));
+                    pass.draw_indexed(draw.indices.clone(), 0, 0..1);
+                }
+            }
+        }
+
+        self.queue.submit(Some(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::Vector2,
+        rendering::{batchers::DrawBatcher, Color},
+        test_util::{colored, fill, TestScene},
+    };
+
+    use super::*;
+
+    /// Viewports past target bounds are clamped, empty ones skip their draws.
+    #[test]
+    fn clamped_viewports() {
+        let scene = TestScene::new();
+
+        let viewports = [
+            Rectangle::new(Vector2::new(8.0, 8.0), Size2::new(16.0, 16.0)),
+            Rectangle::new(Vector2::new(2.0, 2.0), Size2::new(0.0, 4.0)),
+            Rectangle::new(Vector2::new(32.0, 0.0), Size2::new(4.0, 4.0)),
+        ];
+
+        let image = scene.render(&scene.render_target(), Color::<f32>::rgba(0.0, 0.0, 0.0, 1.0), |draw_command| {
+            for viewport in viewports {
+                draw_command.set_viewport(Some(viewport));
+
+                let mut draw_batcher = DrawBatcher::new(draw_command);
+
+                fill(
+                    &mut draw_batcher,
+                    Rectangle::new(Vector2::zero(), Size2::new(16.0, 16.0)),
+                    colored(Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0), scene.shader_config),
+                );
+
+                draw_batcher.flush().unwrap();
+            }
+        });
+
+        assert_eq!(image.get_pixel(12, 12)[0], 255);
+        assert_eq!(image.get_pixel(8, 8)[0], 255);
+        assert_eq!(image.get_pixel(3, 3)[0], 0);
+        assert_eq!(image.get_pixel(7, 12)[0], 0);
     }
 }
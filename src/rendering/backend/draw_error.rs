@@ -10,9 +10,15 @@ pub enum DrawError {
     #[error("shader (0:?) not found at builder")]
     ShaderNotFound { identifier: Shader },
 
+    #[error("pipeline of shader {identifier:?} wasn't created before drawing")]
+    PipelineNotFound { identifier: Shader },
+
     #[error("pass failed: {0}")]
     Pass(#[from] PassError),
 
+    #[error("there is no presentation surface to draw into")]
+    NoPresentationSurface,
+
     #[error("acquiring presentation surface failed: {0}")]
     AcquirePresentationSurface(PresentationSurfaceError),
 
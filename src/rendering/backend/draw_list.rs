@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+use bytemuck::Pod;
+
+use crate::{
+    math::Rectangle,
+    rendering::{shaders::Shader, ShaderConfig},
+};
+
+use super::DrawBuffers;
+
+/// Every draw recorded by a `DrawCommand` during a frame.
+///
+/// Geometry is kept at CPU side until the frame is presented,
+/// so it's uploaded once and drawn by a single render pass.
+#[derive(Default)]
+pub(super) struct DrawList {
+    draws: Vec<RecordedDraw>,
+    vertex_data: Vec<u8>,
+    index_data: Vec<u32>,
+    viewport: Option<Rectangle<f32>>,
+
+    /// Set while viewport has nothing to draw into.
+    culled: bool,
+
+    /// Vertex and index data lengths up to the last recorded draw,
+    /// what's after belongs to a pass which isn't recorded yet.
+    recorded: (usize, usize),
+}
+
+/// A draw call waiting to be recorded into frame's render pass.
+pub(super) struct RecordedDraw {
+    pub shader: Shader,
+    pub config: ShaderConfig,
+    pub bind_group: wgpu::BindGroup,
+
+    /// Byte range at frame's vertex data.
+    pub vertices: Range<wgpu::BufferAddress>,
+
+    /// Range at frame's index data, relative to it's own vertices.
+    pub indices: Range<u32>,
+
+    /// In pixels, whole target when `None`.
+    pub viewport: Option<Rectangle<f32>>,
+
+    /// Depth buffer value every fragment is tested with.
+    pub depth: f32,
+}
+
+impl DrawList {
+    pub fn draws(&self) -> &[RecordedDraw] {
+        &self.draws
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    /// Viewport used by draws pushed from now on.
+    pub fn set_viewport(&mut self, viewport: Option<Rectangle<f32>>) {
+        self.viewport = viewport;
+        self.culled = false;
+    }
+
+    /// Skips draws from now on, until a viewport is set.
+    pub fn cull(&mut self) {
+        self.culled = true;
+    }
+
+    pub fn is_culled(&self) -> bool {
+        self.culled
+    }
+
+    pub fn clear(&mut self) {
+        self.draws.clear();
+        self.vertex_data.clear();
+        self.index_data.clear();
+        self.recorded = (0, 0);
+    }
+
+    /// Starts a draw, dropping whatever a pass left without recording it.
+    pub fn begin(&mut self) {
+        // vertex buffer offsets should be aligned
+        let aligned = wgpu::util::align_to(self.recorded.0, wgpu::VERTEX_STRIDE_ALIGNMENT as usize);
+
+        self.vertex_data.resize(aligned, 0);
+        self.index_data.truncate(self.recorded.1);
+    }
+
+    pub fn extend_vertices<V: Pod>(&mut self, vertices: impl Iterator<Item = V>) {
+        for vertex in vertices {
+            self.vertex_data.extend_from_slice(bytemuck::bytes_of(&vertex));
+        }
+    }
+
+    /// Indices are relative to vertices extended since `DrawList::begin`.
+    pub fn extend_indices(&mut self, indices: impl Iterator<Item = u32>) {
+        self.index_data.extend(indices);
+    }
+
+    /// Records what was extended since `DrawList::begin` as a draw.
+    pub fn record(
+        &mut self,
+        shader: Shader,
+        config: ShaderConfig,
+        bind_group: wgpu::BindGroup,
+        depth: f32,
+    ) {
+        let vertex_start = wgpu::util::align_to(self.recorded.0, wgpu::VERTEX_STRIDE_ALIGNMENT as usize);
+        let index_start = self.recorded.1;
+
+        self.draws.push(RecordedDraw {
+            shader,
+            config,
+            bind_group,
+            vertices: (vertex_start as wgpu::BufferAddress)..(self.vertex_data.len() as wgpu::BufferAddress),
+            indices: (index_start as u32)..(self.index_data.len() as u32),
+            viewport: self.viewport,
+            depth,
+        });
+
+        self.recorded = (self.vertex_data.len(), self.index_data.len());
+    }
+
+    /// Uploads every draw geometry, returning where it was placed at vertex and index buffers.
+    pub fn upload(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        draw_buffers: &mut DrawBuffers,
+    ) -> (Range<wgpu::BufferAddress>, Range<wgpu::BufferAddress>) {
+        (
+            draw_buffers.vertex.push(device, queue, &self.vertex_data[..self.recorded.0]),
+            draw_buffers.index.push(device, queue, bytemuck::cast_slice(&self.index_data[..self.recorded.1])),
+        )
+    }
+}
@@ -0,0 +1,100 @@
+use std::ops::Range;
+
+/// Persistent GPU buffer, sub-allocated linearly every frame.
+///
+/// It's only recreated when a frame needs more room than it has,
+/// growing to the next power of two.
+pub struct DynamicBuffer {
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+    buffer: Option<wgpu::Buffer>,
+    capacity: wgpu::BufferAddress,
+    cursor: wgpu::BufferAddress,
+}
+
+impl DynamicBuffer {
+    const MIN_CAPACITY: wgpu::BufferAddress = 4096;
+
+    pub(super) fn new(label: &'static str, usage: wgpu::BufferUsages) -> Self {
+        Self {
+            label,
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            buffer: None,
+            capacity: 0,
+            cursor: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> wgpu::BufferAddress {
+        self.capacity
+    }
+
+    /// Bytes allocated at current frame.
+    pub fn used(&self) -> wgpu::BufferAddress {
+        self.cursor
+    }
+
+    /// Starts a new frame, previous allocations may be overwritten.
+    pub(in crate::rendering) fn reset(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Uploads data, returning it's byte range at `DynamicBuffer::buffer`.
+    pub(in crate::rendering) fn push(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        data: &[u8],
+    ) -> Range<wgpu::BufferAddress> {
+        let size = Self::align(data.len() as wgpu::BufferAddress);
+
+        if size == 0 {
+            return self.cursor..self.cursor;
+        }
+
+        if self.cursor + size > self.capacity {
+            self.grow(device, size);
+        }
+
+        let buffer = self.buffer.as_ref().unwrap();
+        let start = self.cursor;
+
+        if data.len() as wgpu::BufferAddress == size {
+            queue.write_buffer(buffer, start, data);
+        } else {
+            let mut padded = data.to_vec();
+            padded.resize(size as usize, 0);
+            queue.write_buffer(buffer, start, &padded);
+        }
+
+        self.cursor += size;
+
+        start..(start + data.len() as wgpu::BufferAddress)
+    }
+
+    pub(in crate::rendering) fn buffer(&self) -> Option<&wgpu::Buffer> {
+        self.buffer.as_ref()
+    }
+
+    /// Replaces buffer with a larger one, previous allocations keeps living at old buffer
+    /// until every command which uses them is done.
+    fn grow(&mut self, device: &wgpu::Device, required: wgpu::BufferAddress) {
+        self.capacity = required.max(self.capacity * 2)
+                                .max(Self::MIN_CAPACITY)
+                                .next_power_of_two();
+
+        self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(self.label),
+            size: self.capacity,
+            usage: self.usage,
+            mapped_at_creation: false,
+        }));
+
+        self.cursor = 0;
+    }
+
+    fn align(size: wgpu::BufferAddress) -> wgpu::BufferAddress {
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        size.div_ceil(align) * align
+    }
+}
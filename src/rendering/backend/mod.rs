@@ -10,9 +10,29 @@ pub use render_backend_builder::RenderBackendBuilder;
 mod render_presentation_surface;
 pub use render_presentation_surface::RenderPresentationSurface;
 
+mod backend_preference;
+pub use backend_preference::BackendPreference;
+
+mod present_mode;
+pub use present_mode::PresentMode;
+
 mod presentation_surface_error;
 pub use presentation_surface_error::PresentationSurfaceError;
 
+mod dynamic_buffer;
+pub use dynamic_buffer::DynamicBuffer;
+
+mod depth_stencil_buffer;
+pub use depth_stencil_buffer::DepthStencilBuffer;
+
+mod msaa_buffer;
+pub use msaa_buffer::MsaaBuffer;
+
+mod draw_buffers;
+pub use draw_buffers::DrawBuffers;
+
+mod draw_list;
+
 mod draw_command;
 pub use draw_command::*;
 
@@ -0,0 +1,147 @@
+use std::rc::Rc;
+
+use crate::math::Size2;
+
+use super::DepthStencilBuffer;
+
+/// Multisampled color attachment, which is resolved into it's draw target at every frame.
+#[derive(Clone)]
+pub struct MsaaBuffer {
+    sample_count: u32,
+    texture: Rc<wgpu::Texture>,
+}
+
+impl MsaaBuffer {
+    pub(in crate::rendering) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: Size2<u32>,
+        sample_count: u32,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("msaa buffer"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        Self {
+            sample_count,
+            texture: Rc::new(texture),
+        }
+    }
+
+    /// Greatest sample count, up to `requested`, which adapter supports for both target format and
+    /// depth stencil buffer. It's 1, so no multisampling, when there is none.
+    pub(in crate::rendering) fn resolve_sample_count(
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        requested: u32,
+    ) -> u32 {
+        // device only accepts guaranteed features, unless adapter specific ones were enabled
+        let features = |format: wgpu::TextureFormat| {
+            match device.features().contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+                true => adapter.get_texture_format_features(format),
+                false => format.describe().guaranteed_format_features,
+            }
+        };
+
+        let color = features(format);
+        let depth_stencil = features(DepthStencilBuffer::FORMAT);
+
+        let supported = |count: &u32| {
+            color.flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+                && color.flags.sample_count_supported(*count)
+                && depth_stencil.flags.sample_count_supported(*count)
+        };
+
+        [8, 4, 2]
+            .into_iter()
+            .filter(|count| *count <= requested)
+            .find(supported)
+            .unwrap_or(1)
+    }
+
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    pub(in crate::rendering) fn create_view(&self) -> wgpu::TextureView {
+        self.texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::{Affine2, Rectangle, Vector2},
+        rendering::{batchers::DrawBatcher, Color, GraphicAdapter, TransformedRenderState},
+        test_util::{check_golden, colored, fill, TestScene, SCENE_SIZE},
+    };
+
+    use super::*;
+
+    #[test]
+    fn resolve_sample_count() {
+        let graphic_adapter = GraphicAdapter::software();
+        let graphic_adapter = graphic_adapter.borrow();
+        let backend = graphic_adapter.backend();
+
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let resolve = |requested| MsaaBuffer::resolve_sample_count(backend.adapter(), &backend.device, format, requested);
+
+        assert_eq!(resolve(0), 1);
+        assert_eq!(resolve(1), 1);
+        assert_eq!(resolve(3), resolve(2));
+
+        let color = backend.adapter().get_texture_format_features(format).flags;
+
+        // never above what's requested or what adapter supports, and every fallback is supported
+        for requested in [2, 4, 8, 16] {
+            let sample_count = resolve(requested);
+
+            assert!(sample_count <= requested);
+            assert!(sample_count == 1 || color.sample_count_supported(sample_count));
+            assert_eq!(resolve(sample_count), sample_count);
+        }
+    }
+
+    /// Rotated rectangle drawn with 4 samples, so it's edges are smoothed.
+    #[test]
+    fn msaa_golden() {
+        let scene = TestScene::new();
+        let render_target = scene.graphic_adapter.borrow().create_render_target_with(Size2::new(SCENE_SIZE, SCENE_SIZE), 4, false);
+        assert_eq!(render_target.sample_count(), 4);
+
+        let rectangle = Rectangle::new(Vector2::new(-4.0, -4.0), Size2::new(8.0, 8.0));
+        let transform = Affine2::from_transform(Vector2::new(8.0, 8.0), 0.5, Vector2::one(), Vector2::zero());
+
+        let image = scene.render(&render_target, 0x000000FFu32, |draw_command| {
+            assert_eq!(draw_command.sample_count(), 4);
+
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+
+            fill(
+                &mut TransformedRenderState::new(&mut draw_batcher, transform),
+                rectangle,
+                colored(Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0), scene.shader_config),
+            );
+
+            draw_batcher.flush().unwrap();
+        });
+
+        // partially covered pixels, which are neither clear color nor red
+        assert!(image.pixels().any(|pixel| pixel[0] > 0 && pixel[0] < 255));
+
+        check_golden(&image, "msaa");
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// How frames are presented to the surface.
+///
+/// When a mode isn't supported by the surface, it falls back to the closest
+/// one available, `Vsync` is always supported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresentMode {
+    /// Waits for vertical blank, without tearing.
+    #[default]
+    Vsync,
+
+    /// Replaces queued frame with newer one, without tearing.
+    Mailbox,
+
+    /// Presents as soon as possible, tearing may happen.
+    Immediate,
+}
+
+impl PresentMode {
+    /// Most suitable mode, from the ones supported.
+    pub fn resolve(&self, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+        let preferences: &[wgpu::PresentMode] = match self {
+            PresentMode::Vsync => &[wgpu::PresentMode::Fifo],
+            PresentMode::Mailbox => &[wgpu::PresentMode::Mailbox, wgpu::PresentMode::Fifo],
+            PresentMode::Immediate => &[
+                wgpu::PresentMode::Immediate,
+                wgpu::PresentMode::Mailbox,
+                wgpu::PresentMode::Fifo,
+            ],
+        };
+
+        preferences
+            .iter()
+            .find(|mode| supported.contains(mode))
+            .copied()
+            .unwrap_or(wgpu::PresentMode::Fifo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback() {
+        let supported = [wgpu::PresentMode::Fifo, wgpu::PresentMode::Mailbox];
+
+        assert_eq!(PresentMode::Vsync.resolve(&supported), wgpu::PresentMode::Fifo);
+        assert_eq!(PresentMode::Mailbox.resolve(&supported), wgpu::PresentMode::Mailbox);
+        assert_eq!(PresentMode::Immediate.resolve(&supported), wgpu::PresentMode::Mailbox);
+        assert_eq!(PresentMode::Immediate.resolve(&[]), wgpu::PresentMode::Fifo);
+    }
+}
@@ -1,31 +1,64 @@
 use std::rc::Rc;
 
 use crate::rendering::shaders::builder::ShaderBuilder;
-use super::RenderPresentationSurface;
+use super::{DrawBuffers, RenderPresentationSurface};
 
 pub struct RenderBackend {
+    adapter: Rc<wgpu::Adapter>,
     pub device: Rc<wgpu::Device>,
     pub queue: wgpu::Queue,
-    pub presentation_surface: RenderPresentationSurface,
+
+    /// `None` when running without a window.
+    pub presentation_surface: Option<RenderPresentationSurface>,
     pub shader_builder: ShaderBuilder,
+
+    pub draw_buffers: DrawBuffers,
+
+    target_format: wgpu::TextureFormat,
+    error_checks: bool,
 }
 
 impl RenderBackend {
     pub(super) fn new(
+        adapter: Rc<wgpu::Adapter>,
         device: Rc<wgpu::Device>,
         queue: wgpu::Queue,
-        presentation_surface: RenderPresentationSurface,
+        presentation_surface: Option<RenderPresentationSurface>,
+        target_format: wgpu::TextureFormat,
     ) -> Self {
         let shader_builder = ShaderBuilder::new(
             Rc::downgrade(&device),
-            *presentation_surface.surface_format(),
+            target_format,
         );
 
         Self {
+            adapter,
             device,
             queue,
             presentation_surface,
             shader_builder,
+            draw_buffers: DrawBuffers::new(),
+            target_format,
+            error_checks: cfg!(debug_assertions),
         }
     }
+
+    pub(in crate::rendering) fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    /// Format every shader pipeline draws into.
+    pub fn target_format(&self) -> wgpu::TextureFormat {
+        self.target_format
+    }
+
+    /// Whether validation errors are checked after every frame.
+    /// It blocks until GPU reports back, so it's only enabled by default at debug builds.
+    pub fn error_checks(&self) -> bool {
+        self.error_checks
+    }
+
+    pub fn set_error_checks(&mut self, error_checks: bool) {
+        self.error_checks = error_checks;
+    }
 }
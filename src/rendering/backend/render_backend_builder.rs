@@ -3,6 +3,8 @@ use std::rc::Rc;
 use crate::window::Window;
 
 use super::{
+    BackendPreference,
+    PresentMode,
     RenderBackendBuildError,
     RenderBackend,
     RenderPresentationSurface,
@@ -11,24 +13,101 @@ use super::{
 pub type Result<T> = std::result::Result<T, RenderBackendBuildError>;
 
 pub struct RenderBackendBuilder<'a> {
-    window: &'a Window,
+    window: Option<&'a Window>,
     surface_size: (u32, u32),
+    force_fallback_adapter: bool,
+    present_mode: PresentMode,
+    backend: BackendPreference,
+    app_name: String,
+    error_checks: bool,
+    depth_stencil: bool,
+    sample_count: u32,
 }
 
 impl<'a> RenderBackendBuilder<'a> {
     pub(crate) fn new(window: &'a Window, surface_size: (u32, u32)) -> Self {
         Self {
-            window,
+            window: Some(window),
             surface_size,
+            force_fallback_adapter: false,
+            present_mode: PresentMode::default(),
+            backend: BackendPreference::default(),
+            app_name: env!("CARGO_PKG_NAME").to_owned(),
+            error_checks: cfg!(debug_assertions),
+            depth_stencil: false,
+            sample_count: 1,
         }
     }
 
+    /// Without a window, drawing is only possible into a `RenderTarget`.
+    ///
+    /// [`RenderTarget`]: crate::rendering::RenderTarget
+    pub fn headless() -> Self {
+        Self {
+            window: None,
+            surface_size: (0, 0),
+            force_fallback_adapter: false,
+            present_mode: PresentMode::default(),
+            backend: BackendPreference::default(),
+            app_name: env!("CARGO_PKG_NAME").to_owned(),
+            error_checks: cfg!(debug_assertions),
+            depth_stencil: false,
+            sample_count: 1,
+        }
+    }
+
+    /// Requests a software adapter, useful to get reproducible results.
+    /// Every backend is searched for it, since some software rasterizers,
+    /// such as llvmpipe, are only exposed through GL as a CPU adapter.
+    pub fn with_fallback_adapter(mut self, force_fallback_adapter: bool) -> Self {
+        self.force_fallback_adapter = force_fallback_adapter;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: BackendPreference) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_app_name<N: Into<String>>(mut self, app_name: N) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    /// Checks validation errors after every frame, enabled by default only at debug builds.
+    pub fn with_error_checks(mut self, error_checks: bool) -> Self {
+        self.error_checks = error_checks;
+        self
+    }
+
+    /// Creates a depth stencil buffer for presentation surface, recreated when it's resized.
+    pub fn with_depth_stencil(mut self, depth_stencil: bool) -> Self {
+        self.depth_stencil = depth_stencil;
+        self
+    }
+
+    /// MSAA samples per pixel of presentation surface, 1 disables it.
+    /// It falls back to the greatest count surface supports.
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+
     pub fn build(self) -> Result<RenderBackend> {
-        let app_name = "app name";
+        let instance = Self::create_instance(&self.app_name, self.backend, self.force_fallback_adapter);
+
+        let window = match self.window {
+            Some(window) => window,
+            None => return self.build_headless(&instance),
+        };
 
-        let instance = Self::create_instance(app_name);
-        let surface = Self::create_surface(&instance, self.window)?;
-        let adapter = pollster::block_on(Self::find_adapter(&instance, &surface))?;
+        let surface = Self::create_surface(&instance, window)?;
+        let adapter = pollster::block_on(Self::find_adapter(&instance, Some(&surface), self.force_fallback_adapter))?;
 
         let surface_caps = surface.get_capabilities(&adapter);
         println!("Surface caps: {:#?}", surface_caps);
@@ -40,33 +119,67 @@ impl<'a> RenderBackendBuilder<'a> {
             format: surface_caps.formats[0],
             width: self.surface_size.0,
             height: self.surface_size.1,
-            present_mode: wgpu::PresentMode::Fifo,
+            present_mode: self.present_mode.resolve(&surface_caps.present_modes),
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
         };
 
         surface.configure(&device, &surface_config);
 
+        let adapter = Rc::new(adapter);
         let device = Rc::new(device);
         let weak_device = Rc::downgrade(&device);
 
-        Ok(RenderBackend::new(
+        let mut presentation_surface = RenderPresentationSurface::new(
+            weak_device,
+            adapter.clone(),
+            surface,
+            self.present_mode,
+            self.surface_size.0,
+            self.surface_size.1,
+        );
+
+        presentation_surface.set_depth_stencil(self.depth_stencil);
+        presentation_surface.set_sample_count(self.sample_count);
+
+        let mut render_backend = RenderBackend::new(
+            adapter,
             device,
             queue,
-            RenderPresentationSurface::new(
-                weak_device,
-                adapter,
-                surface,
-                surface_config.format,
-                self.surface_size.0,
-                self.surface_size.1,
-            )
-        ))
+            Some(presentation_surface),
+            surface_config.format,
+        );
+
+        render_backend.set_error_checks(self.error_checks);
+
+        Ok(render_backend)
+    }
+
+    fn build_headless(self, instance: &wgpu::Instance) -> Result<RenderBackend> {
+        let adapter = pollster::block_on(Self::find_adapter(instance, None, self.force_fallback_adapter))?;
+        let (device, queue) = pollster::block_on(Self::open_logical_device(&adapter))?;
+
+        let mut render_backend = RenderBackend::new(
+            Rc::new(adapter),
+            Rc::new(device),
+            queue,
+            None,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+
+        render_backend.set_error_checks(self.error_checks);
+
+        Ok(render_backend)
     }
 
-    fn create_instance(_name: &str) -> wgpu::Instance {
+    fn create_instance(_name: &str, backend: BackendPreference, force_fallback_adapter: bool) -> wgpu::Instance {
+        let backends = match force_fallback_adapter {
+            true => wgpu::Backends::all(),
+            false => backend.into(),
+        };
+
         let instance_desc = wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::PRIMARY,
+            backends,
             dx12_shader_compiler: wgpu::Dx12Compiler::Fxc,
         };
 
@@ -78,22 +191,37 @@ impl<'a> RenderBackendBuilder<'a> {
             .map_err(RenderBackendBuildError::from)
     }
 
-    async fn find_adapter(instance: &wgpu::Instance, surface: &wgpu::Surface) -> Result<wgpu::Adapter> {
-        instance
+    async fn find_adapter(
+        instance: &wgpu::Instance,
+        surface: Option<&wgpu::Surface>,
+        force_fallback_adapter: bool,
+    ) -> Result<wgpu::Adapter> {
+        let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptionsBase {
                 power_preference: wgpu::PowerPreference::default(),
-                force_fallback_adapter: false,
-                compatible_surface: Some(surface),
+                force_fallback_adapter,
+                compatible_surface: surface,
             })
-            .await
-            .ok_or_else(|| RenderBackendBuildError::AdapterNotFound)
+            .await;
+
+        if adapter.is_some() || !force_fallback_adapter {
+            return adapter.ok_or(RenderBackendBuildError::AdapterNotFound);
+        }
+
+        // backends which doesn't report it as fallback, but it still runs at CPU
+        instance
+            .enumerate_adapters(wgpu::Backends::all())
+            .filter(|adapter| surface.is_none_or(|surface| adapter.is_surface_supported(surface)))
+            .find(|adapter| adapter.get_info().device_type == wgpu::DeviceType::Cpu)
+            .ok_or(RenderBackendBuildError::AdapterNotFound)
     }
 
     async fn open_logical_device(adapter: &wgpu::Adapter) -> Result<(wgpu::Device, wgpu::Queue)> {
         adapter.request_device(
                 &wgpu::DeviceDescriptor {
                     label: None,
-                    features: wgpu::Features::empty(),
+                    // lets MSAA use every sample count adapter supports
+                    features: adapter.features() & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
                     limits: wgpu::Limits::downlevel_webgl2_defaults()
                         .using_resolution(adapter.limits()),
 
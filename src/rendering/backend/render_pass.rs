@@ -1,45 +1,55 @@
-use std::slice::Iter;
-use wgpu::util::DeviceExt;
+use std::{marker::PhantomData, slice::Iter};
 
 use crate::{rendering::{
-    shaders::{ builder::ShaderPipeline, Bindings },
-    Color, DrawConfig, RenderState, RenderStateError,
-    Texture, Vertex,
+    shaders::{ builder::ShaderContext, Bindings, Shader },
+    check_indices, DepthStencilConfig, DrawConfig, RenderState, RenderStateError,
+    ShaderConfig, Texture, Vertex,
 }, resources::AssetWeak};
 
-use super::PassError;
+use super::{draw_list::DrawList, PassError};
 
+/// Collects a draw, which is recorded into it's `DrawCommand` at `RenderPass::submit`.
 pub struct RenderPass<'a, V> where
     V: Vertex
 {
-    encoder: wgpu::CommandEncoder,
-    queue: &'a wgpu::Queue,
     device: &'a wgpu::Device,
-    surface_view: &'a wgpu::TextureView,
-    vertex_data: Vec<V>,
+    draw_list: &'a mut DrawList,
+
+    /// Vertices extended into draw list, indices are relative to them.
+    vertex_count: u32,
+    index_count: usize,
+
+    /// Sort key z of the first draw, every fragment is tested at it's depth.
+    z: Option<f32>,
     bindings: Bindings<'a>,
-    shader_pipeline: &'a ShaderPipeline,
-    clear_color: Option<Color<f32>>,
+    shader: Shader,
+    shader_config: ShaderConfig,
+    shader_context: &'a ShaderContext,
+    phantom: PhantomData<V>,
 }
 
 impl<'a, V: Vertex> RenderPass<'a, V> {
     pub(super) fn new(
-        encoder: wgpu::CommandEncoder,
-        queue: &'a wgpu::Queue,
-        surface_view: &'a wgpu::TextureView,
         device: &'a wgpu::Device,
+        draw_list: &'a mut DrawList,
         bindings: Bindings<'a>,
-        shader_pipeline: &'a ShaderPipeline,
+        shader: Shader,
+        shader_config: ShaderConfig,
+        shader_context: &'a ShaderContext,
     ) -> Self {
+        draw_list.begin();
+
         Self {
-            encoder,
-            queue,
             device,
-            surface_view,
-            vertex_data: Vec::new(),
+            draw_list,
+            vertex_count: 0,
+            index_count: 0,
+            z: None,
             bindings,
-            shader_pipeline,
-            clear_color: None,
+            shader,
+            shader_config,
+            shader_context,
+            phantom: PhantomData,
         }
     }
 
@@ -47,12 +57,6 @@ impl<'a, V: Vertex> RenderPass<'a, V> {
         &mut self.bindings
     }
 
-    pub fn clear_color<C: Into<Color<f32>>>(mut self, color: C) -> Self {
-        self.clear_color = Some(color.into());
-
-        self
-    }
-
     /*
     pub fn extend_vertices<T: IntoIterator<Item = Vector2<f32>>>(mut self, iter: T) -> Self {
         self.vertex_data.extend(iter);
@@ -61,76 +65,27 @@ impl<'a, V: Vertex> RenderPass<'a, V> {
     }
     */
 
-    pub fn submit(mut self) -> Result<(), PassError> {
-        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+    /// Records pass into frame's draw list, which already holds it's geometry.
+    /// Nothing is sent to GPU until `DrawCommand::present`.
+    ///
+    /// [`DrawCommand::present`]: super::DrawCommand::present
+    pub fn submit(self) -> Result<(), PassError> {
+        if self.index_count == 0 || self.draw_list.is_culled() {
+            return Ok(());
+        }
 
         let bind_group = {
             let bindings = self.bindings.collect().map_err(PassError::from)?;
 
-            let bind_group = Some(
-                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("Uniform Bind Group"),
-                    layout: &self.shader_pipeline.get_bind_group_layout(0),
-                    entries: &bindings,
-                })
-            );
-
-            bind_group
+            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Uniform Bind Group"),
+                layout: &self.shader_context.bind_group_layout,
+                entries: &bindings,
+            })
         };
 
-        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("vertex buffer"),
-            contents: bytemuck::cast_slice(self.vertex_data.as_slice()),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        // create wgpu render pass and submit
-
-        {
-            let load = match self.clear_color {
-                Some(clear_color) => wgpu::LoadOp::Clear(clear_color.into()),
-                None => wgpu::LoadOp::Load,
-            };
-
-            let mut pass = self.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: self.surface_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load,
-                        store: true,
-                    },
-                })],
-                depth_stencil_attachment: None,
-            });
-
-            pass.push_debug_group("Prepare data for draw.");
-
-            if let Some(ref bindings) = bind_group {
-                pass.set_bind_group(0, bindings, &[]);
-            }
-
-            pass.set_pipeline(self.shader_pipeline);
-
-            pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-            pass.pop_debug_group();
-            pass.insert_debug_marker("Draw!");
-
-            pass.draw(0..(self.vertex_data.len() as u32), 0..1);
-        }
-
-        // TODO  try to submit multiple command buffers at once?
-        self.queue.submit(Some(self.encoder.finish()));
-
-        //let ex = async_executor::Executor::new();
-        //let task = ex.spawn(self.device.pop_error_scope());
-        //futures_lite::future::block_on(ex.run());
-
-        match futures_lite::future::block_on(self.device.pop_error_scope()) {
-            Some(err) => Err(PassError::from(err)),
-            None => Ok(()),
-        }?;
+        let depth = DepthStencilConfig::depth_of(self.z.unwrap_or_default());
+        self.draw_list.record(self.shader, self.shader_config, bind_group, depth);
 
         Ok(())
     }
@@ -143,10 +98,36 @@ impl<'a, V: Vertex> RenderState<V> for RenderPass<'a, V> {
         _texture: Option<AssetWeak<Texture>>,
         draw_config: DrawConfig<V>,
     ) -> Result<(), RenderStateError> {
-        self.vertex_data.extend(
-            vertices.into_iter()
-                    .map(|v| *v + draw_config.vertex)
-        );
+        let base = self.vertex_count;
+        let count = vertices.len() as u32;
+        self.z.get_or_insert(draw_config.sort_key.z);
+
+        self.draw_list.extend_vertices(vertices.map(|v| *v + draw_config.vertex));
+        self.draw_list.extend_indices(base..(base + count));
+
+        self.vertex_count += count;
+        self.index_count += count as usize;
+
+        Ok(())
+    }
+
+    fn extend_indexed<'t>(
+        &mut self,
+        vertices: Iter<V>,
+        indices: Iter<u32>,
+        _texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), RenderStateError> {
+        check_indices(indices.as_slice(), vertices.len())?;
+
+        let base = self.vertex_count;
+        self.z.get_or_insert(draw_config.sort_key.z);
+
+        self.vertex_count += vertices.len() as u32;
+        self.index_count += indices.len();
+
+        self.draw_list.extend_vertices(vertices.map(|v| *v + draw_config.vertex));
+        self.draw_list.extend_indices(indices.map(|i| base + i));
 
         Ok(())
     }
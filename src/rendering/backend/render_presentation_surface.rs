@@ -1,34 +1,52 @@
-use std::rc::Weak;
-use crate::util::Size;
-use super::PresentationSurfaceError;
+use std::rc::{Rc, Weak};
+use crate::{math::Size2, util::Size};
+use super::{DepthStencilBuffer, MsaaBuffer, PresentationSurfaceError, PresentMode};
 
 pub struct RenderPresentationSurface {
     device: Weak<wgpu::Device>,
-    adapter: wgpu::Adapter,
+    adapter: Rc<wgpu::Adapter>,
     surface: wgpu::Surface,
-    surface_format: wgpu::TextureFormat,
+    present_mode: PresentMode,
+    active_present_mode: wgpu::PresentMode,
     need_reconfigure_swapchain: bool,
     requested_swapchain_size: Option<(u32, u32)>,
     surface_extent: Size<u32>,
+
+    /// Whether a depth stencil buffer is requested, it's created when swapchain is reconfigured.
+    use_depth_stencil: bool,
+    depth_stencil: Option<DepthStencilBuffer>,
+
+    /// Requested MSAA samples per pixel, 1 draws directly into surface.
+    sample_count: u32,
+    msaa: Option<MsaaBuffer>,
 }
 
 impl RenderPresentationSurface {
     pub(super) fn new(
         device: Weak<wgpu::Device>,
-        adapter: wgpu::Adapter,
+        adapter: Rc<wgpu::Adapter>,
         surface: wgpu::Surface,
-        surface_format: wgpu::TextureFormat,
+        present_mode: PresentMode,
         width: u32,
         height: u32,
     ) -> Self {
+        let active_present_mode = present_mode.resolve(
+            &surface.get_capabilities(&adapter).present_modes
+        );
+
         Self {
             device,
             adapter,
             surface,
-            surface_format,
+            present_mode,
+            active_present_mode,
             need_reconfigure_swapchain: true,
             requested_swapchain_size: None,
             surface_extent: Size::new(width, height),
+            use_depth_stencil: false,
+            depth_stencil: None,
+            sample_count: 1,
+            msaa: None,
         }
     }
 
@@ -44,6 +62,56 @@ impl RenderPresentationSurface {
         self.surface_extent.height
     }
 
+    /// Requested present mode.
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Present mode in use, after falling back to what surface supports.
+    pub fn active_present_mode(&self) -> wgpu::PresentMode {
+        self.active_present_mode
+    }
+
+    /// Changes present mode, it's applied when swapchain is reconfigured.
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        if self.present_mode != present_mode {
+            self.present_mode = present_mode;
+            self.need_reconfigure_swapchain = true;
+        }
+    }
+
+    pub fn has_depth_stencil(&self) -> bool {
+        self.use_depth_stencil
+    }
+
+    /// Adds or removes depth stencil buffer, it's applied when swapchain is reconfigured.
+    pub fn set_depth_stencil(&mut self, use_depth_stencil: bool) {
+        if self.use_depth_stencil != use_depth_stencil {
+            self.use_depth_stencil = use_depth_stencil;
+            self.need_reconfigure_swapchain = true;
+        }
+    }
+
+    /// Requested MSAA sample count.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// MSAA sample count in use, after falling back to what surface format supports.
+    pub fn active_sample_count(&self) -> u32 {
+        self.msaa
+            .as_ref()
+            .map_or(1, MsaaBuffer::sample_count)
+    }
+
+    /// Changes MSAA sample count, it's applied when swapchain is reconfigured.
+    pub fn set_sample_count(&mut self, sample_count: u32) {
+        if self.sample_count != sample_count {
+            self.sample_count = sample_count;
+            self.need_reconfigure_swapchain = true;
+        }
+    }
+
     pub fn has_request_reconfigure_swapchain(&self) -> bool {
         self.need_reconfigure_swapchain
     }
@@ -74,8 +142,18 @@ impl RenderPresentationSurface {
         Ok((surface_texture, surface_view))
     }
 
-    pub(super) fn surface_format(&self) -> &wgpu::TextureFormat {
-        &self.surface_format
+    /// View of multisampled color buffer, which is resolved into surface.
+    pub(in crate::rendering) fn msaa_view(&self) -> Option<wgpu::TextureView> {
+        self.msaa
+            .as_ref()
+            .map(MsaaBuffer::create_view)
+    }
+
+    /// View of depth stencil buffer, which matches surface's size.
+    pub(in crate::rendering) fn depth_stencil_view(&self) -> Option<wgpu::TextureView> {
+        self.depth_stencil
+            .as_ref()
+            .map(DepthStencilBuffer::create_view)
     }
 
     pub(super) fn capabilities(&self) -> wgpu::SurfaceCapabilities {
@@ -105,12 +183,20 @@ impl RenderPresentationSurface {
             format: surface_caps.formats[0],
             width: self.surface_extent.width,
             height: self.surface_extent.height,
-            present_mode: wgpu::PresentMode::Fifo,
+            present_mode: self.present_mode.resolve(&surface_caps.present_modes),
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
         };
 
         self.surface.configure(&device, &surface_config);
+        self.active_present_mode = surface_config.present_mode;
+
+        // buffers should always match surface size
+        let size = Size2::new(self.surface_extent.width, self.surface_extent.height);
+        let sample_count = MsaaBuffer::resolve_sample_count(&self.adapter, &device, surface_config.format, self.sample_count);
+
+        self.msaa = (sample_count > 1).then(|| MsaaBuffer::new(&device, surface_config.format, size, sample_count));
+        self.depth_stencil = self.use_depth_stencil.then(|| DepthStencilBuffer::new(&device, size, sample_count));
 
         println!("Surface reconfigured to {}", self.surface_extent);
         self.need_reconfigure_swapchain = false;
@@ -1,27 +1,37 @@
 use std::{
     cell::{ Ref, RefMut, RefCell },
+    collections::HashMap,
+    ops::Range,
     slice::Iter,
     rc::Rc,
 };
 
 use crate::{rendering::{
     backend::DrawCommand,
-    shaders::{ Shader, ShaderInstance },
+    shaders::ShaderInstance,
     texture::TextureId,
+    check_indices,
+    BlendMode,
     DrawConfig,
     RenderState,
     RenderStateError,
     ShaderConfig,
+    SortKey,
     Texture,
     TextureConfig,
-    TextureView,
     Vertex,
 }, resources::AssetWeak};
 
 use super::{DrawBatcherError, BatchMode};
 
+/// Collects draws, which are grouped into as few batches as `BatchMode` allows at `DrawBatcher::flush`.
 pub struct DrawBatcher<'a, 'r, V: Vertex> {
-    batches: Vec<ShaderBatch<'a, V>>,
+    /// Every distinct configuration, in order of first use.
+    configurations: Vec<BatchConfiguration>,
+    configuration_indices: HashMap<ConfigurationKey, usize>,
+    draws: Vec<RecordedDraw>,
+    vertices: Vec<V>,
+    indices: Vec<u32>,
     draw_command: &'a mut DrawCommand<'r>,
     mode: BatchMode,
 }
@@ -29,108 +39,165 @@ pub struct DrawBatcher<'a, 'r, V: Vertex> {
 impl<'a, 'r, V: Vertex> DrawBatcher<'a, 'r, V> {
     pub fn new(draw_command: &'a mut DrawCommand<'r>) -> Self {
         Self {
-            batches: Vec::default(),
+            configurations: Vec::new(),
+            configuration_indices: HashMap::new(),
+            draws: Vec::new(),
+            vertices: Vec::new(),
+            indices: Vec::new(),
             draw_command,
             mode: BatchMode::DrawOrder,
         }
     }
 
+    pub fn with_mode(mut self, mode: BatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> BatchMode {
+        self.mode
+    }
+
+    /// How many batches would be submitted if flushed now.
     pub fn batch_count(&self) -> usize {
-        self.batches.len()
+        self.batches().len()
     }
 
+    /// Every shader instance in-use, once each.
     pub fn shader_instances<'i>(
         &'i self
     ) -> impl Iterator<Item = Ref<'i, (dyn ShaderInstance + 'static)>> {
-        self.batches.iter().map(|b| b.instance.borrow())
+        self.distinct_instances().into_iter().map(|i| i.borrow())
     }
 
     pub fn mut_shader_instances<'i>(
         &'i self
     ) -> impl Iterator<Item = RefMut<'i, (dyn ShaderInstance + 'static)>> {
-        self.batches.iter().map(|b| b.instance.borrow_mut())
+        self.distinct_instances().into_iter().map(|i| i.borrow_mut())
     }
 
-    pub fn flush(mut self) -> Result<(), DrawBatcherError> {
-        //println!("-> Flushing ({} batches)...", self.batches.len());
-        for batch in self.batches.drain(..) {
-            let shader_id = batch.instance.borrow().identifier();
-            //println!("-> With shader id {}", shader_id);
-            let (_texture_id, shader_config, _texture_config) = batch.group.configuration;
+    /// Submits every batch, returning how many there were.
+    pub fn flush(self) -> Result<usize, DrawBatcherError> {
+        let batches = self.batches();
 
-            //println!("-> Group");
-            let shader = batch.instance.borrow();
-            let mut pass = self.draw_command.begin(&shader, &shader_config, None)?;
+        for (configuration, draws) in batches.iter() {
+            let configuration = &self.configurations[*configuration];
+            let (_texture_id, shader_config, texture_config) = configuration.key;
+
+            let texture_view = configuration.texture
+                .as_ref()
+                .map(|t| {
+                    let tex_asset = t.upgrade().expect("Failed to get texture");
+                    let tex = tex_asset.get();
+                    let (device, queue) = self.draw_command.device_queue();
+                    tex.view(device, queue, texture_config)
+                });
 
-            {
-                let bindings = pass.bindings();
+            let shader = configuration.instance.borrow();
+            let shader_id = shader.identifier();
+            let mut pass = self.draw_command.begin(&shader, &shader_config, None)?;
 
-                if let Some(texture_view) = batch.group.texture_view {
-                    //println!("-> With texture ({})", texture_view.id);
-                    bindings.texture_view(texture_view)
-                            .map_err(|e| DrawBatcherError::Bindings(e, shader_id))?;
-                } else {
-                    //println!("-> Without texture");
-                }
+            if let Some(texture_view) = texture_view {
+                pass.bindings()
+                    .texture_view(texture_view)
+                    .map_err(|e| DrawBatcherError::Bindings(e, shader_id))?;
             }
 
-            //println!("Vertex count: {}", batch.group.vertices.len());
-            pass.extend(
-                batch.group.vertices.iter(),
-                None,
-                DrawConfig {
-                    vertex: V::default(),
-                    shader_config: None,
-                    texture_config: None,
-                }
-            ).map_err(DrawBatcherError::from)?;
+            // displacement was already applied at recording
+            for draw in draws.iter().map(|d| &self.draws[*d]) {
+                let vertices = self.vertices[draw.vertices.clone()].iter();
+
+                let draw_config = DrawConfig::default().with_sort_key(draw.sort_key);
+
+                match draw.indices {
+                    Some(ref indices) => pass.extend_indexed(vertices, self.indices[indices.clone()].iter(), None, draw_config),
+                    None => pass.extend(vertices, None, draw_config),
+                }.map_err(DrawBatcherError::from)?;
+            }
 
             pass.submit().map_err(DrawBatcherError::from)?;
         }
 
-        //println!("----------------\n");
-        Ok(())
+        Ok(batches.len())
     }
 
-    fn create_batch<'t>(
-        &mut self,
-        shader: &Shader,
-        texture: Option<AssetWeak<Texture>>,
-        configuration: (TextureId, ShaderConfig, TextureConfig),
-    ) -> Result<&mut ShaderBatch<'a, V>, RenderStateError> {
-        let weak_instance = match self.draw_command.shader_builder().get_instance(shader) {
-            Some(ins) => ins,
-            None => return Err(RenderStateError::ShaderInstanceNotFound(shader.clone())),
-        };
+    fn distinct_instances(&self) -> Vec<&Rc<RefCell<dyn ShaderInstance>>> {
+        let mut instances: Vec<&Rc<RefCell<dyn ShaderInstance>>> = Vec::new();
 
-        self.batches.push(ShaderBatch {
-            instance: weak_instance.upgrade()
-                    .expect(format!("Shader ({}) was dropped", shader).as_str()),
-            group: BatchGroup::new(
-                texture.map(|t| {
-                    let tex_asset = t.upgrade().expect("Failed to get texture");
-                    let tex = tex_asset.get();
-                    let (device, queue) = self.draw_command.device_queue();
-                    tex.view(device, queue, configuration.2.clone())
-                }),
-                configuration,
-            ),
-        });
+        for configuration in self.configurations.iter() {
+            if !instances.iter().any(|i| Rc::ptr_eq(i, &configuration.instance)) {
+                instances.push(&configuration.instance);
+            }
+        }
 
-        // NOTE  safe to unwrap, we just pushed an element
-        Ok(self.batches.last_mut().unwrap())
+        instances
     }
-}
 
-impl<'a, 'r, V> RenderState<V> for DrawBatcher<'a, 'r, V> where
-    V: Vertex,
-{
-    fn extend<'t>(
+    /// Draws in submission order, as sorted by `BatchMode`.
+    fn sorted_draws(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.draws.len()).collect();
+        let key = |i: &usize| self.draws[*i].sort_key;
+
+        // NOTE  stable sorts, so draws keep their order when keys are the same
+        match self.mode {
+            BatchMode::DrawOrder => (),
+            BatchMode::Layered => order.sort_by(|a, b| {
+                key(a).z.total_cmp(&key(b).z)
+                    .then(self.draws[*a].configuration.cmp(&self.draws[*b].configuration))
+            }),
+            BatchMode::BackToFront => order.sort_by(|a, b| {
+                let (opaque_a, opaque_b) = (self.is_opaque(*a), self.is_opaque(*b));
+
+                // opaque draws first, depth test already places them
+                opaque_b.cmp(&opaque_a).then_with(|| match opaque_a && opaque_b {
+                    true => self.draws[*a].configuration.cmp(&self.draws[*b].configuration),
+                    false => key(a).z.total_cmp(&key(b).z),
+                })
+            }),
+            BatchMode::YSort => order.sort_by(|a, b| {
+                key(a).z.total_cmp(&key(b).z)
+                    .then(key(a).y.total_cmp(&key(b).y))
+            }),
+        }
+
+        order
+    }
+
+    /// Whether draw overwrites target and writes depth, so it doesn't depend on what was drawn before.
+    fn is_opaque(&self, draw: usize) -> bool {
+        let (_, shader_config, _) = self.configurations[self.draws[draw].configuration].key;
+        shader_config.blend_mode() == BlendMode::Replace && shader_config.depth_stencil().depth_write
+    }
+
+    /// Sorted draws grouped by configuration, only neighbours are merged.
+    /// A batch is drawn at a single depth, so depth tested draws at distinct z aren't merged.
+    fn batches(&self) -> Vec<(usize, Vec<usize>)> {
+        let mut batches: Vec<(usize, Vec<usize>)> = Vec::new();
+
+        for draw in self.sorted_draws() {
+            let configuration = self.draws[draw].configuration;
+
+            let mergeable = |last: usize, first: usize| {
+                last == configuration
+                    && (!self.configurations[configuration].key.1.depth_stencil().uses_depth()
+                        || self.draws[first].sort_key.z == self.draws[draw].sort_key.z)
+            };
+
+            match batches.last_mut() {
+                Some((last, draws)) if mergeable(*last, draws[0]) => draws.push(draw),
+                _ => batches.push((configuration, vec![draw])),
+            }
+        }
+
+        batches
+    }
+
+    /// Validates draw configuration, returning it's index at `configurations`.
+    fn configuration_for(
         &mut self,
-        vertices: Iter<V>,
         texture: Option<AssetWeak<Texture>>,
-        draw_config: DrawConfig<V>
-    ) -> Result<(), RenderStateError> {
+        draw_config: &DrawConfig<V>,
+    ) -> Result<usize, RenderStateError> {
         let shader_config = draw_config
                              .shader_config
                              .ok_or_else(|| RenderStateError::MissingShaderConfig)?;
@@ -143,7 +210,7 @@ impl<'a, 'r, V> RenderState<V> for DrawBatcher<'a, 'r, V> where
                 // TODO  maybe we can disable these validations at release mode
 
                 for b in c.bindings_descriptor() {
-                    b.validate_config(&draw_config)
+                    b.validate_config(draw_config)
                      .map_err(RenderStateError::from)?;
                 }
 
@@ -159,50 +226,227 @@ impl<'a, 'r, V> RenderState<V> for DrawBatcher<'a, 'r, V> where
             None => TextureId::NONE,
         };
 
-        // check if we can extend last batch
-        // or we'll need a new one
-        let configuration = (texture_id.clone(), shader_config.clone(), texture_config.clone());
-
-        let shader_batch = match self.batches.last_mut() {
-            Some(last_batch) => if last_batch.group.configuration == configuration {
-                // we can reuse last batch
-                last_batch
-            } else {
-                // we need a new one
-                self.create_batch(shader, texture, configuration)?
-            },
-            None => self.create_batch(shader, texture, configuration)?,
-        };
+        let key = (texture_id, shader_config, texture_config);
 
-        shader_batch.group.vertices.extend(vertices.map(
-            |v| *v + draw_config.vertex
-        ));
+        if let Some(index) = self.configuration_indices.get(&key) {
+            return Ok(*index);
+        }
+
+        // a dropped instance is as good as a missing one
+        let instance = self.draw_command
+                           .shader_builder()
+                           .get_instance(shader)
+                           .and_then(|weak_instance| weak_instance.upgrade())
+                           .ok_or(RenderStateError::ShaderInstanceNotFound(*shader))?;
+
+        self.configurations.push(BatchConfiguration {
+            instance,
+            texture,
+            key,
+        });
+
+        let index = self.configurations.len() - 1;
+        self.configuration_indices.insert(key, index);
+
+        Ok(index)
+    }
+
+    fn record(
+        &mut self,
+        vertices: Iter<V>,
+        indices: Option<Iter<u32>>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), RenderStateError> {
+        if let Some(ref indices) = indices {
+            check_indices(indices.as_slice(), vertices.len())?;
+        }
+
+        let configuration = self.configuration_for(texture, &draw_config)?;
+
+        let vertex_start = self.vertices.len();
+        self.vertices.extend(vertices.map(|v| *v + draw_config.vertex));
+
+        let indices = indices.map(|indices| {
+            let index_start = self.indices.len();
+            self.indices.extend(indices);
+            index_start..self.indices.len()
+        });
+
+        self.draws.push(RecordedDraw {
+            configuration,
+            sort_key: draw_config.sort_key,
+            vertices: vertex_start..self.vertices.len(),
+            indices,
+        });
 
         Ok(())
     }
 }
 
-struct ShaderBatch<'a, V: Vertex> {
-    pub instance: Rc<RefCell<dyn ShaderInstance>>,
-    pub group: BatchGroup<'a, V>,
+impl<'a, 'r, V> RenderState<V> for DrawBatcher<'a, 'r, V> where
+    V: Vertex,
+{
+    fn extend<'t>(
+        &mut self,
+        vertices: Iter<V>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>
+    ) -> Result<(), RenderStateError> {
+        self.record(vertices, None, texture, draw_config)
+    }
+
+    fn extend_indexed<'t>(
+        &mut self,
+        vertices: Iter<V>,
+        indices: Iter<u32>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>
+    ) -> Result<(), RenderStateError> {
+        self.record(vertices, Some(indices), texture, draw_config)
+    }
 }
 
-#[derive(Default)]
-struct BatchGroup<'v, V: Vertex> {
-    pub texture_view: Option<TextureView<'v>>,
-    pub configuration: (TextureId, ShaderConfig, TextureConfig),
-    pub vertices: Vec<V>,
+type ConfigurationKey = (TextureId, ShaderConfig, TextureConfig);
+
+struct BatchConfiguration {
+    instance: Rc<RefCell<dyn ShaderInstance>>,
+    texture: Option<AssetWeak<Texture>>,
+    key: ConfigurationKey,
 }
 
-impl<'v, V: Vertex> BatchGroup<'v, V> {
-    pub fn new(
-        texture_view: Option<TextureView<'v>>,
-        configuration: (TextureId, ShaderConfig, TextureConfig),
-    ) -> Self {
-        Self {
-            texture_view,
-            configuration,
-            vertices: Vec::new(),
+struct RecordedDraw {
+    configuration: usize,
+    sort_key: SortKey,
+
+    /// Range at batcher's vertices.
+    vertices: Range<usize>,
+
+    /// Range at batcher's indices, relative to draw's own vertices.
+    /// Non-indexed draws don't have it.
+    indices: Option<Range<usize>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::{Matrix4x4, Size2, Vector2},
+        rendering::{
+            graphics::QUAD_INDICES,
+            Color,
+            DepthStencilConfig,
+            GraphicAdapter,
+            TextureFormat,
+            Vertex2DTextureColor,
+            VertexColor,
+            VertexPosition,
+        },
+        resources::Asset,
+        systems::render_system::default_shader::DefaultShader,
+    };
+
+    use super::*;
+
+    /// Non-indexed triangle and indexed quad share a batch, quad's indices are rebased past the triangle.
+    /// Indices past draw's own vertices are rejected, rather than reading another draw's.
+    #[test]
+    fn indices_are_rebased() {
+        let graphic_adapter = GraphicAdapter::software();
+
+        let shader = DefaultShader::new(&graphic_adapter);
+        shader.borrow_mut().uniforms_mut().view = Matrix4x4::ortho(4.0, 0.0, 0.0, 4.0, -1.0, 1.0);
+
+        let shader_config = *shader.borrow().default_config();
+        let render_target = graphic_adapter.borrow().create_render_target(Size2::new(4, 4));
+
+        let vertex = |x, y| Vertex2DTextureColor::from_position(Vector2::new(x, y))
+            .with_color(Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0));
+
+        let triangle = [vertex(0.0, 0.0), vertex(2.0, 0.0), vertex(0.0, 2.0)];
+        let quad = [vertex(2.0, 2.0), vertex(4.0, 2.0), vertex(2.0, 4.0), vertex(4.0, 4.0)];
+
+        let draw_config = DrawConfig {
+            shader_config: Some(shader_config),
+            texture_config: Some(Default::default()),
+            ..Default::default()
+        };
+
+        {
+            let mut adapter = graphic_adapter.borrow_mut();
+            let mut draw_command = adapter.prepare_draw_to(&render_target);
+            draw_command.clear(Color::<f32>::rgba(0.0, 0.0, 0.0, 1.0));
+
+            let mut draw_batcher = DrawBatcher::new(&mut draw_command);
+            draw_batcher.extend(triangle.iter(), None, draw_config).unwrap();
+            draw_batcher.extend_indexed(quad.iter(), QUAD_INDICES.iter(), None, draw_config).unwrap();
+
+            let result = draw_batcher.extend_indexed(quad.iter(), [0, 4, 1].iter(), None, draw_config);
+            assert!(matches!(result, Err(RenderStateError::IndexOutOfBounds { index: 4, vertices: 4 })));
+
+            assert_eq!(draw_batcher.flush().unwrap(), 1);
+            draw_command.present().unwrap();
         }
+
+        let image = graphic_adapter.borrow().capture(&render_target).unwrap();
+
+        assert_eq!(image.get_pixel(0, 0)[0], 255);
+        assert_eq!(image.get_pixel(3, 3)[0], 255);
+        assert_eq!(image.get_pixel(3, 0)[0], 0);
+    }
+
+    #[test]
+    fn batch_modes() {
+        let graphic_adapter = GraphicAdapter::software();
+
+        let shader = DefaultShader::new(&graphic_adapter);
+        let shader_config = *shader.borrow().default_config();
+        let opaque_config = shader_config
+            .with_blend_mode(BlendMode::Replace)
+            .with_depth_stencil(DepthStencilConfig::depth());
+
+        let render_target = graphic_adapter.borrow().create_render_target(Size2::new(4, 4));
+
+        let textures: Vec<Asset<Texture>> = (0..2)
+            .map(|_| Asset::new(Texture::new(TextureFormat::Rgba8UnormSrgb, Size2::new(1, 1), &[0; 4])))
+            .collect();
+
+        // (texture, z, y, opaque)
+        let draws = [
+            (0, 0.0, 3.0, false),
+            (1, 0.0, 2.0, false),
+            (0, 0.0, 1.0, false),
+            (1, 1.0, 0.0, false),
+            (0, 1.0, -1.0, false),
+            (1, 2.0, 0.5, true),
+        ];
+
+        let quad = [Vertex2DTextureColor::default(); 4];
+
+        let mut adapter = graphic_adapter.borrow_mut();
+        let mut draw_command = adapter.prepare_draw_to(&render_target);
+
+        let mut record = |mode| {
+            let mut draw_batcher = DrawBatcher::new(&mut draw_command).with_mode(mode);
+
+            for (texture, z, y, opaque) in draws {
+                draw_batcher.extend_indexed(
+                    quad.iter(),
+                    QUAD_INDICES.iter(),
+                    Some(textures[texture].weak()),
+                    DrawConfig {
+                        shader_config: Some(if opaque { opaque_config } else { shader_config }),
+                        texture_config: Some(Default::default()),
+                        ..Default::default()
+                    }.with_sort_key(SortKey::new(z, y)),
+                ).unwrap();
+            }
+
+            (draw_batcher.batch_count(), draw_batcher.sorted_draws())
+        };
+
+        assert_eq!(record(BatchMode::DrawOrder), (6, vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(record(BatchMode::Layered), (5, vec![0, 2, 1, 4, 3, 5]));
+        assert_eq!(record(BatchMode::BackToFront), (6, vec![5, 0, 1, 2, 3, 4]));
+        assert_eq!(record(BatchMode::YSort), (5, vec![2, 1, 0, 4, 3, 5]));
     }
 }
@@ -4,6 +4,31 @@ pub use draw_batcher::DrawBatcher;
 mod draw_batcher_error;
 pub use draw_batcher_error::DrawBatcherError;
 
+/// How a `DrawBatcher` orders draws, draws are merged when they end up next to each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum BatchMode {
+    /// As they were submitted.
+    #[default]
     DrawOrder,
+
+    /// By `SortKey::z`, then by shader and texture, so compatible draws
+    /// at the same z are merged even when they weren't submitted together.
+    ///
+    /// [`SortKey::z`]: crate::rendering::SortKey::z
+    Layered,
+
+    /// Opaque draws first, which use `BlendMode::Replace` and write depth, grouped by shader and texture.
+    /// Then the rest by `SortKey::z`, farthest first, keeping submission order otherwise.
+    /// Suited for translucent graphics over depth tested ones.
+    ///
+    /// [`BlendMode::Replace`]: crate::rendering::BlendMode::Replace
+    /// [`SortKey::z`]: crate::rendering::SortKey::z
+    BackToFront,
+
+    /// By `SortKey::z`, then by `SortKey::y`, for top-down games where what's lower
+    /// at the screen is drawn on top.
+    ///
+    /// [`SortKey::z`]: crate::rendering::SortKey::z
+    /// [`SortKey::y`]: crate::rendering::SortKey::y
+    YSort,
 }
@@ -0,0 +1,20 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+use crate::rendering::TextureFormat;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum CaptureError {
+    #[error("texture format {0:?} can't be captured")]
+    UnsupportedFormat(TextureFormat),
+
+    #[error("failed to map capture buffer: {0}")]
+    BufferMap(wgpu::BufferAsyncError),
+
+    #[error("graphics device was lost while capturing")]
+    DeviceLost,
+
+    #[error("failed to save captured image: {0}")]
+    Save(#[from] image::ImageError),
+}
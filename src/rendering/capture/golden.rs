@@ -0,0 +1,328 @@
+//! Golden image comparison, to catch rendering regressions.
+//!
+//! A golden image is a PNG which holds a known good result. It's only written when
+//! `MIDNIGHT_UPDATE_GOLDEN` env var is set, a missing one fails the comparison.
+
+use std::path::Path;
+
+use image::RgbaImage;
+
+use super::GoldenError;
+
+pub const UPDATE_ENV_VAR: &str = "MIDNIGHT_UPDATE_GOLDEN";
+
+/// How much an image may differ from golden.
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance {
+    /// Max difference at any channel for a pixel to be considered equal.
+    pub channel: u8,
+
+    /// Ratio, in [0, 1], of pixels which may differ.
+    pub mismatched_ratio: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            channel: 2,
+            mismatched_ratio: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDiff {
+    pub mismatched: usize,
+    pub max_delta: u8,
+}
+
+/// Compares pixel by pixel, counting the ones which exceeds channel tolerance.
+pub fn compare(
+    actual: &RgbaImage,
+    expected: &RgbaImage,
+    channel_tolerance: u8,
+) -> Result<ImageDiff, GoldenError> {
+    if actual.dimensions() != expected.dimensions() {
+        return Err(GoldenError::SizeMismatch {
+            expected: expected.dimensions(),
+            actual: actual.dimensions(),
+        });
+    }
+
+    let mut diff = ImageDiff::default();
+
+    for (a, e) in actual.pixels().zip(expected.pixels()) {
+        let delta = a.0
+                     .iter()
+                     .zip(e.0.iter())
+                     .map(|(a, e)| a.abs_diff(*e))
+                     .max()
+                     .unwrap_or(0);
+
+        diff.max_delta = diff.max_delta.max(delta);
+
+        if delta > channel_tolerance {
+            diff.mismatched += 1;
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Compares against golden image at path, writing it instead when it should be updated.
+pub fn check<P: AsRef<Path>>(
+    actual: &RgbaImage,
+    golden_path: P,
+    tolerance: Tolerance,
+) -> Result<(), GoldenError> {
+    let golden_path = golden_path.as_ref();
+
+    if std::env::var_os(UPDATE_ENV_VAR).is_some() {
+        if let Some(parent) = golden_path.parent() {
+            std::fs::create_dir_all(parent).map_err(image::ImageError::IoError)?;
+        }
+
+        actual.save_with_format(golden_path, image::ImageFormat::Png)?;
+        return Ok(());
+    }
+
+    if !golden_path.exists() {
+        return Err(GoldenError::Missing(golden_path.to_owned()));
+    }
+
+    let expected = image::open(golden_path)?.into_rgba8();
+    let diff = compare(actual, &expected, tolerance.channel)?;
+    let allowed = (tolerance.mismatched_ratio * (actual.width() * actual.height()) as f32) as usize;
+
+    if diff.mismatched > allowed {
+        return Err(GoldenError::Mismatch {
+            mismatched: diff.mismatched,
+            max_delta: diff.max_delta,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use crate::{
+        math::{Affine2, Matrix4x4, Rectangle, Size2, Vector2},
+        rendering::{
+            batchers::DrawBatcher,
+            fonts::{mtsdf::MTSDFShader, Font},
+            graphics::{Graphic, Grid, Text, Tilemap},
+            Color,
+            DrawConfig,
+            PrimitiveState,
+            PrimitiveTopology,
+            ShaderConfig,
+            Texture,
+            TransformedRenderState,
+            Vertex2DTexture,
+            Vertex2DTextureColor,
+        },
+        resources::Asset,
+        test_util::{check_golden, colored, fill, TestScene},
+        util::Size,
+    };
+
+    use super::*;
+
+    #[test]
+    fn tolerance() {
+        let expected = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, Rgba([12, 20, 30, 255]));
+        actual.put_pixel(1, 0, Rgba([10, 40, 30, 255]));
+
+        let diff = compare(&actual, &expected, 2).unwrap();
+        assert_eq!(diff, ImageDiff { mismatched: 1, max_delta: 20 });
+
+        assert!(compare(&RgbaImage::new(2, 2), &expected, 2).is_err());
+    }
+
+    /// Renders with a software adapter, golden is kept at `tests/golden`.
+    #[test]
+    fn clear_golden() {
+        let scene = TestScene::new();
+        let image = scene.render(&scene.render_target(), 0xFF236EFFu32, |_| {});
+
+        check_golden(&image, "clear");
+    }
+
+    /// Every flush is recorded into the same frame pass.
+    #[test]
+    fn rectangles_golden() {
+        let scene = TestScene::new();
+
+        let rectangles = [
+            (Rectangle::new(Vector2::new(2.0, 2.0), Size2::new(8.0, 6.0)), Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0)),
+            (Rectangle::new(Vector2::new(6.0, 6.0), Size2::new(8.0, 8.0)), Color::<f32>::rgba(0.0, 1.0, 0.0, 1.0)),
+        ];
+
+        let image = scene.render(&scene.render_target(), 0xFF236EFFu32, |draw_command| {
+            for (rectangle, color) in rectangles {
+                let mut draw_batcher = DrawBatcher::new(draw_command);
+                fill(&mut draw_batcher, rectangle, colored(color, scene.shader_config));
+                draw_batcher.flush().unwrap();
+            }
+        });
+
+        check_golden(&image, "rectangles");
+    }
+
+    #[test]
+    fn missing_golden() {
+        if std::env::var_os(UPDATE_ENV_VAR).is_some() {
+            return;
+        }
+
+        let result = check(
+            &RgbaImage::new(2, 2),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/missing.png"),
+            Tolerance::default(),
+        );
+
+        assert!(matches!(result, Err(GoldenError::Missing(_))));
+    }
+
+    /// Lines centered at pixels, so rasterization doesn't depend on rounding.
+    #[test]
+    fn grid_golden() {
+        let scene = TestScene::new();
+
+        let shader_config = ShaderConfig::new(&*scene.shader.borrow(), PrimitiveState {
+            topology: PrimitiveTopology::LineList,
+            ..Default::default()
+        });
+
+        let grid = Grid::<Vertex2DTextureColor> {
+            columns: 3,
+            rows: 2,
+            tile_size: Size::new(4, 5),
+            phantom: Default::default(),
+        };
+
+        let image = scene.render(&scene.render_target(), 0x000000FFu32, |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+            let transform = Affine2::from_transform(Vector2::new(1.5, 1.5), 0.0, Vector2::one(), Vector2::zero());
+
+            grid.draw(
+                &mut TransformedRenderState::new(&mut draw_batcher, transform),
+                colored(Color::<f32>::rgba(0.0, 1.0, 0.0, 1.0), shader_config),
+            ).unwrap();
+
+            draw_batcher.flush().unwrap();
+        });
+
+        // a corner and a tile's center
+        assert_eq!(image.get_pixel(1, 1)[1], 255);
+        assert_eq!(image.get_pixel(3, 3)[1], 0);
+
+        check_golden(&image, "grid");
+    }
+
+    /// Tiles picked from a two tiles tileset, a full one and a half one.
+    /// It's drawn with `MTSDFShader`, so white texels are filled and black ones discarded.
+    #[test]
+    fn tilemap_golden() {
+        let scene = TestScene::new();
+        let shader = MTSDFShader::new(&mut scene.graphic_adapter.borrow_mut());
+
+        {
+            let mut shader = shader.borrow_mut();
+            let uniforms = shader.uniforms_mut();
+            uniforms.view = Matrix4x4::ortho(16.0, 0.0, 0.0, 16.0, -1.0, 1.0);
+            uniforms.color = Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0);
+            uniforms.screen_px_range = 1.0;
+        }
+
+        let shader_config = *shader.borrow().default_config();
+
+        let tileset = RgbaImage::from_fn(8, 4, |x, _| match x {
+            0..=5 => Rgba([255, 255, 255, 255]),
+            _ => Rgba([0, 0, 0, 255]),
+        });
+
+        let tileset = Asset::new(Texture::from_image(tileset));
+
+        let mut tilemap = Tilemap::<Vertex2DTexture>::new(4, 3, Size2::new(4, 4), &tileset);
+        tilemap.set_tiles_coord((0..12).map(|i| Vector2::new((i % 4 + i / 4) % 2, 0)).collect());
+
+        let image = scene.render(&scene.render_target(), 0x000000FFu32, |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+
+            tilemap.draw(&mut draw_batcher, DrawConfig {
+                vertex: Vertex2DTexture::default(),
+                shader_config: Some(shader_config),
+                texture_config: Some(Default::default()),
+                ..Default::default()
+            }).unwrap();
+
+            draw_batcher.flush().unwrap();
+        });
+
+        // full tile, left and right halves of half tile, below tilemap
+        assert_eq!(image.get_pixel(3, 1)[0], 255);
+        assert_eq!(image.get_pixel(5, 1)[0], 255);
+        assert_eq!(image.get_pixel(7, 1)[0], 0);
+        assert_eq!(image.get_pixel(1, 14)[0], 0);
+
+        check_golden(&image, "tilemap");
+    }
+
+    /// Font with two glyphs, a vertical and a horizontal bar, rendered from a synthetic atlas.
+    #[test]
+    fn text_golden() {
+        let scene = TestScene::new();
+        let shader = MTSDFShader::new(&mut scene.graphic_adapter.borrow_mut());
+
+        {
+            let mut shader = shader.borrow_mut();
+            let uniforms = shader.uniforms_mut();
+            uniforms.view = Matrix4x4::ortho(16.0, 0.0, 0.0, 16.0, -1.0, 1.0);
+            uniforms.color = Color::<f32>::rgba(1.0, 1.0, 1.0, 1.0);
+            uniforms.screen_px_range = 1.0;
+        }
+
+        let shader_config = *shader.borrow().default_config();
+
+        // inside a glyph is white, outside is black
+        let atlas = RgbaImage::from_fn(8, 8, |x, y| match (x, y) {
+            (1..=2, _) | (4..=7, 3..=4) => Rgba([255, 255, 255, 255]),
+            _ => Rgba([0, 0, 0, 255]),
+        });
+
+        let atlas = Asset::new(Texture::from_image(atlas));
+
+        let font = Font::load_mtsdf(&atlas, concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/bar_font.json"))
+            .with_size(8.0);
+
+        let text = Text::<_, Vertex2DTexture>::with_value(font, "I-I".to_owned());
+        assert_eq!(text.px_size().width, 12.0);
+
+        let image = scene.render(&scene.render_target(), 0x000000FFu32, |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+            let transform = Affine2::from_transform(Vector2::new(2.0, 4.0), 0.0, Vector2::one(), Vector2::zero());
+
+            text.draw(&mut TransformedRenderState::new(&mut draw_batcher, transform), DrawConfig {
+                vertex: Vertex2DTexture::default(),
+                shader_config: Some(shader_config),
+                texture_config: Some(Default::default()),
+                ..Default::default()
+            }).unwrap();
+
+            draw_batcher.flush().unwrap();
+        });
+
+        // vertical bar of first 'I' and horizontal bar of '-'
+        assert_eq!(image.get_pixel(3, 4)[0], 255);
+        assert_eq!(image.get_pixel(7, 7)[0], 255);
+        assert_eq!(image.get_pixel(7, 4)[0], 0);
+
+        check_golden(&image, "text");
+    }
+}
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+use miette::Diagnostic;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug)]
+pub enum GoldenError {
+    #[error("image size {actual:?} differs from golden's {expected:?}")]
+    SizeMismatch { expected: (u32, u32), actual: (u32, u32) },
+
+    #[error("{mismatched} pixels differs from golden (max channel delta: {max_delta})")]
+    Mismatch { mismatched: usize, max_delta: u8 },
+
+    #[error("golden image {0:?} is missing, set MIDNIGHT_UPDATE_GOLDEN to write it")]
+    Missing(PathBuf),
+
+    #[error("failed to read or write golden image: {0}")]
+    Image(#[from] image::ImageError),
+}
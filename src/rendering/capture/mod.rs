@@ -0,0 +1,97 @@
+mod capture_error;
+pub use capture_error::CaptureError;
+
+mod golden_error;
+pub use golden_error::GoldenError;
+
+pub mod golden;
+
+use std::num::NonZeroU32;
+
+use image::RgbaImage;
+
+use super::{RenderTarget, TextureFormat};
+
+/// Copies render target contents into an image, blocking until GPU is done.
+/// Only render targets are copyable, presentation surface isn't.
+pub(super) fn read_render_target(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    render_target: &RenderTarget,
+) -> Result<RgbaImage, CaptureError> {
+    let swap_red_blue = match render_target.format() {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+        format => return Err(CaptureError::UnsupportedFormat(format)),
+    };
+
+    let (width, height) = (render_target.width(), render_target.height());
+
+    // rows must be aligned when copying into a buffer
+    let unpadded_bytes_per_row = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("capture buffer"),
+        size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("capture encoder"),
+    });
+
+    encoder.copy_texture_to_buffer(
+        render_target.raw().as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_bytes_per_row),
+                rows_per_image: NonZeroU32::new(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+
+    queue.submit(Some(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        sender.send(result).ok();
+    });
+
+    device.poll(wgpu::Maintain::Wait);
+
+    receiver.recv()
+            .map_err(|_| CaptureError::DeviceLost)?
+            .map_err(CaptureError::BufferMap)?;
+
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+
+    {
+        let data = slice.get_mapped_range();
+
+        for row in data.chunks_exact(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+    }
+
+    buffer.unmap();
+
+    if swap_red_blue {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    RgbaImage::from_raw(width, height, pixels)
+        .ok_or(CaptureError::DeviceLost)
+}
@@ -0,0 +1,97 @@
+use wgpu::{
+    BlendComponent,
+    BlendFactor,
+    BlendOperation,
+    BlendState,
+};
+
+/// How a draw's colors are combined with what's already at the target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Overwrites target, alpha is written as is.
+    /// It's the default, so configurations which don't pick a mode aren't blended.
+    #[default]
+    Replace,
+
+    /// Straight alpha, source is weighted by it's alpha.
+    Alpha,
+
+    /// Source colors are expected to be already multiplied by their alpha.
+    Premultiplied,
+
+    /// Source, weighted by it's alpha, is added to target. Suited for glows and lights.
+    Additive,
+
+    /// Target is multiplied by source, which can only darken it.
+    Multiply,
+
+    Custom(BlendState),
+}
+
+impl BlendMode {
+    /// State used at pipeline creation, `None` disables blending.
+    pub fn blend_state(&self) -> Option<BlendState> {
+        match self {
+            BlendMode::Replace => None,
+            BlendMode::Alpha => Some(BlendState::ALPHA_BLENDING),
+            BlendMode::Premultiplied => Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            BlendMode::Additive => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: Self::KEEP_TARGET_ALPHA,
+            }),
+            BlendMode::Multiply => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::Zero,
+                    operation: BlendOperation::Add,
+                },
+                alpha: Self::KEEP_TARGET_ALPHA,
+            }),
+            BlendMode::Custom(state) => Some(*state),
+        }
+    }
+
+    const KEEP_TARGET_ALPHA: BlendComponent = BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::{Rectangle, Size2, Vector2},
+        rendering::{batchers::DrawBatcher, Color},
+        test_util::{check_golden, colored, fill, TestScene},
+    };
+
+    use super::*;
+
+    /// Half transparent red over a teal clear, one column per blend mode.
+    #[test]
+    fn blend_modes_golden() {
+        let scene = TestScene::new();
+        let blend_modes = [BlendMode::Replace, BlendMode::Alpha, BlendMode::Additive, BlendMode::Multiply];
+
+        let image = scene.render(&scene.render_target(), 0x408080FFu32, |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+
+            for (column, blend_mode) in blend_modes.into_iter().enumerate() {
+                fill(
+                    &mut draw_batcher,
+                    Rectangle::new(Vector2::new(column as f32 * 4.0, 0.0), Size2::new(4.0, 16.0)),
+                    colored(Color::<f32>::rgba(1.0, 0.0, 0.0, 0.5), scene.shader_config.with_blend_mode(blend_mode)),
+                );
+            }
+
+            assert_eq!(draw_batcher.flush().unwrap(), blend_modes.len());
+        });
+
+        check_golden(&image, "blend_modes");
+    }
+}
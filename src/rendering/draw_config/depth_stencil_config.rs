@@ -0,0 +1,217 @@
+use std::f32::consts::PI;
+
+use wgpu::{
+    CompareFunction,
+    DepthBiasState,
+    DepthStencilState,
+    StencilFaceState,
+    StencilOperation,
+    StencilState,
+    TextureFormat,
+};
+
+/// Depth test and stencil operations of a draw.
+///
+/// It only has effect when target has a depth stencil buffer, default one passes every test
+/// and writes nothing, so it draws as if there were none.
+///
+/// Draws are tested at the depth of their `SortKey::z`, see `DepthStencilConfig::depth_of`.
+///
+/// [`SortKey::z`]: crate::rendering::SortKey::z
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DepthStencilConfig {
+    /// Compares draw's depth against buffer's, which is cleared to the farthest value.
+    pub depth_compare: CompareFunction,
+    pub depth_write: bool,
+
+    /// Stencil operations of front and back facing primitives.
+    pub stencil_front: StencilFaceState,
+    pub stencil_back: StencilFaceState,
+    pub stencil_read_mask: u32,
+    pub stencil_write_mask: u32,
+
+    /// Value which stencil operations compare against or write.
+    /// It's set while drawing, so it doesn't take a pipeline of it's own.
+    pub stencil_reference: u32,
+}
+
+impl Default for DepthStencilConfig {
+    fn default() -> Self {
+        Self {
+            depth_compare: CompareFunction::Always,
+            depth_write: false,
+            stencil_front: StencilFaceState::IGNORE,
+            stencil_back: StencilFaceState::IGNORE,
+            stencil_read_mask: 0,
+            stencil_write_mask: 0,
+            stencil_reference: 0,
+        }
+    }
+}
+
+impl DepthStencilConfig {
+    /// Only nearer, or as near, draws pass and they become the nearest.
+    pub fn depth() -> Self {
+        Self {
+            depth_compare: CompareFunction::LessEqual,
+            depth_write: true,
+            ..Self::default()
+        }
+    }
+
+    /// Writes `reference` into stencil wherever it's drawn, such as a mask shape.
+    pub fn stencil_write(reference: u32) -> Self {
+        Self::with_stencil(reference, StencilFaceState {
+            compare: CompareFunction::Always,
+            fail_op: StencilOperation::Keep,
+            depth_fail_op: StencilOperation::Keep,
+            pass_op: StencilOperation::Replace,
+        })
+    }
+
+    /// Draws only where stencil is equal to `reference`, so inside a mask written before.
+    pub fn stencil_mask(reference: u32) -> Self {
+        Self::with_stencil(reference, StencilFaceState {
+            compare: CompareFunction::Equal,
+            ..StencilFaceState::IGNORE
+        })
+    }
+
+    /// Depth buffer value of a draw at `z`, greater values are nearer.
+    /// Any finite `z` fits, precision is better the closer it's to zero.
+    pub fn depth_of(z: f32) -> f32 {
+        0.5 - z.atan() / PI
+    }
+
+    /// Whether draws depend on depth buffer, so their depth should be kept apart.
+    pub fn uses_depth(&self) -> bool {
+        self.depth_write || self.depth_compare != CompareFunction::Always
+    }
+
+    pub fn stencil(&self) -> StencilState {
+        StencilState {
+            front: self.stencil_front,
+            back: self.stencil_back,
+            read_mask: self.stencil_read_mask,
+            write_mask: self.stencil_write_mask,
+        }
+    }
+
+    pub(in crate::rendering) fn state(&self, format: TextureFormat) -> DepthStencilState {
+        DepthStencilState {
+            format,
+            depth_write_enabled: self.depth_write,
+            depth_compare: self.depth_compare,
+            stencil: self.stencil(),
+            bias: DepthBiasState::default(),
+        }
+    }
+
+    fn with_stencil(reference: u32, face: StencilFaceState) -> Self {
+        Self {
+            stencil_front: face,
+            stencil_back: face,
+            stencil_read_mask: !0,
+            stencil_write_mask: !0,
+            stencil_reference: reference,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        math::{Rectangle, Size2, Vector2},
+        rendering::{
+            batchers::DrawBatcher,
+            BlendComponent,
+            BlendFactor,
+            BlendMode,
+            BlendOperation,
+            BlendState,
+            Color,
+            SortKey,
+        },
+        test_util::{check_golden, colored, fill, TestScene, SCENE_SIZE},
+    };
+
+    use super::*;
+
+    /// Red at greater z is drawn first, blue behind it only shows where it's uncovered.
+    #[test]
+    fn depth_from_z() {
+        let scene = TestScene::new();
+        let shader_config = scene.shader_config.with_depth_stencil(DepthStencilConfig::depth());
+        let render_target = scene.graphic_adapter.borrow().create_render_target_with(Size2::new(SCENE_SIZE, SCENE_SIZE), 1, true);
+
+        let draws = [
+            (Rectangle::new(Vector2::zero(), Size2::new(12.0, 12.0)), Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0), 1.0),
+            (Rectangle::new(Vector2::new(4.0, 4.0), Size2::new(12.0, 12.0)), Color::<f32>::rgba(0.0, 0.0, 1.0, 1.0), 0.0),
+        ];
+
+        let image = scene.render(&render_target, Color::<f32>::rgba(0.0, 0.0, 0.0, 1.0), |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+
+            for (rectangle, color, z) in draws {
+                fill(&mut draw_batcher, rectangle, colored(color, shader_config).with_sort_key(SortKey::new(z, 0.0)));
+            }
+
+            // same configuration, but they're apart at depth
+            assert_eq!(draw_batcher.flush().unwrap(), 2);
+        });
+
+        assert_eq!(image.get_pixel(8, 8).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(14, 14).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(14, 2).0, [0, 0, 0, 255]);
+    }
+
+    /// Red is only drawn inside a stencil mask, while blue fails depth test.
+    #[test]
+    fn depth_stencil_golden() {
+        let scene = TestScene::new();
+        let shader_config = scene.shader_config;
+        let render_target = scene.graphic_adapter.borrow().create_render_target_with(Size2::new(SCENE_SIZE, SCENE_SIZE), 1, true);
+
+        // mask only writes stencil, target is kept as is
+        let keep_target = BlendMode::Custom(BlendState {
+            color: BlendComponent { src_factor: BlendFactor::Zero, dst_factor: BlendFactor::One, operation: BlendOperation::Add },
+            alpha: BlendComponent { src_factor: BlendFactor::Zero, dst_factor: BlendFactor::One, operation: BlendOperation::Add },
+        });
+
+        let draws = [
+            (
+                Rectangle::new(Vector2::new(4.0, 4.0), Size2::new(8.0, 8.0)),
+                Color::<f32>::rgba(1.0, 1.0, 1.0, 1.0),
+                shader_config.with_blend_mode(keep_target).with_depth_stencil(DepthStencilConfig::stencil_write(1)),
+            ),
+            (
+                Rectangle::new(Vector2::zero(), Size2::new(16.0, 16.0)),
+                Color::<f32>::rgba(1.0, 0.0, 0.0, 1.0),
+                shader_config.with_depth_stencil(DepthStencilConfig::stencil_mask(1)),
+            ),
+            (
+                Rectangle::new(Vector2::zero(), Size2::new(16.0, 16.0)),
+                Color::<f32>::rgba(0.0, 0.0, 1.0, 1.0),
+                shader_config.with_depth_stencil(DepthStencilConfig {
+                    depth_compare: CompareFunction::Greater,
+                    ..Default::default()
+                }),
+            ),
+        ];
+
+        let image = scene.render(&render_target, 0x408080FFu32, |draw_command| {
+            assert!(draw_command.has_depth_stencil());
+
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+
+            for (rectangle, color, shader_config) in draws {
+                fill(&mut draw_batcher, rectangle, colored(color, shader_config));
+            }
+
+            draw_batcher.flush().unwrap();
+        });
+
+        check_golden(&image, "depth_stencil");
+    }
+}
@@ -1,20 +1,37 @@
+mod blend_mode;
+pub use blend_mode::BlendMode;
+
+mod depth_stencil_config;
+pub use depth_stencil_config::DepthStencilConfig;
+
 mod shader_config;
 pub use shader_config::ShaderConfig;
 
+mod sort_key;
+pub use sort_key::SortKey;
+
 mod texture_config;
 pub use texture_config::*;
 
 pub use wgpu::{
+    BlendComponent,
+    BlendFactor,
+    BlendOperation,
+    BlendState,
     Face,
     FrontFace,
     IndexFormat,
     PolygonMode,
     PrimitiveState,
     PrimitiveTopology,
+    StencilFaceState,
+    StencilOperation,
+    StencilState,
 };
 
 use super::Vertex;
 
+/// How a graphic is drawn, build it from `..Default::default()` so fields added later don't break it.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct DrawConfig<V: Vertex> {
@@ -23,9 +40,15 @@ pub struct DrawConfig<V: Vertex> {
     pub vertex: V,
     pub shader_config: Option<ShaderConfig>,
     pub texture_config: Option<TextureConfig>,
+    pub sort_key: SortKey,
 }
 
 impl<V: Vertex> DrawConfig<V> {
+    pub fn with_sort_key(mut self, sort_key: SortKey) -> Self {
+        self.sort_key = sort_key;
+        self
+    }
+
     /// Apply changes to shader configuration in place.
     pub fn apply_shader_changes<F>(mut self, changes_fn: F) -> Self where
         F: FnOnce(ShaderConfig) -> ShaderConfig
@@ -10,11 +10,16 @@ pub use wgpu::{
 
 use crate::rendering::shaders::{Shader, ShaderInfo};
 
+use super::{BlendMode, DepthStencilConfig};
+
+/// Everything a pipeline is created from, draws only share a batch when it's the same.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct ShaderConfig {
     shader: Shader,
     primitive: PrimitiveState,
+    blend_mode: BlendMode,
+    depth_stencil: DepthStencilConfig,
 }
 
 impl ShaderConfig {
@@ -22,9 +27,21 @@ impl ShaderConfig {
         Self {
             shader: shader.identifier(),
             primitive,
+            blend_mode: BlendMode::default(),
+            depth_stencil: DepthStencilConfig::default(),
         }
     }
 
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn with_depth_stencil(mut self, depth_stencil: DepthStencilConfig) -> Self {
+        self.depth_stencil = depth_stencil;
+        self
+    }
+
     pub(crate) fn shader(&self) -> &Shader {
         &self.shader
     }
@@ -36,4 +53,27 @@ impl ShaderConfig {
     pub fn mut_primitive_state(&mut self) -> &mut PrimitiveState {
         &mut self.primitive
     }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn depth_stencil(&self) -> &DepthStencilConfig {
+        &self.depth_stencil
+    }
+
+    pub fn mut_depth_stencil(&mut self) -> &mut DepthStencilConfig {
+        &mut self.depth_stencil
+    }
+
+    /// Configuration without what's set while drawing, pipelines are shared by it.
+    pub(in crate::rendering) fn pipeline_key(&self) -> Self {
+        let mut config = *self;
+        config.depth_stencil.stencil_reference = 0;
+        config
+    }
 }
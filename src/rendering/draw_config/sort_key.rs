@@ -0,0 +1,23 @@
+/// Where a draw goes when a `DrawBatcher` reorders it, see `BatchMode`.
+///
+/// [`DrawBatcher`]: crate::rendering::batchers::DrawBatcher
+/// [`BatchMode`]: crate::rendering::batchers::BatchMode
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SortKey {
+    /// Greater values are drawn on top.
+    pub z: f32,
+
+    /// Used by `BatchMode::YSort`, greater values are drawn on top.
+    ///
+    /// [`BatchMode::YSort`]: crate::rendering::batchers::BatchMode::YSort
+    pub y: f32,
+}
+
+impl SortKey {
+    pub const fn new(z: f32, y: f32) -> Self {
+        Self {
+            z,
+            y,
+        }
+    }
+}
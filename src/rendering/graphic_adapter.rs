@@ -3,13 +3,23 @@ use crate::window::Window;
 use super::{
     backend::{
         DrawCommand,
+        MsaaBuffer,
         RenderBackend,
         RenderBackendBuilder, DrawError,
+        PresentMode,
     },
+    capture::{self, CaptureError},
     shaders::builder::ShaderBuilder,
     GraphicAdapterInitError,
+    RenderTarget,
 };
 
+use std::path::Path;
+
+use image::RgbaImage;
+
+use crate::math::Size2;
+
 pub type Result<T> = std::result::Result<T, super::GraphicAdapterInitError>;
 
 pub struct GraphicAdapter {
@@ -22,7 +32,11 @@ impl GraphicAdapter {
     }
 
     pub fn with_surface_size(window: &Window, size: (u32, u32)) -> Result<Self> {
-        let backend = RenderBackendBuilder::new(window, size)
+        Self::from_builder(RenderBackendBuilder::new(window, size))
+    }
+
+    pub fn from_builder(builder: RenderBackendBuilder) -> Result<Self> {
+        let backend = builder
             .build()
             .map_err(GraphicAdapterInitError::BackendFailed)?;
 
@@ -31,10 +45,73 @@ impl GraphicAdapter {
         })
     }
 
+    /// Without window and presentation surface, using a software adapter.
+    /// It can only draw into a `RenderTarget`.
+    pub fn headless() -> Result<Self> {
+        Self::from_builder(RenderBackendBuilder::headless().with_fallback_adapter(true))
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.backend.presentation_surface.is_none()
+    }
+
     pub fn request_resize_surface(&mut self, width: u32, height: u32) {
+        if let Some(ref mut surface) = self.backend.presentation_surface {
+            surface.request_reconfigure_swapchain_with(width, height);
+        }
+    }
+
+    /// Presentation surface size, in pixels, it's `None` when headless.
+    pub fn surface_size(&self) -> Option<Size2<u32>> {
+        self.backend
+            .presentation_surface
+            .as_ref()
+            .map(|surface| Size2::new(surface.width(), surface.height()))
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        self.backend
+            .presentation_surface
+            .as_ref()
+            .map(|surface| surface.present_mode())
+            .unwrap_or_default()
+    }
+
+    /// Switch present mode at runtime, falling back to a supported one.
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        if let Some(ref mut surface) = self.backend.presentation_surface {
+            surface.set_present_mode(present_mode);
+        }
+    }
+
+    /// MSAA sample count in use by presentation surface.
+    pub fn sample_count(&self) -> u32 {
+        self.backend
+            .presentation_surface
+            .as_ref()
+            .map_or(1, |surface| surface.active_sample_count())
+    }
+
+    /// Changes presentation surface's MSAA sample count, falling back to a supported one.
+    pub fn set_sample_count(&mut self, sample_count: u32) {
+        if let Some(ref mut surface) = self.backend.presentation_surface {
+            surface.set_sample_count(sample_count);
+        }
+    }
+
+    pub fn has_depth_stencil(&self) -> bool {
         self.backend
             .presentation_surface
-            .request_reconfigure_swapchain_with(width, height);
+            .as_ref()
+            .map(|surface| surface.has_depth_stencil())
+            .unwrap_or_default()
+    }
+
+    /// Adds or removes presentation surface's depth stencil buffer.
+    pub fn set_depth_stencil(&mut self, depth_stencil: bool) {
+        if let Some(ref mut surface) = self.backend.presentation_surface {
+            surface.set_depth_stencil(depth_stencil);
+        }
     }
 
     pub fn shader_builder(&mut self) -> &mut ShaderBuilder {
@@ -42,14 +119,81 @@ impl GraphicAdapter {
     }
 
     pub fn prepare_draw(&mut self) -> std::result::Result<DrawCommand, DrawError> {
+        let error_checks = self.backend.error_checks();
+        let presentation_surface = self.backend
+                                       .presentation_surface
+                                       .as_mut()
+                                       .ok_or(DrawError::NoPresentationSurface)?;
+
         DrawCommand::new(
             &self.backend.device,
             &self.backend.queue,
-            &mut self.backend.presentation_surface,
+            presentation_surface,
             &mut self.backend.shader_builder,
+            &mut self.backend.draw_buffers,
+            error_checks,
+        )
+    }
+
+    /// Draws into a render target, instead of presentation surface.
+    pub fn prepare_draw_to(&mut self, render_target: &RenderTarget) -> DrawCommand<'_> {
+        let error_checks = self.backend.error_checks();
+
+        DrawCommand::with_target(
+            &self.backend.device,
+            &self.backend.queue,
+            render_target,
+            &mut self.backend.shader_builder,
+            &mut self.backend.draw_buffers,
+            error_checks,
+        )
+    }
+
+    /// Creates a render target with surface's format, so every shader pipeline can draw into it.
+    pub fn create_render_target(&self, size: Size2<u32>) -> RenderTarget {
+        self.create_render_target_with(size, 1, false)
+    }
+
+    /// Creates a render target drawn with MSAA, falling back to a supported sample count,
+    /// and a depth stencil buffer when requested.
+    pub fn create_render_target_with(&self, size: Size2<u32>, sample_count: u32, depth_stencil: bool) -> RenderTarget {
+        let sample_count = MsaaBuffer::resolve_sample_count(
+            self.backend.adapter(),
+            &self.backend.device,
+            self.backend.target_format(),
+            sample_count,
+        );
+
+        RenderTarget::new(
+            &self.backend.device,
+            self.backend.target_format(),
+            size,
+            sample_count,
+            depth_stencil,
         )
     }
 
+    /// Reads render target contents back from GPU.
+    ///
+    /// Presentation surface can't be captured, since it isn't created to be copied from.
+    /// To read a rendered frame, draw it into a `RenderTarget`, such as `RenderSystem::with_render_target`.
+    ///
+    /// [`RenderSystem::with_render_target`]: crate::systems::render_system::RenderSystem::with_render_target
+    pub fn capture(&self, render_target: &RenderTarget) -> std::result::Result<RgbaImage, CaptureError> {
+        capture::read_render_target(&self.backend.device, &self.backend.queue, render_target)
+    }
+
+    /// Reads render target contents back from GPU, saving it as a PNG.
+    pub fn capture_to_png<P: AsRef<Path>>(
+        &self,
+        render_target: &RenderTarget,
+        path: P,
+    ) -> std::result::Result<(), CaptureError> {
+        self.capture(render_target)?
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(CaptureError::from)
+    }
+
     pub(super) fn backend(&self) -> &RenderBackend {
         &self.backend
     }
@@ -58,3 +202,14 @@ impl GraphicAdapter {
         &mut self.backend
     }
 }
+
+#[cfg(test)]
+impl GraphicAdapter {
+    /// `headless` adapter ready to be shared, tests which need it fail when there is none available.
+    pub(crate) fn software() -> std::rc::Rc<std::cell::RefCell<Self>> {
+        match Self::headless() {
+            Ok(graphic_adapter) => std::rc::Rc::new(std::cell::RefCell::new(graphic_adapter)),
+            Err(e) => panic!("software adapter is unavailable: {}", e),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::any::Any;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        atlas::SpriteRegion,
+        VertexPosition,
+        VertexTexture2D,
+    },
+};
+
+use super::{
+    DrawConfig,
+    Graphic,
+    GraphicDrawError,
+    Image,
+    RenderState,
+};
+
+/// Flipbook graphic, it draws whichever frame an `Animator` is at.
+///
+/// [`Animator`]: crate::components::Animator
+pub struct AnimatedSprite<V: VertexPosition<Position = Vector2<f32>>> {
+    frame: Option<Image<V>>,
+}
+
+impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> Default for AnimatedSprite<V> {
+    fn default() -> Self {
+        Self {
+            frame: None,
+        }
+    }
+}
+
+impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> AnimatedSprite<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_frame(mut self, region: SpriteRegion) -> Self {
+        self.set_frame(region);
+        self
+    }
+
+    pub fn frame(&self) -> Option<&SpriteRegion> {
+        self.frame
+            .as_ref()
+            .map(|image| image.region())
+    }
+
+    pub fn set_frame(&mut self, region: SpriteRegion) {
+        self.frame = Some(Image::from_region(region));
+    }
+}
+
+impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> Graphic<V> for AnimatedSprite<V> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn draw<'d>(
+        &'d self,
+        state: &'d mut dyn RenderState<V>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), GraphicDrawError> {
+        match self.frame {
+            Some(ref image) => image.draw(state, draw_config),
+            None => Ok(()),
+        }
+    }
+}
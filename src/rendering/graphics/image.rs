@@ -4,12 +4,13 @@ use std::{
 };
 
 use crate::{
-    math::{Vector2, Rectangle, Size2},
+    math::{Vector2, Rectangle},
     rendering::{
+        atlas::SpriteRegion,
         VertexPosition,
         VertexTexture2D,
     },
-    util::Size, resources::{Asset, AssetWeak},
+    util::Size, resources::Asset,
 };
 
 use super::{
@@ -18,25 +19,47 @@ use super::{
     GraphicDrawError,
     RenderState,
     Texture,
+    QUAD_INDICES,
 };
 
+/// Draws a region of a texture, which is the whole texture by default.
 pub struct Image<V: VertexPosition<Position = Vector2<f32>>> {
-    texture: AssetWeak<Texture>,
+    region: SpriteRegion,
     phantom: PhantomData<V>,
-    clip_region: Rectangle<i32>,
 }
 
 impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> Image<V> {
     pub fn new(texture: &Asset<Texture>) -> Self {
+        Self::from_region(SpriteRegion::full(texture))
+    }
+
+    /// Such as a region from a `TextureAtlas`.
+    ///
+    /// [`TextureAtlas`]: crate::rendering::atlas::TextureAtlas
+    pub fn from_region(region: SpriteRegion) -> Self {
         Self {
-            texture: texture.weak(),
+            region,
             phantom: Default::default(),
-            clip_region: Rectangle::new(
-                Vector2::zero(),
-                Size2::new(texture.get().width() as i32, texture.get().height() as i32),
-            ),
         }
     }
+
+    /// Only draws provided rect, in pixels, of the texture.
+    pub fn with_clip_region(mut self, clip_region: Rectangle<u32>) -> Self {
+        self.set_clip_region(clip_region);
+        self
+    }
+
+    pub fn clip_region(&self) -> Rectangle<u32> {
+        self.region.rect()
+    }
+
+    pub fn set_clip_region(&mut self, clip_region: Rectangle<u32>) {
+        self.region = self.region.clone().with_rect(clip_region);
+    }
+
+    pub fn region(&self) -> &SpriteRegion {
+        &self.region
+    }
 }
 
 impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> Graphic<V> for Image<V> {
@@ -53,21 +76,19 @@ impl<V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D> Graphic<V> fo
         state: &'d mut dyn RenderState<V>,
         draw_config: DrawConfig<V>,
     ) -> Result<(), GraphicDrawError> {
-        let size: Size<f32>
-            = Size::with(self.clip_region.size.width, self.clip_region.size.height).unwrap();
+        let region_size = self.region.size();
+        let size: Size<f32> = Size::with(region_size.width, region_size.height).unwrap();
+        let uv = self.region.uv();
 
         //println!("image size: {}", size);
         let vertices = vec![
-            V::from_position(Vector2::new(0.0, 0.0)).with_uv(Vector2::new(0.0, 0.0)),
-            V::from_position(Vector2::new(size.width, 0.0)).with_uv(Vector2::new(1.0, 0.0)),
-            V::from_position(Vector2::new(0.0, size.height)).with_uv(Vector2::new(0.0, 1.0)),
-
-            V::from_position(Vector2::new(0.0, size.height)).with_uv(Vector2::new(0.0, 1.0)),
-            V::from_position(Vector2::new(size.width, 0.0)).with_uv(Vector2::new(1.0, 0.0)),
-            V::from_position(Vector2::new(size.width, size.height)).with_uv(Vector2::new(1.0, 1.0)),
+            V::from_position(Vector2::new(0.0, 0.0)).with_uv(uv.top_left()),
+            V::from_position(Vector2::new(size.width, 0.0)).with_uv(uv.top_right()),
+            V::from_position(Vector2::new(0.0, size.height)).with_uv(uv.bottom_left()),
+            V::from_position(Vector2::new(size.width, size.height)).with_uv(uv.bottom_right()),
         ];
 
-        state.extend(vertices.iter(), Some(self.texture.clone()), draw_config)
+        state.extend_indexed(vertices.iter(), QUAD_INDICES.iter(), Some(self.region.texture().clone()), draw_config)
              .map_err(GraphicDrawError::from)?;
 
         Ok(())
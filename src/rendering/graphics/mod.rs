@@ -1,5 +1,8 @@
 mod triangle;
 
+mod animated_sprite;
+pub use animated_sprite::AnimatedSprite;
+
 mod grid;
 pub use grid::Grid;
 
@@ -26,6 +29,10 @@ use super::{
     Vertex,
 };
 
+/// Two triangles for a quad with vertices ordered as:
+/// top left, top right, bottom left and bottom right.
+pub(crate) const QUAD_INDICES: [u32; 6] = [0, 1, 2, 2, 1, 3];
+
 pub trait Graphic<V> : Any where
     V: Vertex
 {
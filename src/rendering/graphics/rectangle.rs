@@ -10,6 +10,7 @@ use super::{
     Graphic,
     GraphicDrawError,
     RenderState,
+    QUAD_INDICES,
 };
 
 impl<D, V> Graphic<V> for Rectangle<D> where
@@ -29,16 +30,14 @@ impl<D, V> Graphic<V> for Rectangle<D> where
         state: &'d mut dyn RenderState<V>,
         draw_config: DrawConfig<V>,
     ) -> Result<(), GraphicDrawError> {
-        state.extend(
+        state.extend_indexed(
             vec!(
                 V::from_position(self.position),
                 V::from_position(self.position + Vector2::new(self.size.width, D::zero())),
                 V::from_position(self.position + Vector2::new(D::zero(), self.size.height)),
-
-                V::from_position(self.position + Vector2::new(D::zero(), self.size.height)),
-                V::from_position(self.position + Vector2::new(self.size.width, D::zero())),
                 V::from_position(self.position + Vector2::new(self.size.width, self.size.height)),
             ).iter(),
+            QUAD_INDICES.iter(),
             None,
             draw_config,
         ).map_err(GraphicDrawError::from)
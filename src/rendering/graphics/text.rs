@@ -14,6 +14,7 @@ use super::{
     Graphic,
     GraphicDrawError,
     RenderState,
+    QUAD_INDICES,
 };
 
 pub struct Text<R, V> where
@@ -102,7 +103,8 @@ impl<R, V> Graphic<V> for Text<R, V> where
         }
 
         if let Some(ref render_data) = self.render_data {
-            let mut vertices = Vec::with_capacity(render_data.len() * 3 * 2); // 3 vertices by 2 tri
+            let mut vertices = Vec::with_capacity(render_data.len() * 4);
+            let mut indices = Vec::with_capacity(render_data.len() * QUAD_INDICES.len());
 
             let font_size_ratio = self.font.size() / self.font.rendering.nominal_width();
 
@@ -117,34 +119,26 @@ impl<R, V> Graphic<V> for Text<R, V> where
 
                 let pos = (render_glyph.position * self.font.size() as f64).convert();
 
+                let base = vertices.len() as u32;
+                indices.extend(QUAD_INDICES.iter().map(|i| base + i));
+
                 vertices.extend_from_slice(&[
                     /*
                        0---1
-                       |  /
-                       | /
-                       |/
-                       2
+                       |  /|
+                       | / |
+                       |/  |
+                       2---3
                     */
 
                     V::from_position(pos).with_uv(uv.top_left()),
                     V::from_position(pos + Vector2::new(quad_size.x, 0.0)).with_uv(uv.top_right()),
                     V::from_position(pos + Vector2::new(0.0, quad_size.y)).with_uv(uv.bottom_left()),
-
-                    /*
-                           4
-                          /|
-                         / |
-                        /  |
-                       3---5
-                    */
-
-                    V::from_position(pos + Vector2::new(0.0, quad_size.y)).with_uv(uv.bottom_left()),
-                    V::from_position(pos + Vector2::new(quad_size.x, 0.0)).with_uv(uv.top_right()),
                     V::from_position(pos + Vector2::new(quad_size.x, quad_size.y)).with_uv(uv.bottom_right()),
                 ]);
             }
 
-            state.extend(vertices.iter(), self.font.rendering.texture(), draw_config)
+            state.extend_indexed(vertices.iter(), indices.iter(), self.font.rendering.texture(), draw_config)
                  .map_err(GraphicDrawError::from)?;
         }
 
@@ -15,6 +15,7 @@ use super::{
     GraphicDrawError,
     RenderState,
     Texture,
+    QUAD_INDICES,
 };
 
 pub struct Tilemap<V: VertexPosition<Position = Vector2<f32>>> {
@@ -64,7 +65,9 @@ impl<V> Graphic<V> for Tilemap<V> where
         &'d self, state: &'d mut dyn RenderState<V>,
         draw_config: DrawConfig<V>,
     ) -> Result<(), GraphicDrawError>{
-        let mut vertices = Vec::with_capacity(((self.columns * 2) * (self.rows * 2)) as usize);
+        let tile_count = (self.columns * self.rows) as usize;
+        let mut vertices = Vec::with_capacity(tile_count * 4);
+        let mut indices = Vec::with_capacity(tile_count * QUAD_INDICES.len());
         let tile_size: Size2<f32> = Size2::with(self.tile_size.width, self.tile_size.height).unwrap();
 
         let tileset_columns = self.tileset_size.width / self.tile_size.width;
@@ -90,19 +93,19 @@ impl<V> Graphic<V> for Tilemap<V> where
                     (g_y as f32) * tile_size.height / (self.tileset_size.height as f32)
                 );
 
+                let base = vertices.len() as u32;
+                indices.extend(QUAD_INDICES.iter().map(|i| base + i));
+
                 vertices.extend(&[
                     V::from_position(Vector2::new(x, y)).with_uv(uv),
                     V::from_position(Vector2::new(x + tile_size.width, y)).with_uv(uv + Vector2::new(tileset_tile_uv_size.width, 0.0)),
                     V::from_position(Vector2::new(x, y + tile_size.height)).with_uv(uv + Vector2::new(0.0, tileset_tile_uv_size.height)),
-
-                    V::from_position(Vector2::new(x, y + tile_size.height)).with_uv(uv + Vector2::new(0.0, tileset_tile_uv_size.height)),
-                    V::from_position(Vector2::new(x + tile_size.width, y)).with_uv(uv + Vector2::new(tileset_tile_uv_size.width, 0.0)),
                     V::from_position(Vector2::new(x + tile_size.width, y + tile_size.height)).with_uv(uv + Vector2::<_>::from(tileset_tile_uv_size)),
                 ])
             });
         });
 
-        state.extend(vertices.iter(), Some(self.tileset.clone()), draw_config)
+        state.extend_indexed(vertices.iter(), indices.iter(), Some(self.tileset.clone()), draw_config)
              .map_err(GraphicDrawError::from)?;
 
         Ok(())
@@ -1,7 +1,10 @@
+pub mod atlas;
 pub mod backend;
 pub mod batchers;
+pub mod capture;
 pub mod fonts;
 pub mod graphics;
+pub mod post_process;
 pub mod shaders;
 
 mod graphic_adapter;
@@ -16,8 +19,18 @@ pub use draw_config::*;
 mod color;
 pub use color::*;
 
+mod render_layers;
+pub use render_layers::RenderLayers;
+
+mod render_target;
+pub use render_target::RenderTarget;
+
 mod render_state;
 pub use render_state::RenderState;
+pub(crate) use render_state::check_indices;
+
+mod transformed_render_state;
+pub use transformed_render_state::TransformedRenderState;
 
 mod render_state_error;
 pub use render_state_error::RenderStateError;
@@ -0,0 +1,43 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::math::{Size2, Vector2};
+
+use super::{Effect, EffectUniforms};
+
+/// Bright colors glow into their surroundings, such as lights and magic effects.
+pub type Bloom = Effect<BloomUniforms>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct BloomUniforms {
+    /// Size of a source pixel at uv space.
+    texel_size: Vector2<f32>,
+
+    /// Brightness, at any channel, above which colors glow.
+    pub threshold: f32,
+
+    /// How much glow is added to source.
+    pub intensity: f32,
+
+    /// Spread of glow, in pixels between blur samples.
+    pub radius: f32,
+}
+
+impl Default for BloomUniforms {
+    fn default() -> Self {
+        Self {
+            texel_size: Vector2::one(),
+            threshold: 0.7,
+            intensity: 1.0,
+            radius: 2.0,
+        }
+    }
+}
+
+impl EffectUniforms for BloomUniforms {
+    const FRAGMENT: &'static str = include_str!("res/bloom.wgsl");
+
+    fn resize(&mut self, size: Size2<u32>) {
+        self.texel_size = Vector2::new(1.0 / size.width as f32, 1.0 / size.height as f32);
+    }
+}
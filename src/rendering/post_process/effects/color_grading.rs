@@ -0,0 +1,77 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    math::Size2,
+    rendering::{
+        FilterMode,
+        Texture,
+        TextureConfig,
+        TextureFormat,
+    },
+};
+
+use super::{Effect, EffectUniforms};
+
+/// Maps every color through a lookup table, such as one exported by an image editor.
+///
+/// Lookup table is a strip of `size` slices along blue, each one with red along x and green along y.
+/// So it's `size * size` wide and `size` tall.
+pub type ColorGrading = Effect<ColorGradingUniforms>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct ColorGradingUniforms {
+    /// Lookup table entries along each channel, set with it.
+    lut_size: f32,
+
+    /// How much graded color replaces source, in [0, 1].
+    pub intensity: f32,
+}
+
+impl Default for ColorGradingUniforms {
+    fn default() -> Self {
+        Self {
+            lut_size: ColorGrading::DEFAULT_LUT_SIZE as f32,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl EffectUniforms for ColorGradingUniforms {
+    const FRAGMENT: &'static str = include_str!("res/color_grading.wgsl");
+
+    fn textures() -> Vec<(Texture, TextureConfig)> {
+        let mut config = TextureConfig::default();
+        config.sampler.mag_filter = FilterMode::Linear;
+        config.sampler.min_filter = FilterMode::Linear;
+
+        vec![(ColorGrading::identity_lut(ColorGrading::DEFAULT_LUT_SIZE), config)]
+    }
+}
+
+impl ColorGrading {
+    pub const DEFAULT_LUT_SIZE: u32 = 16;
+
+    /// Lookup table which leaves every color as is, a starting point to edit one.
+    pub fn identity_lut(size: u32) -> Texture {
+        let max = (size.max(2) - 1) as f32;
+        let channel = |i: u32| (i as f32 / max * 255.0).round() as u8;
+
+        let data: Vec<u8> = (0..size)
+            .flat_map(|green| (0..size).flat_map(move |blue| (0..size).map(move |red| (red, green, blue))))
+            .flat_map(|(red, green, blue)| [channel(red), channel(green), channel(blue), 255])
+            .collect();
+
+        Texture::new(TextureFormat::Rgba8Unorm, Size2::new(size * size, size), &data)
+    }
+
+    pub fn lut(&self) -> &Texture {
+        &self.textures[0].0
+    }
+
+    /// Replaces lookup table, it's size is taken from it's height.
+    pub fn set_lut(&mut self, lut: Texture) {
+        self.uniforms.lut_size = lut.height() as f32;
+        self.textures[0].0 = lut;
+    }
+}
@@ -0,0 +1,43 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::math::{Size2, Vector2};
+
+use super::{Effect, EffectUniforms};
+
+/// Darkened horizontal lines over a slightly curved screen, as an old CRT monitor.
+pub type CrtScanlines = Effect<CrtScanlinesUniforms>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct CrtScanlinesUniforms {
+    /// Output size, in pixels.
+    resolution: Vector2<f32>,
+
+    /// How much darker scanlines are, in [0, 1].
+    pub intensity: f32,
+
+    /// In pixels, it's also the gap between scanlines.
+    pub line_height: f32,
+
+    /// Barrel distortion of screen, 0 keeps it flat.
+    pub curvature: f32,
+}
+
+impl Default for CrtScanlinesUniforms {
+    fn default() -> Self {
+        Self {
+            resolution: Vector2::one(),
+            intensity: 0.3,
+            line_height: 1.0,
+            curvature: 0.05,
+        }
+    }
+}
+
+impl EffectUniforms for CrtScanlinesUniforms {
+    const FRAGMENT: &'static str = include_str!("res/crt_scanlines.wgsl");
+
+    fn resize(&mut self, size: Size2<u32>) {
+        self.resolution = Vector2::from(size.convert::<f32>());
+    }
+}
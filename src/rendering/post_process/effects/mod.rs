@@ -0,0 +1,213 @@
+//! Built-in `PostProcessEffect`s.
+//!
+//! [`PostProcessEffect`]: super::PostProcessEffect
+
+mod bloom;
+pub use bloom::*;
+
+mod color_grading;
+pub use color_grading::*;
+
+mod crt_scanlines;
+pub use crt_scanlines::*;
+
+mod pixelate;
+pub use pixelate::*;
+
+mod vignette;
+pub use vignette::*;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+
+use bytemuck::Pod;
+use miette::IntoDiagnostic;
+
+use crate::{
+    math::Size2,
+    rendering::{
+        post_process::PostProcessEffect,
+        shaders::{
+            AttributeFormat,
+            Bindings,
+            BindingsDescriptorEntry,
+            BindingsError,
+            Shader,
+            ShaderDescriptor,
+            ShaderFormat,
+            ShaderInfo,
+            ShaderInstance,
+            ShaderStageKind,
+            VertexAttribute,
+            WorldViewProjectionUniforms,
+        },
+        BlendMode,
+        FrontFace,
+        GraphicAdapter,
+        PolygonMode,
+        PrimitiveState,
+        PrimitiveTopology,
+        SamplerBindingType,
+        ShaderConfig,
+        Texture,
+        TextureConfig,
+        TextureSampleType,
+        TextureViewDimension,
+    },
+    vertex_attrs,
+};
+
+/// Uniforms of a built-in effect, which also pick it's fragment stage and textures.
+pub trait EffectUniforms : Pod + Default {
+    /// WGSL fragment stage, drawn over a full screen quad.
+    const FRAGMENT: &'static str;
+
+    /// Called by `PostProcessStack` before every pass, with it's output size in pixels.
+    ///
+    /// [`PostProcessStack`]: super::PostProcessStack
+    fn resize(&mut self, _size: Size2<u32>) {
+    }
+
+    /// Textures bound after the source one, such as a lookup table.
+    fn textures() -> Vec<(Texture, TextureConfig)> {
+        Vec::new()
+    }
+}
+
+/// A full screen pass of a built-in effect, every one only differs by it's uniforms.
+pub struct Effect<U: EffectUniforms> {
+    shader: Shader,
+    uniforms: U,
+    config: ShaderConfig,
+    enabled: bool,
+    textures: Vec<(Texture, TextureConfig)>,
+}
+
+impl<U: EffectUniforms + 'static> Effect<U> {
+    pub fn new(graphic_adapter: &Rc<RefCell<GraphicAdapter>>) -> Rc<RefCell<Self>> {
+        build(
+            graphic_adapter,
+            U::FRAGMENT,
+            BindingsDescriptorEntry::uniform::<U>(),
+            U::textures().len(),
+        )
+    }
+}
+
+impl<U: EffectUniforms> Effect<U> {
+    pub fn uniforms(&self) -> &U {
+        &self.uniforms
+    }
+
+    pub fn uniforms_mut(&mut self) -> &mut U {
+        &mut self.uniforms
+    }
+}
+
+impl<U: EffectUniforms> PostProcessEffect for Effect<U> {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn shader_config(&self) -> &ShaderConfig {
+        &self.config
+    }
+
+    fn resize(&mut self, size: Size2<u32>) {
+        self.uniforms.resize(size);
+    }
+
+    fn textures(&self) -> Vec<(&Texture, TextureConfig)> {
+        self.textures
+            .iter()
+            .map(|(texture, config)| (texture, *config))
+            .collect()
+    }
+}
+
+impl<U: EffectUniforms> ShaderInstance for Effect<U> {
+    fn new(shader: Shader) -> Self {
+        Self {
+            shader,
+            uniforms: U::default(),
+            config: shader_config(&shader),
+            enabled: true,
+            textures: U::textures(),
+        }
+    }
+
+    fn world_view_projection_uniforms(&self) -> Option<&dyn WorldViewProjectionUniforms> {
+        None
+    }
+
+    fn mut_world_view_projection_uniforms(&mut self) -> Option<&mut dyn WorldViewProjectionUniforms> {
+        None
+    }
+
+    fn bindings<'b>(&'b self, mut bindings: Bindings<'b>) -> Result<Bindings<'b>, BindingsError> {
+        bindings.uniforms(&[self.uniforms])?;
+
+        Ok(bindings)
+    }
+}
+
+impl<U: EffectUniforms> ShaderInfo for Effect<U> {
+    fn identifier(&self) -> Shader {
+        self.shader
+    }
+}
+
+/// Builds an effect from it's fragment stage, which is drawn over a full screen quad.
+/// Source texture and sampler bindings are placed right after effect's uniforms.
+fn build<E: ShaderInstance + 'static>(
+    graphic_adapter: &Rc<RefCell<GraphicAdapter>>,
+    fragment: &str,
+    uniforms: BindingsDescriptorEntry,
+    textures: usize,
+) -> Rc<RefCell<E>> {
+    let bindings = std::iter::once(uniforms).chain(
+        (0..(1 + textures)).flat_map(|_| [
+            BindingsDescriptorEntry::texture(TextureSampleType::Float { filterable: true }, false, TextureViewDimension::D2),
+            BindingsDescriptorEntry::sampler(SamplerBindingType::Filtering),
+        ])
+    );
+
+    graphic_adapter
+        .borrow_mut()
+        .shader_builder()
+        .create(
+            ShaderDescriptor::default()
+                .with_stage(ShaderStageKind::Vertex,    ShaderFormat::WGSL, include_str!("res/fullscreen.wgsl"))
+                .with_stage(ShaderStageKind::Fragment,  ShaderFormat::WGSL, fragment)
+        )
+        .set_vertex_attributes(vertex_attrs![
+            Float32x2,
+            Float32x2,
+        ].into_iter())
+        .bindings(bindings)
+        .build()
+        .into_diagnostic()
+        .unwrap()
+}
+
+/// Output is replaced, since effect already takes source into account.
+fn shader_config(shader: &Shader) -> ShaderConfig {
+    ShaderConfig::new(
+        shader,
+        PrimitiveState {
+            topology: PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: FrontFace::Cw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: PolygonMode::Fill,
+            conservative: false,
+        }
+    ).with_blend_mode(BlendMode::Replace)
+}
@@ -0,0 +1,35 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::math::{Size2, Vector2};
+
+use super::{Effect, EffectUniforms};
+
+/// Lowers resolution, drawing cells of `pixel_size` with a single color.
+pub type Pixelate = Effect<PixelateUniforms>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct PixelateUniforms {
+    /// Output size, in pixels.
+    resolution: Vector2<f32>,
+
+    /// Size, in pixels, of each cell drawn with a single color.
+    pub pixel_size: f32,
+}
+
+impl Default for PixelateUniforms {
+    fn default() -> Self {
+        Self {
+            resolution: Vector2::one(),
+            pixel_size: 4.0,
+        }
+    }
+}
+
+impl EffectUniforms for PixelateUniforms {
+    const FRAGMENT: &'static str = include_str!("res/pixelate.wgsl");
+
+    fn resize(&mut self, size: Size2<u32>) {
+        self.resolution = Vector2::from(size.convert::<f32>());
+    }
+}
@@ -0,0 +1,43 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Globals {
+    texel_size: vec2<f32>,
+    threshold: f32,
+    intensity: f32,
+    radius: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var tex: texture_2d<f32>;
+@group(0) @binding(2) var samp: sampler;
+
+// part of color above threshold
+fn bright(color: vec3<f32>) -> vec3<f32> {
+    let brightness = max(max(color.r, color.g), color.b);
+    return color * (max(brightness - globals.threshold, 0.0) / max(brightness, 0.0001));
+}
+
+@fragment
+fn main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    let source = textureSample(tex, samp, vertex.uv);
+
+    var glow = vec3<f32>(0.0, 0.0, 0.0);
+    var total = 0.0;
+
+    // gaussian blur of bright parts, 5x5 taps spread by radius
+    for (var x: i32 = -2; x <= 2; x++) {
+        for (var y: i32 = -2; y <= 2; y++) {
+            let offset = vec2<f32>(f32(x), f32(y));
+            let weight = exp(-dot(offset, offset) / 4.0);
+            let uv = vertex.uv + offset * globals.texel_size * globals.radius;
+
+            glow += bright(textureSample(tex, samp, uv).rgb) * weight;
+            total += weight;
+        }
+    }
+
+    return vec4<f32>(source.rgb + glow / total * globals.intensity, source.a);
+}
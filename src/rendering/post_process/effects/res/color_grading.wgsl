@@ -0,0 +1,41 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Globals {
+    lut_size: f32,
+    intensity: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var tex: texture_2d<f32>;
+@group(0) @binding(2) var samp: sampler;
+@group(0) @binding(3) var lut: texture_2d<f32>;
+@group(0) @binding(4) var lut_samp: sampler;
+
+// lut is a strip of `lut_size` slices along blue, each with red along x and green along y
+fn lut_uv(color: vec2<f32>, slice: f32) -> vec2<f32> {
+    let size = globals.lut_size;
+    let texel = (color * (size - 1.0) + 0.5) / size;
+
+    return vec2<f32>((slice + texel.x) / size, texel.y);
+}
+
+@fragment
+fn main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    let source = textureSample(tex, samp, vertex.uv);
+    let color = clamp(source.rgb, vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 1.0));
+
+    let blue = color.b * (globals.lut_size - 1.0);
+    let slice = floor(blue);
+    let next_slice = min(slice + 1.0, globals.lut_size - 1.0);
+
+    let graded = mix(
+        textureSample(lut, lut_samp, lut_uv(color.rg, slice)).rgb,
+        textureSample(lut, lut_samp, lut_uv(color.rg, next_slice)).rgb,
+        blue - slice,
+    );
+
+    return vec4<f32>(mix(source.rgb, graded, globals.intensity), source.a);
+}
@@ -0,0 +1,32 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Globals {
+    resolution: vec2<f32>,
+    intensity: f32,
+    line_height: f32,
+    curvature: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var tex: texture_2d<f32>;
+@group(0) @binding(2) var samp: sampler;
+
+@fragment
+fn main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    // barrel distortion, stronger away from center
+    let centered = vertex.uv * 2.0 - 1.0;
+    let uv = centered * (1.0 + globals.curvature * dot(centered, centered)) * 0.5 + 0.5;
+
+    let color = textureSample(tex, samp, uv);
+
+    // every other band of `line_height` pixels is darkened
+    let band = fract(uv.y * globals.resolution.y / (globals.line_height * 2.0));
+    let shade = select(1.0, 1.0 - globals.intensity, band >= 0.5);
+
+    let inside = all(uv >= vec2<f32>(0.0, 0.0)) && all(uv <= vec2<f32>(1.0, 1.0));
+
+    return vec4<f32>(color.rgb * shade * f32(inside), color.a);
+}
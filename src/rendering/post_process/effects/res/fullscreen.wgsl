@@ -0,0 +1,16 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// positions are already at clip space
+@vertex
+fn main(
+    @location(0) pos: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+) -> VertexOutput {
+    var result: VertexOutput;
+    result.position = vec4<f32>(pos, 0.0, 1.0);
+    result.uv = uv;
+    return result;
+}
@@ -0,0 +1,22 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Globals {
+    resolution: vec2<f32>,
+    pixel_size: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var tex: texture_2d<f32>;
+@group(0) @binding(2) var samp: sampler;
+
+@fragment
+fn main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    // every pixel of a cell samples at it's center
+    let cell = max(globals.pixel_size, 1.0) / globals.resolution;
+    let uv = (floor(vertex.uv / cell) + 0.5) * cell;
+
+    return textureSample(tex, samp, uv);
+}
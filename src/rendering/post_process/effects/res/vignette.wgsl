@@ -0,0 +1,26 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Globals {
+    color: vec4<f32>,
+    intensity: f32,
+    radius: f32,
+    softness: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var tex: texture_2d<f32>;
+@group(0) @binding(2) var samp: sampler;
+
+@fragment
+fn main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    let source = textureSample(tex, samp, vertex.uv);
+
+    // 0 at center and 1 at corners
+    let distance = length(vertex.uv - 0.5) * sqrt(2.0);
+    let amount = smoothstep(globals.radius, globals.radius + globals.softness, distance) * globals.intensity;
+
+    return vec4<f32>(mix(source.rgb, globals.color.rgb, amount * globals.color.a), source.a);
+}
@@ -0,0 +1,39 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::rendering::Color;
+
+use super::{Effect, EffectUniforms};
+
+/// Fades screen edges.
+pub type Vignette = Effect<VignetteUniforms>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct VignetteUniforms {
+    /// Color which edges fade into, it's alpha scales `intensity`.
+    pub color: Color<f32>,
+
+    /// In [0, 1].
+    pub intensity: f32,
+
+    /// Distance from center, where 1 is at corners, which starts fading.
+    pub radius: f32,
+
+    /// Distance it takes to fully fade.
+    pub softness: f32,
+}
+
+impl Default for VignetteUniforms {
+    fn default() -> Self {
+        Self {
+            color: Color::<f32>::rgba(0.0, 0.0, 0.0, 1.0),
+            intensity: 0.6,
+            radius: 0.5,
+            softness: 0.5,
+        }
+    }
+}
+
+impl EffectUniforms for VignetteUniforms {
+    const FRAGMENT: &'static str = include_str!("res/vignette.wgsl");
+}
@@ -0,0 +1,10 @@
+pub mod effects;
+
+mod post_process_effect;
+pub use post_process_effect::PostProcessEffect;
+
+mod post_process_error;
+pub use post_process_error::PostProcessError;
+
+mod post_process_stack;
+pub use post_process_stack::PostProcessStack;
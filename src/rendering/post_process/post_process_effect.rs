@@ -0,0 +1,32 @@
+use crate::{
+    math::Size2,
+    rendering::{
+        shaders::ShaderInstance,
+        ShaderConfig,
+        Texture,
+        TextureConfig,
+    },
+};
+
+/// A full screen pass run by `PostProcessStack`.
+///
+/// What was drawn before it is bound at it's first texture and sampler bindings,
+/// after `ShaderInstance::bindings` filled it's uniforms.
+///
+/// [`PostProcessStack`]: super::PostProcessStack
+pub trait PostProcessEffect : ShaderInstance {
+    /// Disabled effects are skipped.
+    fn is_enabled(&self) -> bool;
+    fn set_enabled(&mut self, enabled: bool);
+
+    fn shader_config(&self) -> &ShaderConfig;
+
+    /// Called before every pass with it's output size, in pixels, so it follows window resizes.
+    fn resize(&mut self, _size: Size2<u32>) {
+    }
+
+    /// Textures bound after the source one, in order, such as a lookup table.
+    fn textures(&self) -> Vec<(&Texture, TextureConfig)> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,26 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+use crate::rendering::{
+    backend::{DrawError, PassError},
+    shaders::{BindingsError, Shader},
+    RenderStateError,
+};
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum PostProcessError {
+    #[error("draw command failed")]
+    DrawCommand(#[from] DrawError),
+
+    #[error("effect {1:?} -> bindings failed: {0}")]
+    Bindings(BindingsError, Shader),
+
+    #[error("pass submission failed: {0}")]
+    PassSubmit(#[from] PassError),
+
+    #[error("render state failed: {0}")]
+    RenderState(#[from] RenderStateError),
+
+    #[error("scene target wasn't prepared, it's done by PostProcessStack::scene_target")]
+    NoSceneTarget,
+}
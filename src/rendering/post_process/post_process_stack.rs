@@ -0,0 +1,327 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        backend::DrawError,
+        graphics::QUAD_INDICES,
+        DrawConfig,
+        FilterMode,
+        GraphicAdapter,
+        RenderState,
+        RenderTarget,
+        Texture,
+        TextureConfig,
+        Vertex2DTexture,
+    },
+};
+
+use super::{PostProcessEffect, PostProcessError};
+
+/// Full screen quad, at clip space, with uv's top left at source's top left.
+const QUAD: [Vertex2DTexture; 4] = [
+    Vertex2DTexture { position: Vector2::new(-1.0,  1.0), uv: Vector2::new(0.0, 0.0) },
+    Vertex2DTexture { position: Vector2::new( 1.0,  1.0), uv: Vector2::new(1.0, 0.0) },
+    Vertex2DTexture { position: Vector2::new(-1.0, -1.0), uv: Vector2::new(0.0, 1.0) },
+    Vertex2DTexture { position: Vector2::new( 1.0, -1.0), uv: Vector2::new(1.0, 1.0) },
+];
+
+/// Scene is drawn into an intermediate target, then every enabled effect is run over it, in order.
+/// Last one draws into presentation surface, or provided output target.
+///
+/// A frame first draws into `PostProcessStack::scene_target` and finishes with `PostProcessStack::apply`.
+#[derive(Default)]
+pub struct PostProcessStack {
+    effects: Vec<Rc<RefCell<dyn PostProcessEffect>>>,
+    scene: Option<StackTarget>,
+
+    /// Targets effects alternate between, when there is more than one.
+    swap: Vec<StackTarget>,
+}
+
+impl PostProcessStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_effect<E: PostProcessEffect + 'static>(mut self, effect: Rc<RefCell<E>>) -> Self {
+        self.push_effect(effect);
+        self
+    }
+
+    pub fn push_effect<E: PostProcessEffect + 'static>(&mut self, effect: Rc<RefCell<E>>) {
+        self.effects.push(effect);
+    }
+
+    pub fn effects(&self) -> &[Rc<RefCell<dyn PostProcessEffect>>] {
+        &self.effects
+    }
+
+    /// Whether any effect is enabled, otherwise scene should be drawn directly.
+    pub fn is_active(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|e| e.borrow().is_enabled())
+    }
+
+    /// Target scene should be drawn into, with output's size, sample count and depth stencil buffer.
+    /// Output is presentation surface, when `None`.
+    ///
+    /// It's recreated when output changes, such as when window is resized.
+    pub fn scene_target(
+        &mut self,
+        graphic_adapter: &GraphicAdapter,
+        output: Option<&RenderTarget>,
+    ) -> Result<RenderTarget, PostProcessError> {
+        let (size, sample_count, depth_stencil) = match output {
+            Some(output) => (output.size(), output.sample_count(), output.has_depth_stencil()),
+            None => (
+                graphic_adapter.surface_size().ok_or(DrawError::NoPresentationSurface)?,
+                graphic_adapter.sample_count(),
+                graphic_adapter.has_depth_stencil(),
+            ),
+        };
+
+        let outdated = self.scene
+            .as_ref()
+            .is_none_or(|scene| {
+                let target = &scene.render_target;
+
+                target.size() != size
+                    || target.sample_count() != sample_count
+                    || target.has_depth_stencil() != depth_stencil
+            });
+
+        if outdated {
+            let render_target = graphic_adapter.create_render_target_with(size, sample_count, depth_stencil);
+
+            self.scene = Some(StackTarget::new(render_target));
+            self.swap.clear();
+        }
+
+        Ok(self.scene.as_ref().unwrap().render_target.clone())
+    }
+
+    /// Runs every enabled effect over what was drawn into scene target, returning how many were run.
+    pub fn apply(
+        &mut self,
+        graphic_adapter: &mut GraphicAdapter,
+        output: Option<&RenderTarget>,
+    ) -> Result<usize, PostProcessError> {
+        let effects: Vec<_> = self.effects
+            .iter()
+            .filter(|e| e.borrow().is_enabled())
+            .cloned()
+            .collect();
+
+        let size = self.scene
+            .as_ref()
+            .ok_or(PostProcessError::NoSceneTarget)?
+            .render_target
+            .size();
+
+        let swap_count = effects.len().saturating_sub(1).min(2);
+
+        while self.swap.len() < swap_count {
+            self.swap.push(StackTarget::new(graphic_adapter.create_render_target(size)));
+        }
+
+        let source_config = {
+            let mut config = TextureConfig::default();
+            config.sampler.mag_filter = FilterMode::Linear;
+            config.sampler.min_filter = FilterMode::Linear;
+            config
+        };
+
+        for (i, effect) in effects.iter().enumerate() {
+            let source = match i {
+                0 => &self.scene.as_ref().unwrap().texture,
+                _ => &self.swap[(i - 1) % 2].texture,
+            };
+
+            let destination = match i + 1 == effects.len() {
+                true => output,
+                false => Some(&self.swap[i % 2].render_target),
+            };
+
+            let mut draw_command = match destination {
+                Some(render_target) => graphic_adapter.prepare_draw_to(render_target),
+                None => graphic_adapter.prepare_draw()?,
+            };
+
+            effect.borrow_mut().resize(draw_command.target_size());
+
+            let effect = effect.borrow();
+            let shader_id = effect.identifier();
+
+            let texture_views: Vec<_> = {
+                let (device, queue) = draw_command.device_queue();
+
+                std::iter::once(source.view(device, queue, source_config))
+                    .chain(effect.textures().into_iter().map(|(texture, config)| texture.view(device, queue, config)))
+                    .collect()
+            };
+
+            let mut pass = draw_command.begin::<Vertex2DTexture, _, _>(&effect, effect.shader_config(), None)?;
+
+            for texture_view in texture_views {
+                pass.bindings()
+                    .texture_view(texture_view)
+                    .map_err(|e| PostProcessError::Bindings(e, shader_id))?;
+            }
+
+            pass.extend_indexed(QUAD.iter(), QUAD_INDICES.iter(), None, DrawConfig::default())?;
+            pass.submit()?;
+            draw_command.present()?;
+        }
+
+        Ok(effects.len())
+    }
+}
+
+/// A render target with it's texture, so views are cached between frames.
+struct StackTarget {
+    render_target: RenderTarget,
+    texture: Texture,
+}
+
+impl StackTarget {
+    fn new(render_target: RenderTarget) -> Self {
+        Self {
+            texture: render_target.texture(),
+            render_target,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::RgbaImage;
+
+    use crate::{
+        math::{Rectangle, Size2},
+        rendering::{
+            batchers::DrawBatcher,
+            capture::golden::compare,
+            post_process::effects::{Bloom, ColorGrading, CrtScanlines, Pixelate, Vignette},
+            Color,
+        },
+        test_util::{check_golden, colored, fill, TestScene},
+    };
+
+    use super::*;
+
+    /// Draws a white `square` over a dark clear into stack's scene, then applies stack into an output.
+    /// Returns scene and output contents, with how many effects were applied.
+    fn apply_over_square(
+        test_scene: &TestScene,
+        post_process: &mut PostProcessStack,
+        square: Rectangle<f32>,
+    ) -> (RgbaImage, RgbaImage, usize) {
+        let graphic_adapter = &test_scene.graphic_adapter;
+        let output = test_scene.render_target();
+
+        let scene = post_process.scene_target(&graphic_adapter.borrow(), Some(&output)).unwrap();
+        assert_eq!(scene.size(), output.size());
+
+        let scene_image = test_scene.render(&scene, 0x202040FFu32, |draw_command| {
+            let mut draw_batcher = DrawBatcher::new(draw_command);
+            fill(&mut draw_batcher, square, colored(Color::<f32>::rgba(1.0, 1.0, 1.0, 1.0), test_scene.shader_config));
+            draw_batcher.flush().unwrap();
+        });
+
+        let applied = post_process.apply(&mut graphic_adapter.borrow_mut(), Some(&output)).unwrap();
+
+        (scene_image, graphic_adapter.borrow().capture(&output).unwrap(), applied)
+    }
+
+    #[test]
+    fn identity_color_grading() {
+        let test_scene = TestScene::new();
+        let graphic_adapter = &test_scene.graphic_adapter;
+
+        let mut post_process = PostProcessStack::new().with_effect(ColorGrading::new(graphic_adapter));
+        let square = Rectangle::new(Vector2::new(4.0, 4.0), Size2::new(8.0, 8.0));
+        let (scene, output, applied) = apply_over_square(&test_scene, &mut post_process, square);
+
+        assert_eq!(applied, 1);
+        assert!(compare(&output, &scene, 2).unwrap().max_delta <= 2);
+    }
+
+    #[test]
+    fn bloom_spreads() {
+        let test_scene = TestScene::new();
+        let graphic_adapter = &test_scene.graphic_adapter;
+
+        let bloom = Bloom::new(graphic_adapter);
+        bloom.borrow_mut().uniforms_mut().radius = 1.0;
+
+        let mut post_process = PostProcessStack::new().with_effect(bloom);
+        let square = Rectangle::new(Vector2::new(7.0, 7.0), Size2::new(2.0, 2.0));
+        let (scene, output, _) = apply_over_square(&test_scene, &mut post_process, square);
+
+        // right next to square glows, while far away is kept as is
+        assert!(output.get_pixel(9, 8)[0] > scene.get_pixel(9, 8)[0]);
+        assert!(output.get_pixel(8, 10)[0] > scene.get_pixel(8, 10)[0]);
+        assert_eq!(output.get_pixel(0, 0), scene.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn pixelate_blocks() {
+        let test_scene = TestScene::new();
+        let graphic_adapter = &test_scene.graphic_adapter;
+
+        let pixelate = Pixelate::new(graphic_adapter);
+        pixelate.borrow_mut().uniforms_mut().pixel_size = 4.0;
+
+        let mut post_process = PostProcessStack::new().with_effect(pixelate);
+        let square = Rectangle::new(Vector2::new(5.0, 5.0), Size2::new(6.0, 6.0));
+        let (scene, output, _) = apply_over_square(&test_scene, &mut post_process, square);
+
+        // square isn't aligned to cells, but every cell ends up with a single color
+        for (x, y, pixel) in output.enumerate_pixels() {
+            assert_eq!(pixel, output.get_pixel(x - x % 4, y - y % 4), "at ({x}, {y})");
+        }
+
+        assert_ne!(output, scene);
+        assert_ne!(output.get_pixel(4, 4), output.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn post_process_golden() {
+        let test_scene = TestScene::new();
+        let graphic_adapter = &test_scene.graphic_adapter;
+
+        let bloom = Bloom::new(graphic_adapter);
+        bloom.borrow_mut().set_enabled(false);
+
+        let pixelate = Pixelate::new(graphic_adapter);
+        pixelate.borrow_mut().uniforms_mut().pixel_size = 2.0;
+
+        let mut post_process = PostProcessStack::new()
+            .with_effect(bloom.clone())
+            .with_effect(pixelate)
+            .with_effect(CrtScanlines::new(graphic_adapter))
+            .with_effect(Vignette::new(graphic_adapter))
+            .with_effect(ColorGrading::new(graphic_adapter));
+
+        let square = Rectangle::new(Vector2::new(4.0, 4.0), Size2::new(8.0, 8.0));
+
+        // disabled bloom is skipped
+        let (_, output, applied) = apply_over_square(&test_scene, &mut post_process, square);
+        assert_eq!(applied, 4);
+
+        // vignette darkens corners
+        assert!(output.get_pixel(0, 0)[2] < output.get_pixel(4, 8)[2]);
+
+        check_golden(&output, "post_process");
+
+        bloom.borrow_mut().set_enabled(true);
+
+        let (_, _, applied) = apply_over_square(&test_scene, &mut post_process, square);
+        assert_eq!(applied, 5);
+    }
+}
@@ -0,0 +1,70 @@
+/// Set of up to 32 layers, a graphic is only drawn by cameras sharing at least one layer with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderLayers(u32);
+
+impl RenderLayers {
+    pub const NONE: Self = Self(0);
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// How many layers there are, every function taking a layer panics when it isn't lower.
+    pub const COUNT: u8 = 32;
+
+    /// Only layer 0, which everything belongs to by default.
+    pub const DEFAULT: Self = Self::layer(0);
+
+    /// Only provided layer.
+    pub const fn layer(layer: u8) -> Self {
+        Self(Self::bit(layer))
+    }
+
+    pub const fn with(self, layer: u8) -> Self {
+        Self(self.0 | Self::bit(layer))
+    }
+
+    pub const fn without(self, layer: u8) -> Self {
+        Self(self.0 & !Self::bit(layer))
+    }
+
+    pub const fn contains(&self, layer: u8) -> bool {
+        self.0 & Self::bit(layer) != 0
+    }
+
+    pub const fn intersects(&self, other: &Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    const fn bit(layer: u8) -> u32 {
+        assert!(layer < Self::COUNT, "render layer should be lower than RenderLayers::COUNT (32)");
+        1 << layer
+    }
+}
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let layers = RenderLayers::layer(31).with(3).without(31);
+
+        assert!(layers.contains(3));
+        assert!(!layers.contains(31));
+        assert!(layers.intersects(&RenderLayers::layer(3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "render layer should be lower than RenderLayers::COUNT")]
+    fn layer_out_of_range() {
+        RenderLayers::NONE.with(32);
+    }
+}
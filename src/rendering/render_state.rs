@@ -12,10 +12,77 @@ use super::{
 pub trait RenderState<V> where
     V: Vertex,
 {
+    /// Every vertex is drawn once, in provided order.
     fn extend(
         &mut self,
         vertices: Iter<V>,
         texture: Option<AssetWeak<Texture>>,
         draw_config: DrawConfig<V>,
     ) -> Result<(), RenderStateError>;
+
+    /// Vertices are drawn following indices, which are relative to provided vertices.
+    /// By default, indexed vertices are expanded and drawn with `extend`.
+    fn extend_indexed(
+        &mut self,
+        vertices: Iter<V>,
+        indices: Iter<u32>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), RenderStateError> {
+        let vertices = vertices.as_slice();
+
+        let expanded = indices
+            .map(|index| vertices
+                .get(*index as usize)
+                .copied()
+                .ok_or(RenderStateError::IndexOutOfBounds { index: *index, vertices: vertices.len() })
+            )
+            .collect::<Result<Vec<V>, _>>()?;
+
+        self.extend(expanded.iter(), texture, draw_config)
+    }
+}
+
+/// Fails at the first index which doesn't refer to one of the provided vertices.
+pub(crate) fn check_indices(indices: &[u32], vertices: usize) -> Result<(), RenderStateError> {
+    match indices.iter().find(|index| **index as usize >= vertices) {
+        Some(index) => Err(RenderStateError::IndexOutOfBounds { index: *index, vertices }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{math::Vector2, rendering::{Vertex2D, VertexPosition}};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Vec<Vertex2D>);
+
+    impl RenderState<Vertex2D> for Recorder {
+        fn extend(
+            &mut self,
+            vertices: Iter<Vertex2D>,
+            _texture: Option<AssetWeak<Texture>>,
+            _draw_config: DrawConfig<Vertex2D>,
+        ) -> Result<(), RenderStateError> {
+            self.0.extend(vertices);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn expanded_indices() {
+        let vertices: Vec<_> = (0..3).map(|i| Vertex2D::from_position(Vector2::new(i as f32, 0.0))).collect();
+        let mut recorder = Recorder::default();
+
+        recorder.extend_indexed(vertices.iter(), [2, 0, 2].iter(), None, DrawConfig::default()).unwrap();
+
+        let xs: Vec<f32> = recorder.0.iter().map(|v| v.position().x).collect();
+        assert_eq!(xs, [2.0, 0.0, 2.0]);
+
+        let result = recorder.extend_indexed(vertices.iter(), [3].iter(), None, DrawConfig::default());
+        assert!(matches!(result, Err(RenderStateError::IndexOutOfBounds { index: 3, vertices: 3 })));
+    }
 }
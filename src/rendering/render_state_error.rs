@@ -14,6 +14,9 @@ pub enum RenderStateError {
     #[error("bindings failed: {0}")]
     Bindings(#[from] BindingsError),
 
-    #[error("Shader ({0:?}) instance not found.")]
+    #[error("Shader ({0:?}) instance not found, it was never created or it was dropped.")]
     ShaderInstanceNotFound(Shader),
+
+    #[error("index {index} is out of bounds, there are only {vertices} vertices")]
+    IndexOutOfBounds { index: u32, vertices: usize },
 }
@@ -0,0 +1,113 @@
+use std::rc::Rc;
+
+use crate::math::Size2;
+
+use super::{
+    backend::{DepthStencilBuffer, MsaaBuffer},
+    texture::TextureId,
+    Texture,
+    TextureFormat,
+};
+
+/// Offscreen texture which can be drawn into, instead of presentation surface.
+///
+/// It's contents can be sampled at later passes through `RenderTarget::texture`.
+#[derive(Clone)]
+pub struct RenderTarget {
+    id: TextureId,
+    format: TextureFormat,
+    size: Size2<u32>,
+    texture: Rc<wgpu::Texture>,
+    depth_stencil: Option<DepthStencilBuffer>,
+    msaa: Option<MsaaBuffer>,
+}
+
+impl RenderTarget {
+    /// `sample_count` should be supported by format, 1 doesn't multisample.
+    pub(super) fn new(
+        device: &wgpu::Device,
+        format: TextureFormat,
+        size: Size2<u32>,
+        sample_count: u32,
+        depth_stencil: bool,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("render target"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                 | wgpu::TextureUsages::TEXTURE_BINDING
+                 | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        Self {
+            id: TextureId::generate(),
+            format,
+            size,
+            texture: Rc::new(texture),
+            // both share sample count, so they can be attached together
+            depth_stencil: depth_stencil.then(|| DepthStencilBuffer::new(device, size, sample_count)),
+            msaa: (sample_count > 1).then(|| MsaaBuffer::new(device, format, size, sample_count)),
+        }
+    }
+
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    pub fn size(&self) -> Size2<u32> {
+        self.size
+    }
+
+    pub fn width(&self) -> u32 {
+        self.size.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.size.height
+    }
+
+    /// MSAA sample count in use, 1 when it isn't multisampled.
+    pub fn sample_count(&self) -> u32 {
+        self.msaa
+            .as_ref()
+            .map_or(1, MsaaBuffer::sample_count)
+    }
+
+    pub fn has_depth_stencil(&self) -> bool {
+        self.depth_stencil.is_some()
+    }
+
+    /// A `Texture` sharing this target's contents, it can be registered as an asset.
+    pub fn texture(&self) -> Texture {
+        Texture::from_gpu(self.id, self.format, self.size, self.texture.clone())
+    }
+
+    pub(in crate::rendering) fn raw(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub(in crate::rendering) fn create_view(&self) -> wgpu::TextureView {
+        self.texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    pub(in crate::rendering) fn create_msaa_view(&self) -> Option<wgpu::TextureView> {
+        self.msaa
+            .as_ref()
+            .map(MsaaBuffer::create_view)
+    }
+
+    pub(in crate::rendering) fn create_depth_stencil_view(&self) -> Option<wgpu::TextureView> {
+        self.depth_stencil
+            .as_ref()
+            .map(DepthStencilBuffer::create_view)
+    }
+}
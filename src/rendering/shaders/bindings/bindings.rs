@@ -1,4 +1,4 @@
-use std::mem;
+use std::{mem, rc::Rc};
 use wgpu::util::DeviceExt;
 
 use crate::rendering::TextureView;
@@ -17,10 +17,10 @@ pub struct Bindings<'d> {
 }
 
 // TODO
-// - let user choose which index value is defining, instead always choose first found
+// - let user choose which index value is defining, instead always choose first empty or found
 
 impl<'d> Bindings<'d> {
-    /// Place provided uniforms at first found Uniform binding entry.
+    /// Place provided uniforms at first empty Uniform binding entry, or first found when every one is filled.
     pub fn uniforms<U>(&mut self, uniforms: &[U]) -> Result<(), BindingsError> where
         U: bytemuck::Pod + bytemuck::Zeroable
     {
@@ -71,7 +71,8 @@ impl<'d> Bindings<'d> {
         }
     }
 
-    /// Place provided `TextureView` at first found Texture and Sampler binding entry.
+    /// Place provided `TextureView` at first empty Texture and Sampler binding entry,
+    /// or first found when every one is filled. So textures are placed in order of calls.
     pub(in crate::rendering) fn texture_view(
         &mut self,
         texture_view: TextureView
@@ -87,7 +88,7 @@ impl<'d> Bindings<'d> {
 
         self.replace_entry(
             &BindingsDescriptorEntry::Sampler(texture_view.sampler_binding_type),
-            RawBinding::Sampler(self.device.create_sampler(&texture_view.sampler)),
+            RawBinding::Sampler(texture_view.sampler),
         )?;
 
         Ok(())
@@ -124,13 +125,15 @@ impl<'d> Bindings<'d> {
         &mut self,
         descriptor: &BindingsDescriptorEntry,
     ) -> Option<&mut BindingEntry> {
-        for e in self.entries.iter_mut() {
-            if e.descriptor.match_descriptor(descriptor) {
-                return Some(e);
-            }
-        };
+        let empty = self.entries
+            .iter()
+            .position(|e| !e.has_value() && e.descriptor.match_descriptor(descriptor));
+
+        let index = empty.or_else(|| self.entries
+            .iter()
+            .position(|e| e.descriptor.match_descriptor(descriptor)))?;
 
-        None
+        self.entries.get_mut(index)
     }
 
     fn replace_entry(
@@ -175,8 +178,8 @@ impl BindingEntry {
 /// Stores a resource at binding entry.
 enum RawBinding {
     Buffer(wgpu::Buffer),
-    TextureView(wgpu::TextureView),
-    Sampler(wgpu::Sampler),
+    TextureView(Rc<wgpu::TextureView>),
+    Sampler(Rc<wgpu::Sampler>),
 }
 
 impl RawBinding {
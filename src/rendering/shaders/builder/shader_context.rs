@@ -21,7 +21,7 @@ pub struct ShaderContext {
     pub bind_group_layout: wgpu::BindGroupLayout,
     pub reuse_pipeline: bool,
     pipeline_layout: wgpu::PipelineLayout,
-    pipeline: HashMap<ShaderConfig, ShaderPipeline>,
+    pipeline: HashMap<PipelineKey, ShaderPipeline>,
     surface_format: wgpu::TextureFormat,
     vertex_attributes: Vec<Vec<wgpu::VertexAttribute>>,
     bindings: Vec<BindingsDescriptorEntry>,
@@ -108,16 +108,29 @@ impl ShaderContext {
         &self.bindings
     }
 
+    /// Pipeline previously created with `ShaderContext::pipeline`.
+    pub(in crate::rendering) fn cached_pipeline(
+        &self,
+        config: &ShaderConfig,
+        target_layout: TargetLayout,
+    ) -> Option<&ShaderPipeline> {
+        self.pipeline.get(&(config.pipeline_key(), target_layout))
+    }
+
+    /// Pipeline for a target with provided attachments.
     pub(in crate::rendering) fn pipeline<'p>(
         &'p mut self,
         device: &wgpu::Device,
-        config: &ShaderConfig
+        config: &ShaderConfig,
+        target_layout: TargetLayout,
     ) -> &'p ShaderPipeline {
-        match self.reuse_pipeline && self.pipeline.contains_key(config) {
+        let key = (config.pipeline_key(), target_layout);
+
+        match self.reuse_pipeline && self.pipeline.contains_key(&key) {
             true => {
                 //println!("Using pipeline...");
                 // NOTE  safe to unwrap  key was checked before
-                self.pipeline.get(config).unwrap()
+                self.pipeline.get(&key).unwrap()
             },
             false => {
                 //println!("Creating pipeline...");
@@ -144,28 +157,46 @@ impl ShaderContext {
                     fragment: Some(wgpu::FragmentState {
                         module: &self.fragment_module,
                         entry_point: "main",
-                        targets: &[Some(self.surface_format.into())]
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: self.surface_format,
+                            blend: config.blend_mode().blend_state(),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
                     }),
                     primitive: *config.primitive_state(),
-                    depth_stencil: None,
-                    multisample: wgpu::MultisampleState::default(),
+                    depth_stencil: target_layout
+                        .depth_stencil_format
+                        .map(|format| config.depth_stencil().state(format)),
+                    multisample: wgpu::MultisampleState {
+                        count: target_layout.sample_count,
+                        ..Default::default()
+                    },
                     multiview: None,
                 });
 
                 self.pipeline.insert(
-                    *config,
+                    key,
                     ShaderPipeline {
                         handle,
                     }
                 );
 
                 // NOTE  safe to unwrap  key was inserted before
-                self.pipeline.get(config).unwrap()
+                self.pipeline.get(&key).unwrap()
             },
         }
     }
 }
 
+/// Attachments of a draw target, which every pipeline drawing into it should match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(in crate::rendering) struct TargetLayout {
+    pub depth_stencil_format: Option<wgpu::TextureFormat>,
+    pub sample_count: u32,
+}
+
+type PipelineKey = (ShaderConfig, TargetLayout);
+
 pub struct ShaderPipeline {
     pub handle: wgpu::RenderPipeline,
 }
@@ -1,18 +1,22 @@
 pub use wgpu::TextureFormat;
 
 use std::{
+    cell::RefCell,
+    collections::HashMap,
     fmt::Display,
     hash::Hash,
     num::NonZeroU32,
     path::Path,
+    rc::Rc,
+    sync::atomic::{AtomicU32, Ordering},
 };
 
-use wgpu::{util::DeviceExt, TextureViewDimension, TextureSampleType, FilterMode, SamplerBindingType};
-use image::io::Reader as ImageReader;
+use wgpu::{TextureViewDimension, TextureSampleType, FilterMode, SamplerBindingType};
+use image::{io::Reader as ImageReader, RgbaImage};
 use crate::{math::Size2, resources::AssetPathLoad};
-use super::{TextureError, TextureConfig};
+use super::{TextureError, TextureConfig, TextureSamplerConfig};
 
-static mut NEXT_ID: TextureId = TextureId(1);
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub(crate) struct TextureId(u32);
@@ -20,8 +24,8 @@ pub(crate) struct TextureId(u32);
 impl TextureId {
     pub const NONE: Self = TextureId(0);
 
-    pub(super) fn next(&mut self) {
-        self.0 += 1;
+    pub(super) fn generate() -> Self {
+        TextureId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
     }
 }
 
@@ -35,7 +39,31 @@ pub struct Texture {
     id: TextureId,
     format: TextureFormat,
     size: Size2<u32>,
-    data: Vec<u8>,
+    release_after_upload: bool,
+    storage: RefCell<TextureStorage>,
+}
+
+/// Where texture pixels are kept, created lazily at GPU when texture is first drawn.
+struct TextureStorage {
+    /// CPU copy, it may be released after it's uploaded.
+    data: Option<Vec<u8>>,
+    gpu: Option<GpuTexture>,
+}
+
+struct GpuTexture {
+    texture: Rc<wgpu::Texture>,
+    views: HashMap<TextureConfig, Rc<wgpu::TextureView>>,
+    samplers: HashMap<TextureSamplerConfig, Rc<wgpu::Sampler>>,
+}
+
+impl GpuTexture {
+    fn new(texture: Rc<wgpu::Texture>) -> Self {
+        Self {
+            texture,
+            views: HashMap::new(),
+            samplers: HashMap::new(),
+        }
+    }
 }
 
 impl Texture {
@@ -44,90 +72,153 @@ impl Texture {
         size: Size2<u32>,
         data: &[u8]
     ) -> Self {
-        let id = unsafe {
-            let id = NEXT_ID;
-            NEXT_ID.next();
-
-            id
-        };
+        Self {
+            id: TextureId::generate(),
+            format,
+            size,
+            release_after_upload: false,
+            storage: RefCell::new(TextureStorage {
+                data: Some(data.to_owned()),
+                gpu: None,
+            }),
+        }
+    }
 
+    pub(super) fn from_gpu(
+        id: TextureId,
+        format: TextureFormat,
+        size: Size2<u32>,
+        texture: Rc<wgpu::Texture>,
+    ) -> Self {
         Self {
             id,
             format,
             size,
-            data: data.to_owned(),
+            release_after_upload: false,
+            storage: RefCell::new(TextureStorage {
+                data: None,
+                gpu: Some(GpuTexture::new(texture)),
+            }),
         }
     }
 
+    /// Drops CPU copy as soon as it's uploaded to GPU.
+    pub fn with_release_after_upload(mut self, release_after_upload: bool) -> Self {
+        self.release_after_upload = release_after_upload;
+        self
+    }
+
     pub fn load<P: AsRef<Path> + std::marker::Copy>(
         path: P
     ) -> Result<Self, TextureError> {
-        let mut contents = ImageReader::open(path)
+        let contents = ImageReader::open(path)
             .map_err(|e| TextureError::Open(e))?
             .decode()
             .map_err(|_| TextureError::UnsupportedFormat(path.as_ref().to_owned()))?;
 
-        let size = Size2::new(contents.width(), contents.height());
+        if contents.as_rgba8().is_none() {
+            return Err(TextureError::RepresentationConversion);
+        }
 
-        let data = {
-            let rgba = contents.as_mut_rgba8()
-                               .ok_or_else(|| TextureError::RepresentationConversion)?;
+        Ok(Self::from_image(contents.into_rgba8()))
+    }
 
-            // convert rgba -> bgra
-            for p in rgba.pixels_mut() {
-                let [ r, g, b, a ] = p.0;
-                p.0 = [ b, g, r, a ];
-            }
+    pub fn from_image(mut image: RgbaImage) -> Self {
+        let size = Size2::new(image.width(), image.height());
 
-            rgba.as_raw()
-        };
+        // convert rgba -> bgra
+        for p in image.pixels_mut() {
+            let [ r, g, b, a ] = p.0;
+            p.0 = [ b, g, r, a ];
+        }
 
-        Ok(Self::new(TextureFormat::Bgra8UnormSrgb, size, data))
+        Self::new(TextureFormat::Bgra8UnormSrgb, size, image.as_raw())
     }
 
     pub(super) fn id(&self) -> &TextureId {
         &self.id
     }
 
-    pub(super) fn view<'v>(
+    /// Whether texture is already at GPU.
+    pub fn is_uploaded(&self) -> bool {
+        self.storage.borrow().gpu.is_some()
+    }
+
+    /// Whether CPU copy is still available.
+    pub fn has_data(&self) -> bool {
+        self.storage.borrow().data.is_some()
+    }
+
+    /// Drops CPU copy, it's only released when texture is already at GPU.
+    /// Returns whether it was released.
+    pub fn release_data(&mut self) -> bool {
+        let storage = self.storage.get_mut();
+
+        if storage.gpu.is_none() {
+            return false;
+        }
+
+        storage.data = None;
+        true
+    }
+
+    /// View with provided configuration, texture is uploaded at first use.
+    /// Views and samplers are cached, so they're only created once for each configuration.
+    ///
+    /// Mip level and sample counts are taken from configuration used at first view,
+    /// only first mip level is filled with texture data.
+    pub(super) fn view(
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         config: TextureConfig
-    ) -> TextureView<'v> {
-        let extent = wgpu::Extent3d {
-            width: self.size.width,
-            height: self.size.height,
-            depth_or_array_layers: 1,
-        };
+    ) -> TextureView {
+        let mut storage = self.storage.borrow_mut();
+        let storage = &mut *storage;
 
-        let descriptor = wgpu::TextureDescriptor {
-            size: extent,
-            mip_level_count: config.mip_level_count
-                                   .unwrap_or_else(|| unsafe {
-                                       NonZeroU32::new_unchecked(1u32)
-                                   }).into(),
-            sample_count: config.sampler.sample_count,
-            dimension: wgpu::TextureDimension::D2,
-            format: self.format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            label: None,
-            view_formats: &[],
-        };
+        let gpu = storage.gpu.get_or_insert_with(|| {
+            let extent = wgpu::Extent3d {
+                width: self.size.width,
+                height: self.size.height,
+                depth_or_array_layers: 1,
+            };
+
+            let descriptor = wgpu::TextureDescriptor {
+                size: extent,
+                mip_level_count: config.base_mip_level + config.mip_level_count.map_or(1, NonZeroU32::get),
+                sample_count: config.sampler.sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                label: None,
+                view_formats: &[],
+            };
 
-        let texture = device.create_texture_with_data(
-            &queue,
-            &descriptor,
-            self.data.as_slice(),
-        );
+            let texture = device.create_texture(&descriptor);
 
-        let filterable = config.sampler.mag_filter != FilterMode::Nearest
-                || config.sampler.min_filter != FilterMode::Nearest
-                || config.sampler.mipmap_filter != FilterMode::Nearest;
+            if let Some(data) = storage.data.as_deref() {
+                queue.write_texture(
+                    texture.as_image_copy(),
+                    data,
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(self.size.width * self.format.describe().block_size as u32),
+                        rows_per_image: None,
+                    },
+                    extent,
+                );
+            }
 
-        TextureView {
-            id: self.id,
-            view: texture.create_view(&wgpu::TextureViewDescriptor {
+            GpuTexture::new(Rc::new(texture))
+        });
+
+        if self.release_after_upload {
+            storage.data = None;
+        }
+
+        let view = gpu.views
+            .entry(config)
+            .or_insert_with(|| Rc::new(gpu.texture.create_view(&wgpu::TextureViewDescriptor {
                 label: None,
                 format: None,
                 dimension: Some(TextureViewDimension::D2),
@@ -136,8 +227,12 @@ impl Texture {
                 mip_level_count: config.mip_level_count,
                 base_array_layer: config.base_array_layer,
                 array_layer_count: config.array_layer_count,
-            }),
-            sampler: wgpu::SamplerDescriptor {
+            })))
+            .clone();
+
+        let sampler = gpu.samplers
+            .entry(config.sampler)
+            .or_insert_with(|| Rc::new(device.create_sampler(&wgpu::SamplerDescriptor {
                 label: None,
                 address_mode_u: config.sampler.address_mode_u,
                 address_mode_v: config.sampler.address_mode_v,
@@ -149,7 +244,17 @@ impl Texture {
                 anisotropy_clamp: config.sampler.anisotropy_clamp,
                 border_color: config.sampler.border_color,
                 ..Default::default()
-            },
+            })))
+            .clone();
+
+        let filterable = config.sampler.mag_filter != FilterMode::Nearest
+                || config.sampler.min_filter != FilterMode::Nearest
+                || config.sampler.mipmap_filter != FilterMode::Nearest;
+
+        TextureView {
+            id: self.id,
+            view,
+            sampler,
             sample_count: config.sampler.sample_count,
             sample_type: TextureSampleType::Float {
                 filterable,
@@ -163,6 +268,10 @@ impl Texture {
         }
     }
 
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
     pub fn size(&self) -> Size2<u32> {
         self.size
     }
@@ -184,12 +293,68 @@ impl AssetPathLoad for Texture {
     }
 }
 
-pub(super) struct TextureView<'a> {
+pub(super) struct TextureView {
     pub id: TextureId,
-    pub view: wgpu::TextureView,
-    pub sampler: wgpu::SamplerDescriptor<'a>,
+    pub view: Rc<wgpu::TextureView>,
+    pub sampler: Rc<wgpu::Sampler>,
     pub sample_count: u32,
     pub sample_type: TextureSampleType,
     pub sampler_binding_type: SamplerBindingType,
     pub view_dimension: TextureViewDimension,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::rendering::{FilterMode, GraphicAdapter};
+
+    use super::*;
+
+    #[test]
+    fn cached_views() {
+        let graphic_adapter = GraphicAdapter::software();
+
+        let adapter = graphic_adapter.borrow();
+        let backend = adapter.backend();
+        let mut texture = Texture::new(TextureFormat::Rgba8UnormSrgb, Size2::new(2, 2), &[255; 16]);
+
+        assert!(!texture.release_data());
+        assert!(!texture.is_uploaded());
+
+        let first = texture.view(&backend.device, &backend.queue, TextureConfig::default());
+        let second = texture.view(&backend.device, &backend.queue, TextureConfig::default());
+
+        assert!(Rc::ptr_eq(&first.view, &second.view));
+        assert!(Rc::ptr_eq(&first.sampler, &second.sampler));
+
+        let mut config = TextureConfig::default();
+        config.sampler.mag_filter = FilterMode::Linear;
+
+        let linear = texture.view(&backend.device, &backend.queue, config);
+
+        assert!(!Rc::ptr_eq(&first.sampler, &linear.sampler));
+        assert!(texture.is_uploaded());
+        assert!(texture.release_data());
+        assert!(!texture.has_data());
+    }
+
+    #[test]
+    fn mip_levels_from_config() {
+        let graphic_adapter = GraphicAdapter::software();
+
+        let adapter = graphic_adapter.borrow();
+        let backend = adapter.backend();
+        let texture = Texture::new(TextureFormat::Rgba8UnormSrgb, Size2::new(4, 4), &[255; 64]);
+
+        let config = TextureConfig {
+            mip_level_count: NonZeroU32::new(3),
+            ..Default::default()
+        };
+
+        texture.view(&backend.device, &backend.queue, config);
+
+        let storage = texture.storage.borrow();
+        let gpu_texture = &storage.gpu.as_ref().unwrap().texture;
+        assert_eq!(gpu_texture.mip_level_count(), 3);
+        assert_eq!(gpu_texture.sample_count(), 1);
+    }
+}
@@ -0,0 +1,72 @@
+use std::slice::Iter;
+
+use crate::{
+    math::{Affine2, Vector2},
+    resources::AssetWeak,
+};
+
+use super::{
+    DrawConfig,
+    RenderState,
+    RenderStateError,
+    Texture,
+    VertexPosition,
+};
+
+/// Transforms every vertex position before handing it to another `RenderState`.
+///
+/// Vertices are transformed at CPU, so draws with distinct transforms still share a batch.
+pub struct TransformedRenderState<'a, V: VertexPosition<Position = Vector2<f32>>> {
+    state: &'a mut dyn RenderState<V>,
+    transform: Affine2,
+    vertices: Vec<V>,
+}
+
+impl<'a, V: VertexPosition<Position = Vector2<f32>>> TransformedRenderState<'a, V> {
+    pub fn new(state: &'a mut dyn RenderState<V>, transform: Affine2) -> Self {
+        Self {
+            state,
+            transform,
+            vertices: Vec::new(),
+        }
+    }
+
+    pub fn transform(&self) -> Affine2 {
+        self.transform
+    }
+
+    /// Changes transform for next vertices, it's scratch memory is kept.
+    pub fn set_transform(&mut self, transform: Affine2) {
+        self.transform = transform;
+    }
+
+    fn transform_vertices(&mut self, vertices: Iter<V>) {
+        let transform = self.transform;
+
+        self.vertices.clear();
+        self.vertices.extend(vertices.map(|v| v.with_position(transform.transform_point(v.position()))));
+    }
+}
+
+impl<'a, V: VertexPosition<Position = Vector2<f32>>> RenderState<V> for TransformedRenderState<'a, V> {
+    fn extend(
+        &mut self,
+        vertices: Iter<V>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), RenderStateError> {
+        self.transform_vertices(vertices);
+        self.state.extend(self.vertices.iter(), texture, draw_config)
+    }
+
+    fn extend_indexed(
+        &mut self,
+        vertices: Iter<V>,
+        indices: Iter<u32>,
+        texture: Option<AssetWeak<Texture>>,
+        draw_config: DrawConfig<V>,
+    ) -> Result<(), RenderStateError> {
+        self.transform_vertices(vertices);
+        self.state.extend_indexed(self.vertices.iter(), indices, texture, draw_config)
+    }
+}
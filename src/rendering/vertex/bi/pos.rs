@@ -30,6 +30,12 @@ impl VertexPosition for Vertex2D {
     fn position(&self) -> Self::Position {
         self.position
     }
+
+    fn with_position(mut self, position: Self::Position) -> Self {
+        self.position = position;
+
+        self
+    }
 }
 
 impl Add<Self> for Vertex2D {
@@ -28,6 +28,12 @@ impl VertexPosition for Vertex2DColor {
     fn position(&self) -> Self::Position {
         self.position
     }
+
+    fn with_position(mut self, position: Self::Position) -> Self {
+        self.position = position;
+
+        self
+    }
 }
 
 impl VertexColor for Vertex2DColor {
@@ -24,6 +24,7 @@ pub trait VertexPosition : Vertex {
 
     fn from_position(pos: Self::Position) -> Self;
     fn position(&self) -> Self::Position;
+    fn with_position(self, position: Self::Position) -> Self;
 }
 
 /// A vertex which has a 2d uv.
@@ -52,6 +52,11 @@ impl<T> AssetWeak<T> {
             .upgrade()
             .map(|r| r.into())
     }
+
+    /// Whether both point to the same asset.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.res.ptr_eq(&other.res)
+    }
 }
 
 impl<T> Clone for AssetWeak<T> {
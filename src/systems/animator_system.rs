@@ -0,0 +1,78 @@
+use std::marker::PhantomData;
+
+use crate::{
+    base::ApplicationState,
+    components::{
+        Animator,
+        GraphicDisplayer,
+    },
+    ecs::{
+        component::{
+            self,
+            BaseQuery,
+            QueryEntry,
+        },
+        system::System,
+        FrameState,
+    },
+    math::Vector2,
+    rendering::{
+        graphics::AnimatedSprite,
+        VertexPosition,
+        VertexTexture2D,
+    },
+};
+
+/// Advances every `Animator` by frame's delta time, updating it's `AnimatedSprite`.
+/// It should run at `SchedulerStep::Update`, before anything reading animation events.
+///
+/// [`SchedulerStep::Update`]: crate::ecs::SchedulerStep::Update
+pub struct AnimatorSystem<V> {
+    phantom: PhantomData<V>,
+}
+
+impl<V> Default for AnimatorSystem<V> {
+    fn default() -> Self {
+        Self {
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<V> System for AnimatorSystem<V> where
+    V: VertexPosition<Position = Vector2<f32>> + VertexTexture2D,
+{
+    type Query<'q> = (
+        component::MutQuery<'q, Animator>,
+        component::MutQuery<'q, GraphicDisplayer<V>>,
+    );
+
+    type Resources<'r> = ();
+
+    fn setup(&mut self) {
+    }
+
+    fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+    }
+
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, _resources: Self::Resources<'r>, state: &mut FrameState) {
+        for QueryEntry { component: (mut animator, mut graphic_displayer), .. } in query.iter_components() {
+            animator.advance(state.delta.duration);
+
+            let (Some(frame), Some(sprite)) = (
+                animator.current_frame(),
+                graphic_displayer.mut_retrieve_graphic::<AnimatedSprite<V>>(),
+            ) else {
+                continue;
+            };
+
+            if sprite.frame() != Some(&frame.region) {
+                sprite.set_frame(frame.region.clone());
+            }
+        }
+    }
+
+    fn create_query<'q>(&self) -> Self::Query<'q> {
+        Self::Query::default()
+    }
+}
@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use crate::{
     base::ApplicationState,
     components::{
@@ -7,12 +5,14 @@ use crate::{
         DiagComponent,
         GraphicDisplayer,
     },
+    diag::Diagnostics,
     ecs::{
         component::{
             self,
             QueryEntry,
             BaseQuery,
         },
+        resource::Res,
         system::System,
         FrameState,
     },
@@ -24,11 +24,8 @@ use crate::{
     },
 };
 
-pub struct DiagSystem {
-    fps: u32,
-    frame_count: u32,
-    remaining_duration: Duration,
-}
+#[derive(Default)]
+pub struct DiagSystem;
 
 impl System for DiagSystem {
     type Query<'q> = (
@@ -37,51 +34,39 @@ impl System for DiagSystem {
         component::MutQuery<'q, Transform>,
     );
 
+    type Resources<'r> = Res<'r, Diagnostics>;
+
     fn setup(&mut self) {
     }
 
     fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
     }
 
-    fn run<'q>(&mut self, query: Self::Query<'q>, state: &mut FrameState) {
-        self.frame_count += 1;
-        self.remaining_duration = self.remaining_duration.saturating_sub(state.delta.duration);
-
-        if self.remaining_duration.is_zero() {
-            // frame count completed
-            self.fps = self.frame_count;
-            self.frame_count = 0;
-            self.remaining_duration = Duration::from_secs(1);
-        }
-
-        for QueryEntry { component: (a, b, c), .. } in query.iter_components() {
-            if let Some(mut graphic_displayer) = b {
-                if let Some(mut diag) = a {
-                    diag.fps = self.fps;
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, diagnostics: Self::Resources<'r>, state: &mut FrameState) {
+        for QueryEntry { component: (mut diag, mut graphic_displayer, mut transform), .. } in query.iter_components() {
+            diag.fps = diagnostics.fps;
 
-                    // update graphic, if there is any
-                    if let Some(ref mut g) = graphic_displayer.graphic {
-                        let text: &mut Text<MTSDFFontRendering, Vertex2DTexture> = g.as_any_mut().downcast_mut().unwrap();
+            // update graphic, if there is any
+            if let Some(ref mut g) = graphic_displayer.graphic {
+                let text: &mut Text<MTSDFFontRendering, Vertex2DTexture> = g.as_any_mut().downcast_mut().unwrap();
 
-                        text.change_value(format!(
-                            "{}\nu: {:.4}\nr: {:.4}\ndc: {}",
-                            self.fps.to_string(),
-                            state.app.diagnostics.update_timer.as_secs_f32(),
-                            state.app.diagnostics.render_timer.as_secs_f32(),
-                            state.app.diagnostics.draw_calls,
-                        ));
+                text.change_value(format!(
+                    "{}\nu: {:.4}\nr: {:.4}\ndc: {}",
+                    diagnostics.fps.to_string(),
+                    diagnostics.update_timer.as_secs_f32(),
+                    diagnostics.render_timer.as_secs_f32(),
+                    diagnostics.draw_calls,
+                ));
 
-                        // reposition
-                        if let Some(mut transform) = c {
-                            let window_size = state.app.main_window.inner_size();
+                // reposition
+                if let Some(ref app) = state.app {
+                    let window_size = app.main_window.inner_size();
 
-                            transform.local_position
-                                = Vector2::new(
-                                    window_size.width as f32 - text.px_size().width,
-                                    8 as f32
-                                ).convert()
-                        }
-                    }
+                    transform.local_position
+                        = Vector2::new(
+                            window_size.width as f32 - text.px_size().width,
+                            8 as f32
+                        ).convert()
                 }
             }
         }
@@ -92,13 +77,3 @@ impl System for DiagSystem {
     }
 }
 
-impl Default for DiagSystem {
-    fn default() -> Self {
-        Self {
-            fps: 0,
-            frame_count: 0,
-            remaining_duration: Duration::from_secs(1),
-        }
-    }
-}
-
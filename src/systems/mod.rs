@@ -5,3 +5,25 @@ pub use update_system::UpdateSystem;
 
 mod diag_system;
 pub use diag_system::DiagSystem;
+
+mod animator_system;
+pub use animator_system::AnimatorSystem;
+
+mod transform_system;
+pub use transform_system::TransformSystem;
+
+/// Labels built-in systems are registered with, so others can be ordered around them.
+pub mod labels {
+    use crate::ecs::scheduler::SystemLabel;
+
+    /// `TransformSystem`, registered by default at `SchedulerStep::Render`.
+    ///
+    /// [`TransformSystem`]: super::TransformSystem
+    /// [`SchedulerStep::Render`]: crate::ecs::SchedulerStep::Render
+    pub const TRANSFORM: SystemLabel = "midnight::transform";
+
+    /// `RenderSystem`, it should be registered with it to run after `TRANSFORM`.
+    ///
+    /// [`RenderSystem`]: super::render_system::RenderSystem
+    pub const RENDER: SystemLabel = "midnight::render";
+}
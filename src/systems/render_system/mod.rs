@@ -3,6 +3,7 @@ pub mod default_shader;
 use std::{
     rc::{Rc, Weak},
     cell::RefCell,
+    fmt::Display,
     marker::PhantomData,
 };
 
@@ -10,26 +11,36 @@ use crate::{
     base::ApplicationState,
     components::{
         transform::Transform,
+        Camera2D,
         GraphicDisplayer,
     },
+    diag::Diagnostics,
     ecs::{
         component::{
             self,
             BaseQuery,
             QueryEntry,
         },
+        resource::ResMut,
         system::System,
         FrameState,
     },
     math::{
+        Affine2,
         Matrix4x4,
         Vector2,
     },
     rendering::{
-        batchers::DrawBatcher,
+        backend::DrawCommand,
+        batchers::{BatchMode, DrawBatcher},
         Color,
         DrawConfig,
         GraphicAdapter,
+        post_process::PostProcessStack,
+        RenderLayers,
+        RenderTarget,
+        SortKey,
+        TransformedRenderState,
         Vertex,
         VertexPosition,
     },
@@ -38,14 +49,28 @@ use crate::{
 
 use default_shader::DefaultShader;
 
+/// Draws every `GraphicDisplayer` at `SchedulerStep::Render`.
+/// Register it labeled `labels::RENDER`, so it runs after transforms are propagated.
+///
+/// [`SchedulerStep::Render`]: crate::ecs::SchedulerStep::Render
+/// [`labels::RENDER`]: crate::systems::labels::RENDER
 pub struct RenderSystem<V: Vertex> {
     //pub world: Matrix4x4<f32>,
+
+    /// Used when there is no active `Camera2D`.
     pub view: Matrix4x4<f32>,
     //pub projection: Matrix4x4<f32>,
 
     graphic_adapter: Weak<RefCell<GraphicAdapter>>,
     default_shader: AssetWeak<DefaultShader>,
     clear_color: Color::<f32>,
+    render_target: Option<RenderTarget>,
+    post_process: Option<PostProcessStack>,
+    batch_mode: BatchMode,
+
+    /// Whether last frame was skipped, so a failure repeating every frame is only reported once.
+    frame_skipped: bool,
+    post_process_skipped: bool,
     phantom: PhantomData<V>,
 }
 
@@ -65,6 +90,11 @@ impl<V: Vertex> RenderSystem<V> {
             default_shader,
             phantom: Default::default(),
             clear_color: 0xFF236EFF.into(),
+            render_target: None,
+            post_process: None,
+            batch_mode: BatchMode::default(),
+            frame_skipped: false,
+            post_process_skipped: false,
             view: Matrix4x4::default(),
         }
     }
@@ -74,21 +104,136 @@ impl<V: Vertex> RenderSystem<V> {
 
         self
     }
+
+    /// How draws are ordered and merged, `Transform` z and position are used as sort key.
+    pub fn with_batch_mode(mut self, batch_mode: BatchMode) -> Self {
+        self.batch_mode = batch_mode;
+
+        self
+    }
+
+    /// Draws into provided target, instead of presentation surface.
+    pub fn with_render_target(mut self, render_target: RenderTarget) -> Self {
+        self.render_target = Some(render_target);
+
+        self
+    }
+
+    /// Scene is drawn into an intermediate target, then it's effects are run over it before presenting.
+    /// While every effect is disabled, scene is drawn directly.
+    pub fn with_post_process(mut self, post_process: PostProcessStack) -> Self {
+        self.post_process = Some(post_process);
+
+        self
+    }
+
+    pub fn post_process_mut(&mut self) -> Option<&mut PostProcessStack> {
+        self.post_process.as_mut()
+    }
+
+    fn skip_frame(&mut self, error: impl Display) {
+        if !self.frame_skipped {
+            eprintln!("[RenderSystem] Frame skipped: {}", error);
+        }
+
+        self.frame_skipped = true;
+    }
+}
+
+type GraphicsQuery<'q, V> = (
+    component::Query<'q, GraphicDisplayer<V>>,
+    component::Query<'q, Transform>,
+);
+
+impl<V: Vertex + VertexPosition<Position = Vector2<f32>>> RenderSystem<V> {
+    /// Draws every graphic on provided layers, returning how many batches were needed.
+    fn draw_view(
+        &self,
+        draw_command: &mut DrawCommand<'_>,
+        graphics: &GraphicsQuery<'_, V>,
+        view: Matrix4x4<f32>,
+        layers: RenderLayers,
+    ) -> usize {
+        // collects everything into a batcher
+        let mut draw_batcher = DrawBatcher::new(draw_command).with_mode(self.batch_mode);
+
+        //draw_batcher.register_shader(&self.default_shader);
+
+        // every vertex is placed by it's entity global transform
+        {
+            let mut transformed_state = TransformedRenderState::new(&mut draw_batcher, Affine2::IDENTITY);
+
+            //println!("{} query components", query.iter_components().count());
+            for QueryEntry { component: (graphic_displayer, transform), .. } in graphics.iter_components() {
+                if !graphic_displayer.render_layers.intersects(&layers) {
+                    continue;
+                }
+
+                if let Some(ref g) = graphic_displayer.graphic {
+                    transformed_state.set_transform(transform.global_matrix());
+
+                    let draw_config = DrawConfig {
+                        vertex: V::default(),
+                        shader_config: graphic_displayer
+                                        .shader_config
+                                        .or_else(|| { Some(
+                                            self.default_shader
+                                                .upgrade()
+                                                .get()?
+                                                .default_config()
+                                                .clone()
+                                        ) } )
+                                        .map(|config| match graphic_displayer.blend_mode {
+                                            Some(blend_mode) => config.with_blend_mode(blend_mode),
+                                            None => config,
+                                        })
+                                        .map(|config| match graphic_displayer.depth_stencil {
+                                            Some(depth_stencil) => config.with_depth_stencil(depth_stencil),
+                                            None => config,
+                                        }),
+                        texture_config: graphic_displayer
+                                         .texture_config
+                                         .or_else(|| Some(Default::default())),
+                        sort_key: SortKey::new(transform.z(), transform.position().y),
+                    };
+
+                    //println!("[RenderSystem] Rendering with {:?}", draw_config);
+                    //println!("[RenderSystem] Transform: {:?}", *transform);
+
+                    g.draw(&mut transformed_state, draw_config).unwrap()
+                }
+            }
+        }
+
+        {
+            // update world view projection matrices for every shader in-use
+
+            for mut shader_instance in draw_batcher.mut_shader_instances() {
+                if let Some(wvp) = shader_instance.mut_world_view_projection_uniforms() {
+                    *wvp.mut_view() = view;
+                }
+            }
+        }
+
+        draw_batcher.flush().unwrap()
+    }
 }
 
 impl<V: Vertex + VertexPosition<Position = Vector2<f32>>> System for RenderSystem<V> {
     type Query<'q> = (
-        component::Query<'q, GraphicDisplayer<V>>,
-        component::Query<'q, Transform>,
+        GraphicsQuery<'q, V>,
+        component::Query<'q, Camera2D>,
     );
 
+    type Resources<'r> = ResMut<'r, Diagnostics>;
+
     fn setup(&mut self) {
     }
 
     fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
     }
 
-    fn run<'q>(&mut self, query: Self::Query<'q>, state: &mut FrameState) {
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, mut diagnostics: Self::Resources<'r>, _state: &mut FrameState) {
         /*
         println!(
             "[RenderSystem] captured components({}): {} GraphicDisplayer, {} Transform",
@@ -114,72 +259,79 @@ impl<V: Vertex + VertexPosition<Position = Vector2<f32>>> System for RenderSyste
 
         let mut adapter = graphic_adapter.borrow_mut();
 
-        match adapter.prepare_draw() {
-            Ok(mut draw_command) => {
-                // TODO  use default shader to clear screen
-
-                if let Some(shader) = self.default_shader.upgrade().get() {
-                    //let shader: std::cell::Ref<dyn ShaderInstance> = self.default_shader.borrow();
-                    //draw_command.clear::<_, Vertex2D, _>(Color::<u8>::rgb_hex(0x46236E), &shader)
-                    draw_command.clear(self.clear_color, &shader)
-                                .unwrap();
+        // with post processing, scene is drawn into an intermediate target
+        let scene_target = match self.post_process {
+            Some(ref mut post_process) if post_process.is_active() => {
+                match post_process.scene_target(&adapter, self.render_target.as_ref()) {
+                    Ok(scene_target) => Some(scene_target),
+                    Err(e) => {
+                        self.skip_frame(e);
+                        return;
+                    },
                 }
+            },
+            _ => None,
+        };
+
+        let mut draw_command = match scene_target.as_ref().or(self.render_target.as_ref()) {
+            Some(render_target) => adapter.prepare_draw_to(render_target),
+            None => match adapter.prepare_draw() {
+                Ok(draw_command) => draw_command,
+                Err(e) => {
+                    self.skip_frame(e);
+                    return;
+                },
+            },
+        };
 
-                // collects everything into a batcher
-                {
-                    let mut draw_batcher = DrawBatcher::new(&mut draw_command);
-
-                    //draw_batcher.register_shader(&self.default_shader);
-
-                    //println!("{} query components", query.iter_components().count());
-                    for QueryEntry { component: (a, b), .. } in query.iter_components() {
-                        if let Some(graphic_displayer) = a {
-                            if let Some(transform) = b {
-                                if let Some(ref g) = graphic_displayer.graphic {
-                                    let draw_config = DrawConfig {
-                                        vertex: V::from_position(transform.position()),
-                                        shader_config: graphic_displayer
-                                                        .shader_config
-                                                        .or_else(|| { Some(
-                                                            self.default_shader
-                                                                .upgrade()
-                                                                .get()?
-                                                                .default_config()
-                                                                .clone()
-                                                        ) } ),
-                                        texture_config: graphic_displayer
-                                                         .texture_config
-                                                         .or_else(|| Some(Default::default()))
-                                    };
-
-                                    //println!("[RenderSystem] Rendering with {:?}", draw_config);
-                                    //println!("[RenderSystem] Transform: {:?}", *transform);
-
-                                    g.draw(&mut draw_batcher, draw_config).unwrap()
-                                }
-                            }
-                        }
-                    }
+        draw_command.clear(self.clear_color);
 
-                    state.app.diagnostics.draw_calls += draw_batcher.batch_count();
+        let target_size = draw_command.target_size();
+        let (ref graphics, ref cameras) = query;
 
-                    {
-                        // update world view projection matrices for every shader in-use
+        let mut cameras: Vec<Camera2D> = cameras
+            .iter_components()
+            .filter(|entry| entry.component.active)
+            .map(|entry| (*entry.component).clone())
+            .collect();
 
-                        for mut shader_instance in draw_batcher.mut_shader_instances() {
-                            if let Some(wvp) = shader_instance.mut_world_view_projection_uniforms() {
-                                let view = wvp.mut_view();
-                                *view = self.view;
-                            }
-                        }
-                    }
+        cameras.sort_by_key(|camera| camera.order);
 
-                    draw_batcher.flush().unwrap();
-                }
+        // without cameras, `view` is used as is
+        if cameras.is_empty() {
+            diagnostics.draw_calls += self.draw_view(&mut draw_command, graphics, self.view, RenderLayers::ALL);
+        }
 
-                draw_command.present();
-            },
-            Err(_e) => return,
+        for camera in cameras {
+            draw_command.set_viewport(Some(camera.viewport_rect(target_size)));
+
+            diagnostics.draw_calls += self.draw_view(
+                &mut draw_command,
+                graphics,
+                camera.view_projection(target_size),
+                camera.layers,
+            );
+        }
+
+        if let Err(e) = draw_command.present() {
+            self.skip_frame(e);
+            return;
+        }
+
+        self.frame_skipped = false;
+
+        // effect passes aren't scene draw calls, so they aren't counted
+        if let (Some(_), Some(post_process)) = (scene_target, self.post_process.as_mut()) {
+            match post_process.apply(&mut adapter, self.render_target.as_ref()) {
+                Ok(_) => self.post_process_skipped = false,
+                Err(e) => {
+                    if !self.post_process_skipped {
+                        eprintln!("[RenderSystem] Post processing skipped: {}", e);
+                    }
+
+                    self.post_process_skipped = true;
+                },
+            }
         }
     }
 
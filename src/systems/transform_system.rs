@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::{
+    base::ApplicationState,
+    components::transform::Transform,
+    ecs::{
+        component::{
+            self,
+            BaseQuery,
+        },
+        entity::EntityId,
+        hierarchy::Parent,
+        system::System,
+        FrameState,
+    },
+    math::Affine2,
+};
+
+/// Caches every `Transform` global matrix and z, combining them with it's ancestors.
+/// It's registered by default at `SchedulerStep::Render`, before systems labeled `labels::RENDER`.
+///
+/// A parent without `Transform` breaks the chain, so it's children are placed relative to world.
+///
+/// [`SchedulerStep::Render`]: crate::ecs::SchedulerStep::Render
+/// [`labels::RENDER`]: crate::systems::labels::RENDER
+#[derive(Default)]
+pub struct TransformSystem;
+
+impl System for TransformSystem {
+    type Query<'q> = (
+        component::MutQuery<'q, Transform>,
+        component::Query<'q, Parent>,
+    );
+
+    type Resources<'r> = ();
+
+    fn setup(&mut self) {
+    }
+
+    fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
+    }
+
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, _resources: Self::Resources<'r>, _state: &mut FrameState) {
+        let (ref transforms, ref parents) = query;
+
+        let locals: HashMap<EntityId, Global> = transforms
+            .iter_components()
+            .map(|entry| (*entry.entity_id(), (entry.component.local_matrix(), entry.component.local_z)))
+            .collect();
+
+        let parents: HashMap<EntityId, EntityId> = parents
+            .iter_components()
+            .map(|entry| (*entry.entity_id(), entry.component.id()))
+            .collect();
+
+        let mut globals = HashMap::with_capacity(locals.len());
+
+        for mut entry in transforms.iter_components() {
+            let (matrix, z) = propagate(*entry.entity_id(), &locals, &parents, &mut globals);
+
+            // avoids flagging unchanged transforms
+            if entry.component.global_matrix() != matrix || entry.component.z() != z {
+                entry.component.set_global(matrix, z);
+            }
+        }
+    }
+
+    fn create_query<'q>(&self) -> Self::Query<'q> {
+        Self::Query::default()
+    }
+}
+
+/// Matrix and z.
+type Global = (Affine2, f32);
+
+/// Global matrix and z of an entity, caching every ancestor's on the way.
+fn propagate(
+    id: EntityId,
+    locals: &HashMap<EntityId, Global>,
+    parents: &HashMap<EntityId, EntityId>,
+    globals: &mut HashMap<EntityId, Global>,
+) -> Global {
+    let mut chain = Vec::new();
+    let mut global = (Affine2::IDENTITY, 0.0);
+    let mut current = Some(id);
+
+    while let Some(entity_id) = current {
+        if let Some(cached) = globals.get(&entity_id) {
+            global = *cached;
+            break;
+        }
+
+        if !locals.contains_key(&entity_id) {
+            break;
+        }
+
+        chain.push(entity_id);
+        current = parents.get(&entity_id).copied();
+    }
+
+    for entity_id in chain.into_iter().rev() {
+        let (matrix, z) = locals[&entity_id];
+        global = (global.0 * matrix, global.1 + z);
+        globals.insert(entity_id, global);
+    }
+
+    global
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use crate::{
+        ecs::{
+            command::Commands,
+            component::SystemTicks,
+            entity::Entities,
+        },
+        math::Vector2,
+        test_util::assert_near,
+        time::DeltaTime,
+    };
+
+    use super::*;
+
+    fn spawn(entities: &mut Entities, transform: Transform) -> EntityId {
+        let mut e = entities.create();
+        e.register_component(transform);
+        let id = e.id();
+        e.build();
+        id
+    }
+
+    #[test]
+    fn propagates_hierarchy() {
+        let mut entities = Entities::new();
+
+        let root = spawn(&mut entities, Transform::new(Vector2::new(10.0, 0.0)).with_scale(Vector2::new(2.0, 2.0)).with_z(1.0));
+        let child = spawn(&mut entities, Transform::new(Vector2::new(1.0, 0.0)).with_rotation(FRAC_PI_2));
+        let grandchild = spawn(&mut entities, Transform::new(Vector2::new(1.0, 0.0)).with_z(0.5));
+
+        assert!(entities.set_parent(grandchild, child));
+        assert!(entities.set_parent(child, root));
+
+        let mut system = TransformSystem;
+        let mut query = system.create_query();
+
+        for archetype in entities.archetypes() {
+            query.capture_archetype(archetype, SystemTicks::default());
+        }
+
+        let mut commands = Commands::new(&entities);
+        let mut state = FrameState {
+            delta: DeltaTime::new(Duration::ZERO),
+            app: None,
+            commands: &mut commands,
+        };
+
+        system.run(query, (), &mut state);
+
+        let transform_of = |id| entities.get(id).unwrap().get::<Transform>().unwrap().borrow().clone();
+
+        assert_near(transform_of(child).position(), Vector2::new(12.0, 0.0));
+        assert_near(transform_of(grandchild).position(), Vector2::new(12.0, 2.0));
+        assert_near(transform_of(grandchild).scale(), Vector2::new(2.0, 2.0));
+        assert_eq!(transform_of(grandchild).z(), 1.5);
+    }
+}
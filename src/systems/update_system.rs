@@ -24,7 +24,9 @@ impl<'a> UpdateSystem<'a> {
 }
 
 impl<'a> System for UpdateSystem<'a> {
-    type Query<'q> = component::FnQuery;
+    type Query<'q> = component::FnQuery<'q>;
+
+    type Resources<'r> = ();
 
     fn setup(&mut self) {
     }
@@ -32,7 +34,7 @@ impl<'a> System for UpdateSystem<'a> {
     fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
     }
 
-    fn run<'q>(&mut self, _query: Self::Query<'q>, _state: &mut FrameState) {
+    fn run<'q, 'r>(&mut self, _query: Self::Query<'q>, _resources: Self::Resources<'r>, _state: &mut FrameState) {
         //println!("[UpdateSystem] {} captured components", query.count());
 
         //for component_ref in query.iter_components() {
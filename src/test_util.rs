@@ -0,0 +1,104 @@
+//! Helpers shared by unit tests.
+
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use image::RgbaImage;
+
+use crate::{
+    math::{Matrix4x4, Rectangle, Size2, Vector2},
+    rendering::{
+        backend::DrawCommand,
+        capture::golden::{self, Tolerance},
+        graphics::Graphic,
+        Color,
+        DrawConfig,
+        GraphicAdapter,
+        RenderState,
+        RenderTarget,
+        ShaderConfig,
+        Vertex2DTextureColor,
+        VertexColor,
+    },
+    systems::render_system::default_shader::DefaultShader,
+};
+
+pub(crate) fn assert_near(a: Vector2<f32>, b: Vector2<f32>) {
+    assert!((a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4, "{a:?} != {b:?}");
+}
+
+/// Width and height of `TestScene` targets, it's view maps one unit to a pixel.
+pub(crate) const SCENE_SIZE: u32 = 16;
+
+/// Software adapter with a `DefaultShader` viewing `SCENE_SIZE` square targets.
+pub(crate) struct TestScene {
+    pub graphic_adapter: Rc<RefCell<GraphicAdapter>>,
+    pub shader: Rc<RefCell<DefaultShader>>,
+    pub shader_config: ShaderConfig,
+}
+
+impl TestScene {
+    pub fn new() -> Self {
+        let graphic_adapter = GraphicAdapter::software();
+
+        let shader = DefaultShader::new(&graphic_adapter);
+        shader.borrow_mut().uniforms_mut().view = Matrix4x4::ortho(SCENE_SIZE as f32, 0.0, 0.0, SCENE_SIZE as f32, -1.0, 1.0);
+
+        let shader_config = *shader.borrow().default_config();
+
+        Self {
+            graphic_adapter,
+            shader,
+            shader_config,
+        }
+    }
+
+    pub fn render_target(&self) -> RenderTarget {
+        self.graphic_adapter.borrow().create_render_target(Size2::new(SCENE_SIZE, SCENE_SIZE))
+    }
+
+    /// Clears target, lets `draw` record into it, then presents and captures it.
+    pub fn render<C, F>(&self, render_target: &RenderTarget, clear_color: C, draw: F) -> RgbaImage where
+        C: Into<Color<f32>>,
+        F: FnOnce(&mut DrawCommand<'_>),
+    {
+        {
+            let mut adapter = self.graphic_adapter.borrow_mut();
+            let mut draw_command = adapter.prepare_draw_to(render_target);
+            draw_command.clear(clear_color);
+
+            draw(&mut draw_command);
+
+            draw_command.present().unwrap();
+        }
+
+        self.graphic_adapter.borrow().capture(render_target).unwrap()
+    }
+}
+
+/// Draw config tinting every vertex with a color.
+pub(crate) fn colored(color: Color<f32>, shader_config: ShaderConfig) -> DrawConfig<Vertex2DTextureColor> {
+    DrawConfig {
+        vertex: Vertex2DTextureColor::default().with_color(color),
+        shader_config: Some(shader_config),
+        texture_config: Some(Default::default()),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn fill(
+    state: &mut dyn RenderState<Vertex2DTextureColor>,
+    rectangle: Rectangle<f32>,
+    draw_config: DrawConfig<Vertex2DTextureColor>,
+) {
+    Graphic::<Vertex2DTextureColor>::draw(&rectangle, state, draw_config).unwrap();
+}
+
+/// Checks against golden image `tests/golden/<name>.png`.
+pub(crate) fn check_golden(image: &RgbaImage, name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+        .with_extension("png");
+
+    golden::check(image, path, Tolerance::default()).unwrap();
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use super::TimeError;
+
+/// Drives `SchedulerStep::FixedUpdate`, which runs with a constant timestep.
+///
+/// Elapsed time is accumulated and consumed one timestep at a time, leftover
+/// is exposed as an interpolation alpha to be used at `SchedulerStep::Render`.
+///
+/// [`SchedulerStep::FixedUpdate`]: crate::ecs::SchedulerStep::FixedUpdate
+/// [`SchedulerStep::Render`]: crate::ecs::SchedulerStep::Render
+#[derive(Debug)]
+pub struct FixedTime {
+    timestep: Duration,
+    max_catch_up: u32,
+    accumulator: Duration,
+}
+
+impl FixedTime {
+    pub const DEFAULT_RATE: f64 = 60.0;
+    pub const DEFAULT_MAX_CATCH_UP: u32 = 5;
+
+    /// Steps per second, fails when it isn't positive or is too high.
+    pub fn with_rate(rate: f64) -> Result<Self, TimeError> {
+        Ok(Self::with_timestep(timestep(rate)?))
+    }
+
+    fn with_timestep(timestep: Duration) -> Self {
+        Self {
+            timestep,
+            max_catch_up: Self::DEFAULT_MAX_CATCH_UP,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.timestep
+    }
+
+    pub fn rate(&self) -> f64 {
+        1.0 / self.timestep.as_secs_f64()
+    }
+
+    /// Fails when it isn't positive, keeping current rate.
+    pub fn set_rate(&mut self, rate: f64) -> Result<(), TimeError> {
+        self.timestep = timestep(rate)?;
+        Ok(())
+    }
+
+    pub fn max_catch_up(&self) -> u32 {
+        self.max_catch_up
+    }
+
+    /// How many steps may run at a single frame, when falling behind.
+    pub fn set_max_catch_up(&mut self, max_catch_up: u32) {
+        self.max_catch_up = max_catch_up;
+    }
+
+    /// How far, in [0, 1), current time is between last and next step.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f64() / self.timestep.as_secs_f64()) as f32
+    }
+
+    /// Accumulates elapsed time and returns how many steps should run.
+    /// Time which exceeds max catch up is dropped.
+    pub(crate) fn accumulate(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+
+        let steps = (self.accumulator.as_secs_f64() / self.timestep.as_secs_f64()) as u32;
+
+        if steps > self.max_catch_up {
+            self.accumulator = Duration::from_secs_f64(
+                self.accumulator.as_secs_f64() % self.timestep.as_secs_f64()
+            );
+
+            return self.max_catch_up;
+        }
+
+        self.accumulator -= self.timestep * steps;
+        steps
+    }
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::with_timestep(Duration::from_secs_f64(1.0 / Self::DEFAULT_RATE))
+    }
+}
+
+/// Interval between each tick at a rate per second.
+/// Fails when rate is so high that interval is shorter than a nanosecond.
+pub(super) fn timestep(rate: f64) -> Result<Duration, TimeError> {
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(TimeError::InvalidRate(rate));
+    }
+
+    let timestep = Duration::from_secs_f64(1.0 / rate);
+
+    if timestep.is_zero() {
+        return Err(TimeError::InvalidRate(rate));
+    }
+
+    Ok(timestep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulate() {
+        let mut fixed_time = FixedTime::with_rate(10.0).unwrap();
+
+        assert_eq!(fixed_time.accumulate(Duration::from_millis(50)), 0);
+        assert!((fixed_time.alpha() - 0.5).abs() < 1e-4);
+
+        assert_eq!(fixed_time.accumulate(Duration::from_millis(175)), 2);
+        assert!((fixed_time.alpha() - 0.25).abs() < 1e-4);
+
+        // falling behind
+        fixed_time.set_max_catch_up(3);
+        assert_eq!(fixed_time.accumulate(Duration::from_millis(1000)), 3);
+        assert!((fixed_time.alpha() - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn invalid_rate() {
+        assert_eq!(FixedTime::with_rate(0.0).unwrap_err(), TimeError::InvalidRate(0.0));
+        assert!(FixedTime::with_rate(-1.0).is_err());
+        assert!(FixedTime::with_rate(f64::NAN).is_err());
+        assert!(FixedTime::with_rate(f64::INFINITY).is_err());
+        assert_eq!(FixedTime::with_rate(1e10).unwrap_err(), TimeError::InvalidRate(1e10));
+
+        let mut fixed_time = FixedTime::default();
+        assert!(fixed_time.set_rate(0.0).is_err());
+        assert!(fixed_time.set_rate(1e10).is_err());
+        assert_eq!(fixed_time.timestep(), FixedTime::default().timestep());
+
+        fixed_time.set_rate(30.0).unwrap();
+        assert!((fixed_time.rate() - 30.0).abs() < 1e-6);
+    }
+}
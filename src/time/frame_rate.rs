@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use super::{fixed_time::timestep, TimeError};
+
+/// Target rate which `SchedulerStep::Render` should run.
+///
+/// [`SchedulerStep::Render`]: crate::ecs::SchedulerStep::Render
+#[derive(Debug, Default)]
+pub struct FrameRate {
+    target: Option<f64>,
+    interval: Option<Duration>,
+}
+
+impl FrameRate {
+    /// Frames per second, renders as fast as possible when `None`.
+    /// Fails when it isn't positive.
+    pub fn with_target(target: Option<f64>) -> Result<Self, TimeError> {
+        Ok(Self {
+            target,
+            interval: target.map(timestep).transpose()?,
+        })
+    }
+
+    pub fn target(&self) -> Option<f64> {
+        self.target
+    }
+
+    /// Fails when it isn't positive, keeping current target.
+    pub fn set_target(&mut self, target: Option<f64>) -> Result<(), TimeError> {
+        *self = Self::with_target(target)?;
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// When next frame is due, `None` if there is no target.
+    pub fn next_frame(&self, last_render: Instant) -> Option<Instant> {
+        self.interval().map(|interval| last_render + interval)
+    }
+
+    /// Next frame is due, considering when last one was rendered.
+    pub fn is_due(&self, last_render: Instant, now: Instant) -> bool {
+        match self.interval() {
+            Some(interval) => now.duration_since(last_render) >= interval,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_target() {
+        assert!(FrameRate::with_target(Some(0.0)).is_err());
+        assert!(FrameRate::with_target(Some(-60.0)).is_err());
+        assert!(FrameRate::with_target(Some(1e10)).is_err());
+        assert!(FrameRate::with_target(None).unwrap().interval().is_none());
+
+        let mut frame_rate = FrameRate::with_target(Some(60.0)).unwrap();
+        assert!(frame_rate.set_target(Some(0.0)).is_err());
+        assert_eq!(frame_rate.target(), Some(60.0));
+    }
+}
@@ -1,23 +1,59 @@
+mod fixed_time;
+pub use fixed_time::FixedTime;
+
+mod frame_rate;
+pub use frame_rate::FrameRate;
+
+mod time_error;
+pub use time_error::TimeError;
+
 use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub struct Time {
     begin: Instant,
+
+    /// Elapsed time of a simulated clock, which only moves when advanced.
+    simulated: Option<Duration>,
 }
 
 impl Time {
     pub(crate) fn new() -> Self {
         Self {
             begin: Instant::now(),
+            simulated: None,
+        }
+    }
+
+    pub(crate) fn simulated() -> Self {
+        Self {
+            begin: Instant::now(),
+            simulated: Some(Duration::ZERO),
         }
     }
 
+    pub fn is_simulated(&self) -> bool {
+        self.simulated.is_some()
+    }
+
     pub fn now() -> Instant {
         Instant::now()
     }
 
     pub fn elapsed(&self) -> Duration {
-        self.begin.elapsed()
+        match self.simulated {
+            Some(elapsed) => elapsed,
+            None => self.begin.elapsed(),
+        }
+    }
+
+    /// Moves simulated clock forward.
+    pub(crate) fn advance(&mut self, duration: Duration) -> DeltaTime {
+        if let Some(ref mut elapsed) = self.simulated {
+            *elapsed += duration;
+        }
+
+        DeltaTime::new(duration)
     }
 
     pub fn delta(&self, last_instant: &mut Instant) -> DeltaTime {
@@ -0,0 +1,9 @@
+use thiserror::Error;
+use miette::Diagnostic;
+
+#[non_exhaustive]
+#[derive(Error, Diagnostic, Debug, PartialEq)]
+pub enum TimeError {
+    #[error("rate should be a positive finite number, below one step per nanosecond, but it's {0}")]
+    InvalidRate(f64),
+}
@@ -88,13 +88,15 @@ pub struct UISystem {
 impl System for UISystem {
     type Query<'q> = component::Query<'q, UI>;
 
+    type Resources<'r> = ();
+
     fn setup(&mut self) {
     }
 
     fn input<'q>(&mut self, _query: Self::Query<'q>, _state: &mut ApplicationState) {
     }
 
-    fn run<'q>(&mut self, query: Self::Query<'q>, _state: &mut FrameState) {
+    fn run<'q, 'r>(&mut self, query: Self::Query<'q>, _resources: Self::Resources<'r>, _state: &mut FrameState) {
         for QueryEntry { component: ui, .. } in query.iter_components() {
             if let Some(displayer_ref) = ui.displayer() {
                 let mut displayer = displayer_ref.borrow_mut();
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FullscreenMode {
+    #[default]
+    Windowed,
+
+    /// Fullscreen window, at current monitor's video mode.
+    Borderless,
+
+    /// Takes over monitor, using it's best video mode.
+    Exclusive,
+}
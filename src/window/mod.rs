@@ -1,29 +1,47 @@
+mod fullscreen_mode;
+pub use fullscreen_mode::FullscreenMode;
+
 mod window_error;
 pub use window_error::WindowError;
 
+use std::time::Instant;
+
 use raw_window_handle::{ HasRawDisplayHandle, HasRawWindowHandle };
 
 use winit::{
     dpi::{ LogicalSize, PhysicalSize },
     event_loop::{ ControlFlow, EventLoop },
+    window::Fullscreen,
 };
 
 use crate::math::Size2;
 
 pub struct WindowEventHandler {
-    pub(super) close_requested: bool
+    pub(super) close_requested: bool,
+    pub(super) control_flow: ControlFlow,
 }
 
 impl WindowEventHandler {
     pub fn new() -> Self {
         Self {
-            close_requested: false
+            close_requested: false,
+            control_flow: ControlFlow::Poll,
         }
     }
 
     pub fn request_close(&mut self) {
         self.close_requested = true
     }
+
+    /// Keep handling events without waiting.
+    pub fn poll(&mut self) {
+        self.control_flow = ControlFlow::Poll;
+    }
+
+    /// Sleep until instant, unless an event arrives before it.
+    pub fn wait_until(&mut self, instant: Instant) {
+        self.control_flow = ControlFlow::WaitUntil(instant);
+    }
 }
 
 pub struct WindowContext {
@@ -64,6 +82,7 @@ impl WindowContext {
         self.event_loop.set_control_flow(ControlFlow::Poll);
         self.event_loop.run(move |event, event_window_target| {
             handler(event, &mut self.event_handler);
+            event_window_target.set_control_flow(self.event_handler.control_flow);
 
             if self.event_handler.close_requested {
                 event_window_target.exit();
@@ -90,6 +109,53 @@ impl<'a> WindowBuilder<'a> {
         self
     }
 
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.builder = self.builder.with_title(title);
+        self
+    }
+
+    pub fn with_min_inner_size<S: Into<winit::dpi::Size>>(mut self, size: S) -> Self {
+        self.builder = self.builder.with_min_inner_size(size);
+        self
+    }
+
+    pub fn with_max_inner_size<S: Into<winit::dpi::Size>>(mut self, size: S) -> Self {
+        self.builder = self.builder.with_max_inner_size(size);
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.builder = self.builder.with_resizable(resizable);
+        self
+    }
+
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.builder = self.builder.with_decorations(decorations);
+        self
+    }
+
+    pub fn with_fullscreen(mut self, mode: FullscreenMode) -> Self {
+        let fullscreen = match mode {
+            FullscreenMode::Windowed => None,
+            FullscreenMode::Borderless => Some(Fullscreen::Borderless(None)),
+            FullscreenMode::Exclusive => {
+                self.context
+                    .event_loop
+                    .primary_monitor()
+                    .and_then(|monitor| monitor.video_modes().max_by_key(|video_mode| {
+                        let size = video_mode.size();
+                        (size.width * size.height, video_mode.refresh_rate_millihertz())
+                    }))
+                    .map(Fullscreen::Exclusive)
+                    // fallback when there is no monitor info
+                    .or(Some(Fullscreen::Borderless(None)))
+            },
+        };
+
+        self.builder = self.builder.with_fullscreen(fullscreen);
+        self
+    }
+
     pub fn build(self) -> Result<Window, &'static str> {
         match self.builder.build(&self.context.event_loop) {
             Ok(w) => {